@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use camino::{Utf8Path, Utf8PathBuf};
 use directories_next::ProjectDirs;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,10 @@ pub struct Config {
     /// override_api_key is used for overriding the API key returned
     /// when loading a profile
     pub override_api_key: Option<String>,
+
+    /// credential_passphrase encrypts and decrypts API keys kept in the
+    /// encrypted-file credential store
+    pub credential_passphrase: Option<String>,
 }
 
 impl Config {
@@ -59,9 +64,19 @@ impl Config {
         Ok(Config {
             home,
             override_api_key,
+            credential_passphrase: None,
         })
     }
 
+    /// Uses `credential_passphrase` for profiles whose API keys are in the encrypted-file
+    /// credential store
+    pub fn with_credential_passphrase(self, credential_passphrase: Option<String>) -> Config {
+        Config {
+            credential_passphrase,
+            ..self
+        }
+    }
+
     /// Removes all configuration files from filesystem
     pub fn clear(&self) -> Result<(), HoustonProblem> {
         tracing::debug!(home_dir = ?self.home);
@@ -92,6 +107,70 @@ impl Config {
     fn get_elv2_toml_path(&self) -> Utf8PathBuf {
         self.home.join("elv2_license.toml")
     }
+
+    /// Reads the settings in self.home.join("settings.toml"), which are all defaults
+    /// if the file doesn't exist
+    pub fn settings(&self) -> Result<Settings, HoustonProblem> {
+        let toml_path = self.get_settings_toml_path();
+        if !toml_path.exists() {
+            return Ok(Settings::default());
+        }
+        let contents = Fs::read_file(&toml_path)?;
+        toml::from_str(&contents)
+            .map_err(|e| HoustonProblem::AdhocError(anyhow!("Could not read {}: {}", toml_path, e)))
+    }
+
+    fn get_settings_toml_path(&self) -> Utf8PathBuf {
+        self.home.join("settings.toml")
+    }
+}
+
+/// Settings that apply to every profile, from `settings.toml` in the config home
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// How failed requests are retried, from the `[retry]` table
+    #[serde(default)]
+    pub retry: RetrySettings,
+
+    /// How requests reach the network, from the `[network]` table
+    #[serde(default)]
+    pub network: NetworkSettings,
+}
+
+/// How failed requests are retried. Anything left unset uses Rover's defaults.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetrySettings {
+    /// How many seconds to keep retrying a request for
+    pub max_elapsed_seconds: Option<u64>,
+
+    /// How many times to retry a request
+    pub max_retries: Option<u32>,
+
+    /// The HTTP statuses to retry requests for
+    pub statuses: Option<Vec<u16>>,
+}
+
+/// How requests reach the network: through which proxy, trusting which certificate
+/// authorities, and identifying with which client certificate.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// The URL of the proxy to send requests through, which may include credentials
+    pub proxy: Option<String>,
+
+    /// Hosts that requests are sent to directly instead of through the proxy
+    pub no_proxy: Option<Vec<String>>,
+
+    /// PEM files of certificate authorities to trust as well as the system's
+    pub ca_certs: Option<Vec<Utf8PathBuf>>,
+
+    /// The PEM file of the client certificate to identify with
+    pub client_cert: Option<Utf8PathBuf>,
+
+    /// The PEM file of the private key for `client_cert`
+    pub client_key: Option<Utf8PathBuf>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -101,7 +180,7 @@ struct Elv2Toml {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, Settings};
     use assert_fs::TempDir;
     use camino::Utf8PathBuf;
     use std::convert::TryFrom;
@@ -114,4 +193,35 @@ mod tests {
         config.clear().unwrap();
         assert!(!config.home.exists());
     }
+
+    #[test]
+    fn it_reads_settings() {
+        let tmp_home = TempDir::new().unwrap();
+        let tmp_path = Utf8PathBuf::try_from(tmp_home.path().to_path_buf()).unwrap();
+        let config = Config::new(Some(&tmp_path), None).unwrap();
+        assert_eq!(config.settings().unwrap(), Settings::default());
+
+        std::fs::write(
+            tmp_path.join("settings.toml"),
+            "[retry]\nmax_retries = 2\nstatuses = [429, 503]\n",
+        )
+        .unwrap();
+        let retry = config.settings().unwrap().retry;
+        assert_eq!(retry.max_retries, Some(2));
+        assert_eq!(retry.statuses, Some(vec![429, 503]));
+        assert_eq!(retry.max_elapsed_seconds, None);
+
+        std::fs::write(
+            tmp_path.join("settings.toml"),
+            "[network]\nproxy = \"http://proxy:3128\"\nno_proxy = [\"localhost\"]\n",
+        )
+        .unwrap();
+        let network = config.settings().unwrap().network;
+        assert_eq!(network.proxy.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(network.no_proxy, Some(vec!["localhost".to_string()]));
+        assert_eq!(network.ca_certs, None);
+
+        std::fs::write(tmp_path.join("settings.toml"), "[retry]\nretries = 2\n").unwrap();
+        assert!(config.settings().is_err());
+    }
 }
@@ -38,6 +38,43 @@ pub enum HoustonProblem {
     #[error("The API key associated with profile \"{0}\" is corrupt.")]
     CorruptedProfile(String),
 
+    /// CredentialPassphraseRequired occurs when a profile's API key is in the encrypted-file
+    /// credential store and no passphrase was given.
+    #[error("The API key for profile \"{0}\" is encrypted, and no passphrase was provided.")]
+    CredentialPassphraseRequired(String),
+
+    /// CredentialDecryptionFailed occurs when an encrypted API key can't be decrypted with the
+    /// passphrase given, usually because it's the wrong one.
+    #[error("Could not decrypt the API key for profile \"{0}\".")]
+    CredentialDecryptionFailed(String),
+
+    /// CredentialHelperFailed occurs when a credential helper can't be run or reports a failure.
+    #[error("The credential helper \"{helper}\" failed because {reason}.")]
+    CredentialHelperFailed {
+        /// The credential helper's name or path
+        helper: String,
+        /// What went wrong
+        reason: String,
+    },
+
+    /// ProfileAlreadyExists occurs when importing a profile that would replace an existing one.
+    #[error("There is already a profile named \"{0}\".")]
+    ProfileAlreadyExists(String),
+
+    /// InvalidProfileName occurs when a profile name can't be used as a directory name or
+    /// sent to a credential helper, such as when it's empty or contains a path separator.
+    #[error("\"{0}\" is not a valid profile name.")]
+    InvalidProfileName(String),
+
+    /// InvalidProfileSetting occurs when a profile setting doesn't exist or is given an invalid value.
+    #[error("Could not set \"{key}\" because {reason}.")]
+    InvalidProfileSetting {
+        /// The name of the setting
+        key: String,
+        /// What's wrong with it
+        reason: String,
+    },
+
     /// PathNotUtf8 occurs when Houston encounters a file path that is not valid UTF-8
     #[error(transparent)]
     PathNotUtf8(#[from] camino::FromPathBufError),
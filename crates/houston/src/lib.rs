@@ -6,9 +6,12 @@ mod config;
 mod error;
 mod profile;
 
-pub use config::Config;
+pub use config::{Config, NetworkSettings, RetrySettings, Settings};
 pub use error::HoustonProblem;
 
 pub use profile::mask_key;
 /// Utilites for saving, loading, and deleting configuration profiles.
-pub use profile::{Credential, CredentialOrigin, LoadOpts, Profile};
+pub use profile::{
+    Credential, CredentialOrigin, CredentialStore, ExportKeys, LoadOpts, Profile, ProfileExport,
+    ProfileSettings,
+};
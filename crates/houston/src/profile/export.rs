@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profile::{sensitive::passphrase, store::EncryptedApiKey, Profile},
+    Config, CredentialStore, HoustonProblem, ProfileSettings,
+};
+
+/// The version of the export format this version of houston writes and reads
+const EXPORT_VERSION: u32 = 1;
+
+/// How API keys are included in a [`ProfileExport`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKeys {
+    /// API keys are left out, so each profile has to be authenticated again after importing it
+    Exclude,
+
+    /// API keys are included in plain text
+    Plaintext,
+
+    /// API keys are encrypted with the config's credential passphrase
+    Encrypted,
+}
+
+/// Profiles and their settings, written as TOML to move them to another machine
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileExport {
+    version: u32,
+
+    #[serde(default)]
+    profiles: BTreeMap<String, ExportedProfile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExportedProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encrypted_api_key: Option<EncryptedApiKey>,
+
+    #[serde(default)]
+    settings: ProfileSettings,
+}
+
+impl ProfileExport {
+    /// Exports the profiles called `names`, with their API keys included as `keys` says
+    pub fn new(
+        names: &[String],
+        config: &Config,
+        keys: ExportKeys,
+    ) -> Result<ProfileExport, HoustonProblem> {
+        let mut profiles = BTreeMap::new();
+        for name in names {
+            let mut profile = ExportedProfile {
+                settings: Profile::settings(name, config)?,
+                ..Default::default()
+            };
+            match keys {
+                ExportKeys::Exclude => {}
+                ExportKeys::Plaintext => {
+                    profile.api_key = Some(Profile::get_api_key(name, config)?)
+                }
+                ExportKeys::Encrypted => {
+                    profile.encrypted_api_key = Some(EncryptedApiKey::encrypt(
+                        &Profile::get_api_key(name, config)?,
+                        passphrase(name, config)?,
+                    )?)
+                }
+            }
+            profiles.insert(name.clone(), profile);
+        }
+        Ok(ProfileExport {
+            version: EXPORT_VERSION,
+            profiles,
+        })
+    }
+
+    /// Reads an export written by [`ProfileExport::to_toml`]
+    pub fn from_toml(contents: &str) -> Result<ProfileExport, HoustonProblem> {
+        let export: ProfileExport = toml::from_str(contents)?;
+        if export.version != EXPORT_VERSION {
+            return Err(HoustonProblem::AdhocError(anyhow!(
+                "Profile exports in version {} of the format can't be imported, only version {}",
+                export.version,
+                EXPORT_VERSION
+            )));
+        }
+        Ok(export)
+    }
+
+    /// Writes this export as TOML
+    pub fn to_toml(&self) -> Result<String, HoustonProblem> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// The name of every profile in this export, and whether its API key is included
+    pub fn profiles(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.profiles.iter().map(|(name, profile)| {
+            (
+                name.as_str(),
+                profile.api_key.is_some() || profile.encrypted_api_key.is_some(),
+            )
+        })
+    }
+
+    /// Creates every profile in this export, keeping the API keys it includes in `store`.
+    /// Existing profiles are only replaced if `overwrite` is true. Nothing is imported
+    /// unless every profile can be.
+    pub fn import(
+        &self,
+        config: &Config,
+        store: &CredentialStore,
+        overwrite: bool,
+    ) -> Result<(), HoustonProblem> {
+        let mut api_keys = BTreeMap::new();
+        for (name, profile) in &self.profiles {
+            // profile names are directory names, so they can't point outside the profiles
+            // directory, and they're sent to credential helpers as `profile=<name>` lines
+            let is_reserved = |c: char| c.is_control() || matches!(c, '/' | '\\' | '=');
+            if name.is_empty() || name == "." || name == ".." || name.contains(is_reserved) {
+                return Err(HoustonProblem::InvalidProfileName(name.clone()));
+            }
+            if !overwrite && Profile::dir(name, config).exists() {
+                return Err(HoustonProblem::ProfileAlreadyExists(name.clone()));
+            }
+            let api_key = match (&profile.api_key, &profile.encrypted_api_key) {
+                (Some(api_key), _) => Some(api_key.clone()),
+                (None, Some(encrypted_api_key)) => {
+                    Some(encrypted_api_key.decrypt(passphrase(name, config)?, name)?)
+                }
+                (None, None) => None,
+            };
+            api_keys.insert(name, api_key);
+        }
+
+        for (name, profile) in &self.profiles {
+            match &api_keys[name] {
+                Some(api_key) => Profile::set_api_key_in(name, config, api_key, store)?,
+                None => Fs::create_dir_all(Profile::dir(name, config))?,
+            }
+            Profile::save_settings(name, config, &profile.settings)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use camino::Utf8Path;
+
+    use super::*;
+
+    fn config(home: &Utf8Path) -> Config {
+        Config::new(Some(&home), None)
+            .unwrap()
+            .with_credential_passphrase(Some("correct horse".to_string()))
+    }
+
+    #[test]
+    fn it_round_trips_profiles_through_an_export() {
+        let source_home = TempDir::new().unwrap();
+        let source = config(Utf8Path::from_path(source_home.path()).unwrap());
+        let api_key = "user:gh.foo:djru4788dhsg3657fhLOLO";
+        Profile::set_api_key("staging", &source, api_key).unwrap();
+        let mut settings = ProfileSettings::default();
+        settings.set("graph_ref", "my-graph@staging").unwrap();
+        Profile::save_settings("staging", &source, &settings).unwrap();
+
+        let names = vec!["staging".to_string()];
+        let export = ProfileExport::new(&names, &source, ExportKeys::Encrypted).unwrap();
+        let contents = export.to_toml().unwrap();
+        assert!(!contents.contains("djru4788"));
+
+        let destination_home = TempDir::new().unwrap();
+        let destination = config(Utf8Path::from_path(destination_home.path()).unwrap());
+        let export = ProfileExport::from_toml(&contents).unwrap();
+        assert_eq!(
+            export.profiles().collect::<Vec<_>>(),
+            vec![("staging", true)]
+        );
+        export
+            .import(&destination, &CredentialStore::File, false)
+            .unwrap();
+        assert_eq!(
+            Profile::get_api_key("staging", &destination).unwrap(),
+            api_key
+        );
+        assert_eq!(
+            Profile::settings("staging", &destination).unwrap(),
+            settings
+        );
+
+        assert!(matches!(
+            export.import(&destination, &CredentialStore::File, false),
+            Err(HoustonProblem::ProfileAlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn it_authenticates_profiles_imported_without_keys() {
+        let home = TempDir::new().unwrap();
+        let config = config(Utf8Path::from_path(home.path()).unwrap());
+        let export = ProfileExport::from_toml(
+            "version = 1\n\n[profiles.staging.settings]\ngraph_ref = \"my-graph@staging\"\n",
+        )
+        .unwrap();
+        export
+            .import(&config, &CredentialStore::File, false)
+            .unwrap();
+        assert_eq!(
+            Profile::credential_store("staging", &config).unwrap(),
+            CredentialStore::default()
+        );
+
+        Profile::set_api_key("staging", &config, "service:my-graph:abc123").unwrap();
+        assert_eq!(
+            Profile::get_api_key("staging", &config).unwrap(),
+            "service:my-graph:abc123"
+        );
+    }
+
+    #[test]
+    fn it_rejects_profile_names_that_cant_be_used() {
+        let home = TempDir::new().unwrap();
+        let home = Utf8Path::from_path(home.path()).unwrap();
+        let config = config(&home.join("config"));
+        for name in [
+            "",
+            "..",
+            "../escaped",
+            "nested/profile",
+            "nested\\\\profile",
+            "staging\\napi_key=service:other:key",
+            "staging\\r",
+            "profile=staging",
+        ] {
+            let export = ProfileExport::from_toml(&format!(
+                "version = 1\n\n[profiles.\"{}\".settings]\n",
+                name
+            ))
+            .unwrap();
+            assert!(matches!(
+                export.import(&config, &CredentialStore::File, true),
+                Err(HoustonProblem::InvalidProfileName(_))
+            ));
+        }
+        assert!(!home.join("config").join("escaped").exists());
+        assert!(!home.join("config").join("profiles").exists());
+    }
+
+    #[test]
+    fn it_exports_profiles_without_keys() {
+        let home = TempDir::new().unwrap();
+        let config = config(Utf8Path::from_path(home.path()).unwrap());
+        Profile::set_api_key("default", &config, "service:my-graph:abc123").unwrap();
+
+        let names = vec!["default".to_string()];
+        let export = ProfileExport::new(&names, &config, ExportKeys::Exclude).unwrap();
+        assert!(!export.to_toml().unwrap().contains("abc123"));
+        assert_eq!(
+            export.profiles().collect::<Vec<_>>(),
+            vec![("default", false)]
+        );
+    }
+}
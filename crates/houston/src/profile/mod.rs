@@ -1,15 +1,21 @@
+mod export;
 mod sensitive;
+mod settings;
+mod store;
 
 use crate::{Config, HoustonProblem};
+pub use export::{ExportKeys, ProfileExport};
 use sensitive::Sensitive;
-use serde::{Deserialize, Serialize};
+pub use settings::ProfileSettings;
+use store::CredentialHelper;
+pub use store::CredentialStore;
 
 use camino::Utf8PathBuf as PathBuf;
 use rover_std::Fs;
 use std::fmt;
 
 /// Collects configuration related to a profile.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
 pub struct Profile {
     sensitive: Sensitive,
 }
@@ -54,7 +60,8 @@ impl Profile {
         Profile::base_dir(config).join(name)
     }
 
-    /// Writes an api_key to the filesystem (`$APOLLO_CONFIG_HOME/profiles/<profile_name>/.sensitive`).
+    /// Stores an api_key in the profile's current [`CredentialStore`], which is the
+    /// plaintext `$APOLLO_CONFIG_HOME/profiles/<profile_name>/.sensitive` file for new profiles.
     pub fn set_api_key(name: &str, config: &Config, api_key: &str) -> Result<(), HoustonProblem> {
         let data = ProfileData {
             api_key: Some(api_key.to_string()),
@@ -63,6 +70,57 @@ impl Profile {
         Ok(())
     }
 
+    /// Stores an api_key in `store`, erasing it from the credential helper it was kept in
+    /// before, if any. A helper that fails to erase it doesn't stop it from being stored.
+    pub fn set_api_key_in(
+        name: &str,
+        config: &Config,
+        api_key: &str,
+        store: &CredentialStore,
+    ) -> Result<(), HoustonProblem> {
+        let previous_store = Profile::credential_store(name, config)?;
+        Sensitive {
+            api_key: api_key.to_string(),
+            store: store.clone(),
+        }
+        .save(name, config)?;
+        if let CredentialStore::Helper(helper) = &previous_store {
+            if previous_store != *store {
+                if let Err(e) = CredentialHelper::new(helper).erase(name) {
+                    tracing::warn!("could not erase the API key for profile {}: {}", name, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns which [`CredentialStore`] a profile's API key is kept in, without retrieving it.
+    /// Profiles that don't have an API key yet use the default store.
+    pub fn credential_store(
+        name: &str,
+        config: &Config,
+    ) -> Result<CredentialStore, HoustonProblem> {
+        if Sensitive::path(name, config).exists() {
+            Sensitive::load_store(name, config)
+        } else {
+            Ok(CredentialStore::default())
+        }
+    }
+
+    /// Moves a profile's API key to `store`, returning the store it was kept in before.
+    pub fn migrate_api_key(
+        name: &str,
+        config: &Config,
+        store: &CredentialStore,
+    ) -> Result<CredentialStore, HoustonProblem> {
+        let profile = Profile::load(name, config, LoadOpts { sensitive: true })?;
+        let previous_store = profile.sensitive.store.clone();
+        if previous_store != *store {
+            Profile::set_api_key_in(name, config, &profile.sensitive.api_key, store)?;
+        }
+        Ok(previous_store)
+    }
+
     /// Returns an API key for interacting with Apollo services.
     ///
     /// Checks for the presence of an `APOLLO_KEY` env var, and returns its value
@@ -90,11 +148,53 @@ impl Profile {
         Ok(credential)
     }
 
+    /// Returns the API key stored for a profile, even when an `APOLLO_KEY` env var is set.
+    pub fn get_api_key(name: &str, config: &Config) -> Result<String, HoustonProblem> {
+        let profile = Profile::load(name, config, LoadOpts { sensitive: true })?;
+        Ok(profile.sensitive.api_key)
+    }
+
+    /// Reads a profile's non-sensitive settings from
+    /// `$APOLLO_CONFIG_HOME/profiles/<profile_name>/profile.toml`, which are all unset if
+    /// the file doesn't exist.
+    pub fn settings(name: &str, config: &Config) -> Result<ProfileSettings, HoustonProblem> {
+        if !Profile::dir(name, config).exists() {
+            return Err(Profile::not_found(name, config));
+        }
+        let path = Profile::settings_path(name, config);
+        if !path.exists() {
+            return Ok(ProfileSettings::default());
+        }
+        let contents = Fs::read_file(&path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Saves a profile's non-sensitive settings to
+    /// `$APOLLO_CONFIG_HOME/profiles/<profile_name>/profile.toml`.
+    pub fn save_settings(
+        name: &str,
+        config: &Config,
+        settings: &ProfileSettings,
+    ) -> Result<(), HoustonProblem> {
+        if !Profile::dir(name, config).exists() {
+            return Err(Profile::not_found(name, config));
+        }
+        let path = Profile::settings_path(name, config);
+        Fs::write_file(&path, toml::to_string(settings)?)?;
+        tracing::debug!(path = ?path, ?settings);
+        Ok(())
+    }
+
+    fn settings_path(name: &str, config: &Config) -> PathBuf {
+        Profile::dir(name, config).join("profile.toml")
+    }
+
     /// Saves configuration options for a specific profile to the file system,
     /// splitting sensitive information into a separate file.
     pub fn save(name: &str, config: &Config, data: ProfileData) -> Result<(), HoustonProblem> {
         if let Some(api_key) = data.api_key {
-            Sensitive { api_key }.save(name, config)?;
+            let store = Profile::credential_store(name, config)?;
+            Sensitive { api_key, store }.save(name, config)?;
         }
         Ok(())
     }
@@ -115,13 +215,19 @@ impl Profile {
                 profile_name.to_string(),
             ))
         } else {
-            let profiles_base_dir = Profile::base_dir(config);
-            let mut base_dir_contents = Fs::get_dir_entries(profiles_base_dir)
-                .map_err(|_| HoustonProblem::NoConfigProfiles)?;
-            if base_dir_contents.next().is_none() {
-                return Err(HoustonProblem::NoConfigProfiles);
-            }
-            Err(HoustonProblem::ProfileNotFound(profile_name.to_string()))
+            Err(Profile::not_found(profile_name, config))
+        }
+    }
+
+    /// The error for a profile that doesn't exist, which differs when no profiles exist at all
+    fn not_found(profile_name: &str, config: &Config) -> HoustonProblem {
+        let profiles_base_dir = Profile::base_dir(config);
+        match Fs::get_dir_entries(profiles_base_dir) {
+            Ok(base_dir_contents) => match base_dir_contents.into_iter().next() {
+                Some(_) => HoustonProblem::ProfileNotFound(profile_name.to_string()),
+                None => HoustonProblem::NoConfigProfiles,
+            },
+            Err(_) => HoustonProblem::NoConfigProfiles,
         }
     }
 
@@ -129,6 +235,12 @@ impl Profile {
     pub fn delete(name: &str, config: &Config) -> Result<(), HoustonProblem> {
         let dir = Profile::dir(name, config);
         tracing::debug!(dir = ?dir);
+        if let Ok(CredentialStore::Helper(helper)) = Sensitive::load_store(name, config) {
+            // a helper that can't erase the key shouldn't stop the profile from being deleted
+            if let Err(e) = CredentialHelper::new(&helper).erase(name) {
+                tracing::warn!("could not erase the API key for profile {}: {}", name, e);
+            }
+        }
         Fs::remove_dir_all(dir)?;
         Ok(())
     }
@@ -180,7 +292,109 @@ pub fn mask_key(key: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::mask_key;
+    use assert_fs::TempDir;
+    use camino::Utf8Path;
+
+    use super::{mask_key, CredentialStore, Profile};
+    use crate::{Config, HoustonProblem};
+
+    const API_KEY: &str = "user:gh.foo:djru4788dhsg3657fhLOLO";
+
+    fn get_config(home: &TempDir) -> Config {
+        let home = Utf8Path::from_path(home.path()).unwrap();
+        Config::new(Some(&home), None).unwrap()
+    }
+
+    #[test]
+    fn it_reads_plaintext_profiles_from_before_credential_stores() {
+        let home = TempDir::new().unwrap();
+        let config = get_config(&home);
+        let dir = Profile::dir("default", &config);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".sensitive"), format!("api_key = \"{API_KEY}\"\n")).unwrap();
+
+        assert_eq!(
+            Profile::credential_store("default", &config).unwrap(),
+            CredentialStore::File
+        );
+        let credential = Profile::get_credential("default", &config).unwrap();
+        assert_eq!(credential.api_key, API_KEY);
+    }
+
+    #[test]
+    fn it_migrates_api_keys_to_the_encrypted_file_store() {
+        let home = TempDir::new().unwrap();
+        let config = get_config(&home);
+        Profile::set_api_key("default", &config, API_KEY).unwrap();
+
+        assert!(matches!(
+            Profile::migrate_api_key("default", &config, &CredentialStore::EncryptedFile),
+            Err(HoustonProblem::CredentialPassphraseRequired(_))
+        ));
+
+        let config = config.with_credential_passphrase(Some("correct horse".to_string()));
+        let previous_store =
+            Profile::migrate_api_key("default", &config, &CredentialStore::EncryptedFile).unwrap();
+        assert_eq!(previous_store, CredentialStore::File);
+        let sensitive =
+            std::fs::read_to_string(Profile::dir("default", &config).join(".sensitive")).unwrap();
+        assert!(!sensitive.contains(API_KEY));
+        assert_eq!(
+            Profile::get_credential("default", &config).unwrap().api_key,
+            API_KEY
+        );
+
+        // new keys stay in the profile's store
+        Profile::set_api_key("default", &config, "service:foo:newkey").unwrap();
+        assert_eq!(
+            Profile::credential_store("default", &config).unwrap(),
+            CredentialStore::EncryptedFile
+        );
+
+        let config = config.with_credential_passphrase(Some("battery staple".to_string()));
+        assert!(matches!(
+            Profile::get_credential("default", &config),
+            Err(HoustonProblem::CredentialDecryptionFailed(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_stores_api_keys_with_credential_helpers() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let home = TempDir::new().unwrap();
+        let config = get_config(&home);
+        let helper = home.path().join("rover-credential-test");
+        let keys = home.path().join("keys");
+        std::fs::write(
+            &helper,
+            format!(
+                "#!/bin/sh\nkeys={}\ncase \"$1\" in\n  get) cat \"$keys\" ;;\n  store) grep '^api_key=' > \"$keys\" ;;\n  erase) rm -f \"$keys\" ;;\nesac\n",
+                keys.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let store = CredentialStore::Helper(helper.to_str().unwrap().to_string());
+
+        Profile::set_api_key_in("default", &config, API_KEY, &store).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&keys).unwrap(),
+            format!("api_key={API_KEY}\n")
+        );
+        assert_eq!(
+            Profile::get_credential("default", &config).unwrap().api_key,
+            API_KEY
+        );
+
+        Profile::migrate_api_key("default", &config, &CredentialStore::File).unwrap();
+        assert!(!keys.exists());
+        assert_eq!(
+            Profile::get_credential("default", &config).unwrap().api_key,
+            API_KEY
+        );
+    }
 
     #[test]
     fn it_can_mask_user_key() {
@@ -1,4 +1,8 @@
-use crate::{profile::Profile, Config, HoustonProblem};
+use crate::{
+    profile::store::{CredentialHelper, EncryptedApiKey},
+    profile::Profile,
+    Config, CredentialStore, HoustonProblem,
+};
 use rover_std::Fs;
 
 use std::fmt;
@@ -7,44 +11,124 @@ use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
 
 /// Holds sensitive information regarding authentication.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
 pub struct Sensitive {
     pub api_key: String,
+    pub store: CredentialStore,
+}
+
+/// The contents of a `.sensitive` file. Only one of the fields is set, depending on the
+/// profile's [`CredentialStore`]; files written before stores existed only have `api_key`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SensitiveFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encrypted_api_key: Option<EncryptedApiKey>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credential_helper: Option<String>,
+}
+
+impl SensitiveFile {
+    fn store(&self) -> CredentialStore {
+        match (&self.credential_helper, &self.encrypted_api_key) {
+            (Some(helper), _) => CredentialStore::Helper(helper.clone()),
+            (None, Some(_)) => CredentialStore::EncryptedFile,
+            (None, None) => CredentialStore::File,
+        }
+    }
 }
 
 impl Sensitive {
-    fn path(profile_name: &str, config: &Config) -> Utf8PathBuf {
+    pub(crate) fn path(profile_name: &str, config: &Config) -> Utf8PathBuf {
         Profile::dir(profile_name, config).join(".sensitive")
     }
 
-    /// Serializes to toml and saves to file system at `$APOLLO_CONFIG_HOME/<profile_name>/.sensitive`.
+    /// Stores the API key with its [`CredentialStore`], and saves a record of where it went
+    /// to `$APOLLO_CONFIG_HOME/<profile_name>/.sensitive`.
     pub fn save(&self, profile_name: &str, config: &Config) -> Result<(), HoustonProblem> {
         let path = Sensitive::path(profile_name, config);
-        let data = toml::to_string(self)?;
+        let file = match &self.store {
+            CredentialStore::File => SensitiveFile {
+                api_key: Some(self.api_key.clone()),
+                ..Default::default()
+            },
+            CredentialStore::EncryptedFile => {
+                let passphrase = passphrase(profile_name, config)?;
+                SensitiveFile {
+                    encrypted_api_key: Some(EncryptedApiKey::encrypt(&self.api_key, passphrase)?),
+                    ..Default::default()
+                }
+            }
+            CredentialStore::Helper(helper) => {
+                CredentialHelper::new(helper).store(profile_name, &self.api_key)?;
+                SensitiveFile {
+                    credential_helper: Some(helper.clone()),
+                    ..Default::default()
+                }
+            }
+        };
+        let data = toml::to_string(&file)?;
 
         if let Some(dirs) = &path.parent() {
             Fs::create_dir_all(dirs)?;
         }
 
         Fs::write_file(&path, &data)?;
-        tracing::debug!(path = ?path, data_len = ?data.len());
+        tracing::debug!(path = ?path, data_len = ?data.len(), store = %self.store);
         Ok(())
     }
 
-    /// Opens and deserializes `$APOLLO_CONFIG_HOME/<profile_name>/.sensitive`.
+    /// Opens `$APOLLO_CONFIG_HOME/<profile_name>/.sensitive` and retrieves the API key
+    /// from wherever it says it's stored.
     pub fn load(profile_name: &str, config: &Config) -> Result<Sensitive, HoustonProblem> {
-        let path = Sensitive::path(profile_name, config);
-        let data = Fs::read_file(&path)?;
-        tracing::debug!(path = ?path, data_len = ?data.len());
-        let sensitive: Self = toml::from_str(&data)?;
+        let file = Sensitive::load_file(profile_name, config)?;
+        let store = file.store();
+        let api_key = match (&store, file.api_key, file.encrypted_api_key) {
+            (CredentialStore::Helper(helper), ..) => {
+                CredentialHelper::new(helper).get(profile_name)?
+            }
+            (CredentialStore::EncryptedFile, _, Some(encrypted_api_key)) => {
+                encrypted_api_key.decrypt(passphrase(profile_name, config)?, profile_name)?
+            }
+            (_, Some(api_key), _) => api_key,
+            _ => return Err(HoustonProblem::CorruptedProfile(profile_name.to_string())),
+        };
         // old versions of rover used to allow profiles to be created
         // with these contents in certain PowerShell environments
-        if sensitive.api_key.as_bytes() == [22] {
+        if api_key.as_bytes() == [22] {
             Err(HoustonProblem::CorruptedProfile(profile_name.to_string()))
         } else {
-            Ok(sensitive)
+            Ok(Sensitive { api_key, store })
         }
     }
+
+    /// Reads which [`CredentialStore`] a profile's API key is in, without retrieving it.
+    pub fn load_store(
+        profile_name: &str,
+        config: &Config,
+    ) -> Result<CredentialStore, HoustonProblem> {
+        Ok(Sensitive::load_file(profile_name, config)?.store())
+    }
+
+    fn load_file(profile_name: &str, config: &Config) -> Result<SensitiveFile, HoustonProblem> {
+        let path = Sensitive::path(profile_name, config);
+        let data = Fs::read_file(&path)?;
+        tracing::debug!(path = ?path, data_len = ?data.len());
+        Ok(toml::from_str(&data)?)
+    }
+}
+
+pub(crate) fn passphrase<'a>(
+    profile_name: &str,
+    config: &'a Config,
+) -> Result<&'a str, HoustonProblem> {
+    config
+        .credential_passphrase
+        .as_deref()
+        .ok_or_else(|| HoustonProblem::CredentialPassphraseRequired(profile_name.to_string()))
 }
 
 impl fmt::Display for Sensitive {
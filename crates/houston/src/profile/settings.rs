@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use crate::HoustonProblem;
+
+/// Non-secret defaults for commands run with a profile, from `profile.toml` in the
+/// profile's directory. Commands only use these where the corresponding flag isn't passed.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileSettings {
+    /// The `<NAME>@<VARIANT>` of the graph to use when none is passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_ref: Option<String>,
+
+    /// The GraphOS API endpoint to send requests to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_url: Option<String>,
+
+    /// How many seconds to wait for HTTP requests to finish
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_timeout: Option<u64>,
+
+    /// The URL of the proxy to send requests through
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// The version of Apollo Federation to compose supergraphs with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub federation_version: Option<String>,
+}
+
+impl ProfileSettings {
+    /// The name of every setting, as it appears in `profile.toml`
+    pub const KEYS: [&'static str; 5] = [
+        "graph_ref",
+        "registry_url",
+        "client_timeout",
+        "proxy",
+        "federation_version",
+    ];
+
+    /// Returns the value of the setting named `key`, if it's set
+    pub fn get(&self, key: &str) -> Result<Option<String>, HoustonProblem> {
+        match key {
+            "graph_ref" => Ok(self.graph_ref.clone()),
+            "registry_url" => Ok(self.registry_url.clone()),
+            "client_timeout" => Ok(self.client_timeout.map(|seconds| seconds.to_string())),
+            "proxy" => Ok(self.proxy.clone()),
+            "federation_version" => Ok(self.federation_version.clone()),
+            key => Err(not_a_setting(key)),
+        }
+    }
+
+    /// Sets the setting named `key` to `value`
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), HoustonProblem> {
+        match key {
+            "client_timeout" => {
+                let seconds =
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| HoustonProblem::InvalidProfileSetting {
+                            key: key.to_string(),
+                            reason: format!("'{value}' is not a whole number of seconds"),
+                        })?;
+                self.client_timeout = Some(seconds);
+            }
+            key => *self.string_setting(key)? = Some(value.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Unsets the setting named `key`, so commands use their usual default for it
+    pub fn unset(&mut self, key: &str) -> Result<(), HoustonProblem> {
+        match key {
+            "client_timeout" => self.client_timeout = None,
+            key => *self.string_setting(key)? = None,
+        }
+        Ok(())
+    }
+
+    fn string_setting(&mut self, key: &str) -> Result<&mut Option<String>, HoustonProblem> {
+        match key {
+            "graph_ref" => Ok(&mut self.graph_ref),
+            "registry_url" => Ok(&mut self.registry_url),
+            "proxy" => Ok(&mut self.proxy),
+            "federation_version" => Ok(&mut self.federation_version),
+            key => Err(not_a_setting(key)),
+        }
+    }
+}
+
+fn not_a_setting(key: &str) -> HoustonProblem {
+    HoustonProblem::InvalidProfileSetting {
+        key: key.to_string(),
+        reason: "it is not a profile setting".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_and_unsets_settings_by_key() {
+        let mut settings = ProfileSettings::default();
+        settings.set("graph_ref", "my-graph@prod").unwrap();
+        settings.set("client_timeout", "60").unwrap();
+        assert_eq!(
+            settings.get("graph_ref").unwrap().as_deref(),
+            Some("my-graph@prod")
+        );
+        assert_eq!(settings.client_timeout, Some(60));
+        assert_eq!(
+            toml::to_string(&settings).unwrap(),
+            "graph_ref = \"my-graph@prod\"\nclient_timeout = 60\n"
+        );
+
+        settings.unset("graph_ref").unwrap();
+        assert_eq!(settings.get("graph_ref").unwrap(), None);
+
+        assert!(settings.set("client_timeout", "a minute").is_err());
+        assert!(settings.set("api_key", "service:foo:bar").is_err());
+        for key in ProfileSettings::KEYS {
+            assert!(settings.get(key).is_ok());
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use argon2::Argon2;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::HoustonProblem;
+
+/// Where a profile's API key is kept
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum CredentialStore {
+    /// In plain text, in the profile's `.sensitive` file
+    #[default]
+    File,
+
+    /// In the profile's `.sensitive` file, encrypted with a key derived from a passphrase
+    EncryptedFile,
+
+    /// By an external credential helper executable, in the same way as git's credential helpers.
+    /// A bare name runs `rover-credential-<name>` from the `PATH`; anything else is run as a path.
+    Helper(String),
+}
+
+impl FromStr for CredentialStore {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "file" => Ok(CredentialStore::File),
+            "encrypted-file" => Ok(CredentialStore::EncryptedFile),
+            _ => match input.strip_prefix("helper:") {
+                Some(helper) if !helper.is_empty() => Ok(CredentialStore::Helper(helper.to_string())),
+                _ => Err(format!(
+                    "'{input}' is not a credential store. Use 'file', 'encrypted-file', or 'helper:<name>'."
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialStore::File => write!(f, "file"),
+            CredentialStore::EncryptedFile => write!(f, "encrypted-file"),
+            CredentialStore::Helper(helper) => write!(f, "helper:{helper}"),
+        }
+    }
+}
+
+/// An API key encrypted with ChaCha20-Poly1305, using a key derived from a passphrase with Argon2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct EncryptedApiKey {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl EncryptedApiKey {
+    pub(crate) fn encrypt(api_key: &str, passphrase: &str) -> Result<Self, HoustonProblem> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, api_key.as_bytes())
+            .map_err(|_| HoustonProblem::AdhocError(anyhow!("Could not encrypt the API key")))?;
+        Ok(EncryptedApiKey {
+            salt: BASE64_STANDARD.encode(salt),
+            nonce: BASE64_STANDARD.encode(nonce),
+            ciphertext: BASE64_STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub(crate) fn decrypt(
+        &self,
+        passphrase: &str,
+        profile_name: &str,
+    ) -> Result<String, HoustonProblem> {
+        let undecryptable = || HoustonProblem::CredentialDecryptionFailed(profile_name.to_string());
+        let salt = BASE64_STANDARD
+            .decode(&self.salt)
+            .map_err(|_| undecryptable())?;
+        let nonce = BASE64_STANDARD
+            .decode(&self.nonce)
+            .map_err(|_| undecryptable())?;
+        let ciphertext = BASE64_STANDARD
+            .decode(&self.ciphertext)
+            .map_err(|_| undecryptable())?;
+        if nonce.len() != 12 {
+            return Err(undecryptable());
+        }
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+        let api_key = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| undecryptable())?;
+        String::from_utf8(api_key).map_err(|_| undecryptable())
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, HoustonProblem> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| HoustonProblem::AdhocError(anyhow!("Could not derive a key: {}", e)))?;
+    Ok(Key::from(key))
+}
+
+/// Runs a credential helper, which is passed an action (`get`, `store`, or `erase`) as its
+/// only argument and `key=value` lines on stdin, ending with a blank line:
+///
+/// ```text
+/// profile=default
+/// api_key=user:gh.foo:djru4788dhsg3657fhLOLO
+/// ```
+///
+/// `api_key` is only sent to `store`. `get` prints an `api_key=<key>` line to stdout.
+pub(crate) struct CredentialHelper<'a> {
+    helper: &'a str,
+}
+
+impl<'a> CredentialHelper<'a> {
+    pub(crate) fn new(helper: &'a str) -> Self {
+        CredentialHelper { helper }
+    }
+
+    pub(crate) fn get(&self, profile_name: &str) -> Result<String, HoustonProblem> {
+        let output = self.run("get", &format!("profile={profile_name}\n\n"))?;
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix("api_key="))
+            .map(|api_key| api_key.trim().to_string())
+            .filter(|api_key| !api_key.is_empty())
+            .ok_or_else(|| HoustonProblem::CredentialHelperFailed {
+                helper: self.helper.to_string(),
+                reason: format!("it has no API key for profile \"{profile_name}\""),
+            })
+    }
+
+    pub(crate) fn store(&self, profile_name: &str, api_key: &str) -> Result<(), HoustonProblem> {
+        self.run(
+            "store",
+            &format!("profile={profile_name}\napi_key={api_key}\n\n"),
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn erase(&self, profile_name: &str) -> Result<(), HoustonProblem> {
+        self.run("erase", &format!("profile={profile_name}\n\n"))?;
+        Ok(())
+    }
+
+    fn command(&self) -> Command {
+        if self.helper.contains(['/', '\\']) {
+            Command::new(self.helper)
+        } else {
+            Command::new(format!("rover-credential-{}", self.helper))
+        }
+    }
+
+    fn run(&self, action: &str, input: &str) -> Result<String, HoustonProblem> {
+        let failed = |reason: String| HoustonProblem::CredentialHelperFailed {
+            helper: self.helper.to_string(),
+            reason,
+        };
+        tracing::debug!(helper = self.helper, action);
+        let mut child = self
+            .command()
+            .arg(action)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| failed(format!("it could not be run: {e}")))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| failed(format!("it could not be sent the request: {e}")))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| failed(e.to_string()))?;
+        if !output.status.success() {
+            return Err(failed(format!("`{action}` exited with {}", output.status)));
+        }
+        String::from_utf8(output.stdout).map_err(|_| failed("its output is not UTF-8".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_credential_stores() {
+        for store in [
+            CredentialStore::File,
+            CredentialStore::EncryptedFile,
+            CredentialStore::Helper("1password".to_string()),
+        ] {
+            assert_eq!(store.to_string().parse::<CredentialStore>(), Ok(store));
+        }
+        assert!("helper:".parse::<CredentialStore>().is_err());
+        assert!("keychain".parse::<CredentialStore>().is_err());
+    }
+
+    #[test]
+    fn it_round_trips_encrypted_api_keys() {
+        let api_key = "user:gh.foo:djru4788dhsg3657fhLOLO";
+        let encrypted = EncryptedApiKey::encrypt(api_key, "correct horse").unwrap();
+        assert!(!encrypted.ciphertext.contains("djru4788"));
+        assert_eq!(
+            encrypted.decrypt("correct horse", "default").unwrap(),
+            api_key
+        );
+        assert!(matches!(
+            encrypted.decrypt("battery staple", "default"),
+            Err(HoustonProblem::CredentialDecryptionFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+//! This module packages a crash report with context about the run that crashed.
+//!
+//! A crash bundle is a `.tar.gz` archive that holds the crash report, if there is one,
+//! along with context the application gathers itself, like the command that was run.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::sync::OnceLock;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::report::Report;
+
+type ContextProvider = Box<dyn Fn() -> BundleContext + Send + Sync>;
+
+static CONTEXT_PROVIDER: OnceLock<ContextProvider> = OnceLock::new();
+
+/// Context about a run that's included in crash bundles.
+///
+/// Nothing in it is redacted by robot-panic, so secrets need to be
+/// masked before they're added.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct BundleContext {
+    /// The command that was run, with the values of its arguments except those that are always
+    /// left out, such as graph refs and file paths
+    pub command: serde_json::Value,
+
+    /// The environment variables the application reads
+    pub env: BTreeMap<String, String>,
+
+    /// The plugins that are installed
+    pub plugins: Vec<String>,
+
+    /// The most recent log lines, oldest first
+    #[serde(skip)]
+    pub log_lines: Vec<String>,
+}
+
+/// Sets the function that gathers the context for crash bundles when a panic occurs.
+/// Only the first function set is used.
+pub fn set_context_provider<F>(provider: F)
+where
+    F: Fn() -> BundleContext + Send + Sync + 'static,
+{
+    let _ = CONTEXT_PROVIDER.set(Box::new(provider));
+}
+
+/// Writes a crash bundle to `path`, with the crash report if there is one.
+pub fn write_bundle(
+    path: &Utf8Path,
+    report: Option<&Report>,
+    context: &BundleContext,
+) -> Result<(), Box<dyn Error + 'static>> {
+    let file = File::create(path)?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut append = |name: &str, contents: &[u8]| {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive.append_data(&mut header, name, contents)
+    };
+    if let Some(report) = report.and_then(Report::serialize) {
+        append("report.toml", report.as_bytes())?;
+    }
+    append(
+        "context.json",
+        serde_json::to_string_pretty(context)?.as_bytes(),
+    )?;
+    let mut logs = context.log_lines.join("\n");
+    logs.push('\n');
+    append("logs.txt", logs.as_bytes())?;
+
+    archive.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Writes a crash bundle for `report` to the temp directory, if a context provider has been set.
+pub fn persist_crash_bundle(
+    report: &Report,
+) -> Result<Option<Utf8PathBuf>, Box<dyn Error + 'static>> {
+    let Some(provider) = CONTEXT_PROVIDER.get() else {
+        return Ok(None);
+    };
+    let uuid = Uuid::new_v4().hyphenated().to_string();
+    let tmp_dir = Utf8PathBuf::try_from(env::temp_dir())?;
+    let path = tmp_dir.join(format!("crash-{}.tar.gz", &uuid));
+    write_bundle(&path, Some(report), &provider())?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+    use crate::report::Method;
+
+    #[test]
+    fn it_writes_a_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().join("bundle.tar.gz")).unwrap();
+        let report = Report::new(
+            "rover",
+            "0.0.0",
+            Method::Panic,
+            "Panic occurred in file 'src/cli.rs' at line 1\n".to_string(),
+            "oh no".to_string(),
+        );
+        let context = BundleContext {
+            command: serde_json::json!({ "name": "subgraph publish" }),
+            env: BTreeMap::from([("APOLLO_KEY".to_string(), "user****abcd".to_string())]),
+            plugins: vec!["supergraph-v2.8.0".to_string()],
+            log_lines: vec!["INFO rover: publishing".to_string()],
+        };
+        write_bundle(&path, Some(&report), &context).unwrap();
+
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(&path).unwrap()));
+        let mut files = BTreeMap::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().display().to_string();
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents).unwrap();
+            files.insert(name, contents);
+        }
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec!["context.json", "logs.txt", "report.toml"]
+        );
+        assert!(files["report.toml"].contains("oh no"));
+        assert!(files["context.json"].contains("user****abcd"));
+        assert_eq!(files["logs.txt"], "INFO rover: publishing\n");
+    }
+}
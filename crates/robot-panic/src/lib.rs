@@ -35,6 +35,7 @@
 //!
 //! Thanks for your patience!
 
+pub mod bundle;
 pub mod report;
 use report::{Method, Report};
 
@@ -205,6 +206,16 @@ pub fn print_msg(crash_report: &Report, meta: &Metadata) -> IoResult<()> {
         }
     }
 
+    if let Ok(Some(bundle_path)) = bundle::persist_crash_bundle(crash_report) {
+        writeln!(
+            &mut buffer,
+            "\nWe have also saved a crash bundle at \"{}\", with the command that was run, \
+             environment variables, installed plugins, and recent logs. Check it for anything \
+             you don't want to share before attaching it to the issue.",
+            bundle_path
+        )?;
+    }
+
     writeln!(
         &mut buffer,
         "\nWe take privacy seriously, and do not perform any \
@@ -1,21 +1,32 @@
-use std::time::Duration;
-
-use graphql_client::{Error as GraphQLError, GraphQLQuery, Response as GraphQLResponse};
+use graphql_client::{GraphQLQuery, Response as GraphQLResponse};
 use reqwest::{
     blocking::{Client as ReqwestClient, Response},
     header::{HeaderMap, HeaderValue},
     StatusCode,
 };
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Duration;
 
-use crate::error::{EndpointKind, RoverClientError};
+use std::cell::Cell;
 
-pub(crate) const JSON_CONTENT_TYPE: &str = "application/json";
+use crate::blocking::{
+    fixtures::FixtureKey, retry::retry_after, websocket, FixtureMode, GraphQLTransport, RetryPolicy,
+};
+use crate::error::{EndpointKind, RoverClientError};
+use crate::graphql::{
+    classify_send_error, get_document_request_body, get_query_params, get_raw_request_body,
+    get_request_body, handle_response_body, handle_status_and_body, JSON_CONTENT_TYPE,
+};
 
 /// Represents a generic GraphQL client for making http requests.
 pub struct GraphQLClient {
     graphql_endpoint: String,
     client: ReqwestClient,
-    retry_period: Option<Duration>,
+    retry_policy: RetryPolicy,
+    transport: GraphQLTransport,
+    timeout: Option<Duration>,
+    fixtures: Option<FixtureMode>,
 }
 
 impl GraphQLClient {
@@ -24,15 +35,38 @@ impl GraphQLClient {
     pub fn new(
         graphql_endpoint: &str,
         client: ReqwestClient,
-        retry_period: Option<Duration>,
+        retry_policy: RetryPolicy,
     ) -> GraphQLClient {
         GraphQLClient {
             graphql_endpoint: graphql_endpoint.to_string(),
             client,
-            retry_period,
+            retry_policy,
+            transport: GraphQLTransport::default(),
+            timeout: None,
+            fixtures: None,
         }
     }
 
+    /// Sends requests with `transport` rather than POSTing them.
+    pub fn with_transport(mut self, transport: GraphQLTransport) -> GraphQLClient {
+        self.transport = transport;
+        self
+    }
+
+    /// Gives up on WebSocket requests that take longer than `timeout` to connect or
+    /// to receive each message. HTTP requests use the timeout of the `reqwest` client.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> GraphQLClient {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Records each response to a fixture file, or replays responses from fixture files
+    /// without using the network.
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> GraphQLClient {
+        self.fixtures = Some(fixtures);
+        self
+    }
+
     /// Client method for making a GraphQL request.
     ///
     /// Takes one argument, `variables`. Returns an optional response.
@@ -46,10 +80,8 @@ impl GraphQLClient {
     where
         Q: GraphQLQuery,
     {
-        let request_body = self.get_request_body::<Q>(variables)?;
-        header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
-        let response = self.execute(request_body, header_map, true, endpoint_kind);
-        GraphQLClient::handle_response::<Q>(response?, endpoint_kind)
+        let request_body = get_request_body::<Q>(variables)?;
+        self.send::<Q::ResponseData>(request_body, header_map, true, endpoint_kind)
     }
 
     /// Client method for making a GraphQL request.
@@ -65,18 +97,158 @@ impl GraphQLClient {
     where
         Q: GraphQLQuery,
     {
-        let request_body = self.get_request_body::<Q>(variables)?;
-        header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
-        let response = self.execute(request_body, header_map, false, endpoint_kind);
-        GraphQLClient::handle_response::<Q>(response?, endpoint_kind)
+        let request_body = get_request_body::<Q>(variables)?;
+        self.send::<Q::ResponseData>(request_body, header_map, false, endpoint_kind)
     }
 
-    fn get_request_body<Q: GraphQLQuery>(
+    /// Client method for making a GraphQL request whose query is built at runtime
+    /// rather than generated by `graphql_client`.
+    ///
+    /// Takes the query document and the name of the operation to run. Returns the
+    /// response's `data`, deserialized into `T`.
+    pub fn post_document<T>(
         &self,
-        variables: Q::Variables,
-    ) -> Result<String, RoverClientError> {
-        let body = Q::build_query(variables);
-        Ok(serde_json::to_string(&body)?)
+        query: &str,
+        operation_name: &str,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError>
+    where
+        T: DeserializeOwned,
+    {
+        let request_body = get_document_request_body(query, operation_name)?;
+        self.send::<T>(request_body, header_map, should_retry, endpoint_kind)
+    }
+
+    /// Client method for making a GraphQL request with any query document and variables.
+    ///
+    /// Returns the response's `data` as JSON. Automatically retries requests.
+    pub fn post_raw(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Value,
+        header_map: &mut HeaderMap,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Value, RoverClientError> {
+        let request_body = get_raw_request_body(query, operation_name, variables)?;
+        self.send::<Value>(request_body, header_map, true, endpoint_kind)
+    }
+
+    /// Sends `request_body` with this client's transport and returns the response's `data`,
+    /// in a span recording the operation, the response's status, and how many times it was retried.
+    fn send<T: DeserializeOwned>(
+        &self,
+        request_body: String,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError> {
+        let operation_name = serde_json::from_str::<Value>(&request_body)
+            .ok()
+            .and_then(|body| body.get("operationName")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| "anonymous".to_string());
+        let span = tracing::info_span!(
+            "graphql_request",
+            otel.name = %format!("graphql {}", operation_name),
+            graphql.operation.name = %operation_name,
+            http.response.status_code = tracing::field::Empty,
+            rover.retries = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let _guard = span.enter();
+        let result = self.send_with_fixtures(request_body, header_map, should_retry, endpoint_kind);
+        if let Err(e) = &result {
+            span.record("error", e.to_string());
+        }
+        result
+    }
+
+    fn send_with_fixtures<T: DeserializeOwned>(
+        &self,
+        request_body: String,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError> {
+        match &self.fixtures {
+            Some(FixtureMode::Replay(dir)) => {
+                let fixture = FixtureKey::from_request_body(&request_body)?.replay(dir)?;
+                let status = fixture.status();
+                let response_body = serde_json::from_value(fixture.response).map_err(Into::into);
+                handle_status_and_body(status, response_body)
+            }
+            Some(FixtureMode::Record(dir)) => {
+                let fixture_key = FixtureKey::from_request_body(&request_body)?;
+                let (status, response) =
+                    self.send_for_json(request_body, header_map, should_retry, endpoint_kind)?;
+                fixture_key.record(dir, status, &response)?;
+                let response_body = serde_json::from_value(response).map_err(Into::into);
+                handle_status_and_body(status, response_body)
+            }
+            None => self.send_for_data(request_body, header_map, should_retry, endpoint_kind),
+        }
+    }
+
+    fn send_for_data<T: DeserializeOwned>(
+        &self,
+        request_body: String,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError> {
+        if self.transport == GraphQLTransport::WebSocket {
+            let response_body = websocket::execute(
+                &self.graphql_endpoint,
+                &request_body,
+                header_map,
+                should_retry,
+                &self.retry_policy,
+                self.timeout,
+            )?;
+            tracing::debug!("WebSocket response: {}", response_body);
+            handle_response_body(serde_json::from_value(response_body)?)
+        } else {
+            header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
+            let response = self.execute(request_body, header_map, should_retry, endpoint_kind);
+            GraphQLClient::handle_response_data::<T>(response?, endpoint_kind)
+        }
+    }
+
+    /// Sends `request_body` and returns the response's status and its whole JSON body, to record
+    fn send_for_json(
+        &self,
+        request_body: String,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<(StatusCode, Value), RoverClientError> {
+        if self.transport == GraphQLTransport::WebSocket {
+            let response_body = websocket::execute(
+                &self.graphql_endpoint,
+                &request_body,
+                header_map,
+                should_retry,
+                &self.retry_policy,
+                self.timeout,
+            )?;
+            Ok((StatusCode::OK, response_body))
+        } else {
+            header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
+            let response = self.execute(request_body, header_map, should_retry, endpoint_kind)?;
+            let status = response.status();
+            match response.json::<Value>() {
+                Ok(response_body) => Ok((status, response_body)),
+                Err(source) if status.is_success() => Err(RoverClientError::SendRequest {
+                    source,
+                    endpoint_kind,
+                }),
+                Err(_) => Err(RoverClientError::ClientError {
+                    msg: status.to_string(),
+                }),
+            }
+        }
     }
 
     fn execute(
@@ -86,100 +258,66 @@ impl GraphQLClient {
         should_retry: bool,
         endpoint_kind: EndpointKind,
     ) -> Result<Response, RoverClientError> {
-        use backoff::{retry, Error as BackoffError, ExponentialBackoff};
+        use backoff::Error as BackoffError;
 
         tracing::trace!(request_headers = ?header_map);
         tracing::debug!("Request Body: {}", request_body);
+        let query_params = match self.transport {
+            GraphQLTransport::Get => Some(get_query_params(&request_body)?),
+            _ => None,
+        };
+        let attempts = Cell::new(0u32);
         let graphql_operation = || {
-            let response = self
-                .client
-                .post(&self.graphql_endpoint)
-                .headers(header_map.clone())
-                .body(request_body.clone())
-                .send();
+            attempts.set(attempts.get() + 1);
+            let request = match &query_params {
+                Some(query_params) => self.client.get(&self.graphql_endpoint).query(query_params),
+                None => self
+                    .client
+                    .post(&self.graphql_endpoint)
+                    .body(request_body.clone()),
+            };
+            let response = request.headers(header_map.clone()).send();
 
+            if let Ok(response) = &response {
+                tracing::Span::current()
+                    .record("http.response.status_code", response.status().as_u16());
+            }
             match response {
-                Err(client_error) => {
-                    if client_error.is_timeout() || client_error.is_connect() {
-                        Err(BackoffError::transient(client_error))
-                    } else if client_error.is_body()
-                        || client_error.is_decode()
-                        || client_error.is_builder()
-                        || client_error.is_redirect()
+                Err(client_error) => Err(classify_send_error(client_error)),
+                Ok(success) => match success.error_for_status_ref().err() {
+                    None => Ok(success),
+                    // retrying with the same request only helps when the server says it might,
+                    // not for a malformed request or rejected credentials
+                    Some(status_error)
+                        if self.retry_policy.is_retryable_status(success.status()) =>
                     {
-                        Err(BackoffError::Permanent(client_error))
-                    } else if client_error.is_request() {
-                        if let Some(hyper_error) =
-                            get_source_error_type::<hyper::Error>(&client_error)
-                        {
-                            if hyper_error.is_incomplete_message() {
-                                Err(BackoffError::transient(client_error))
-                            } else {
-                                Err(BackoffError::Permanent(client_error))
-                            }
-                        } else {
-                            Err(BackoffError::Permanent(client_error))
-                        }
-                    } else {
-                        Err(BackoffError::Permanent(client_error))
+                        Err(BackoffError::Transient {
+                            retry_after: retry_after(success.headers()),
+                            err: status_error,
+                        })
                     }
-                }
-                Ok(success) => {
-                    if let Err(status_error) = success.error_for_status_ref() {
-                        if let Some(response_status) = status_error.status() {
-                            if response_status.is_server_error()
-                                || response_status.is_client_error()
-                                || response_status.is_redirection()
-                            {
-                                if matches!(response_status, StatusCode::BAD_REQUEST) {
-                                    if let Ok(text) = success.text() {
-                                        tracing::debug!("{}", text);
-                                    }
-                                    Err(BackoffError::Permanent(status_error))
-                                } else {
-                                    Err(BackoffError::transient(status_error))
-                                }
-                            } else {
-                                Err(BackoffError::Permanent(status_error))
-                            }
-                        } else {
-                            Err(BackoffError::Permanent(status_error))
+                    Some(status_error) => {
+                        if let Ok(text) = success.text() {
+                            tracing::debug!("{}", text);
                         }
-                    } else {
-                        Ok(success)
+                        Err(BackoffError::Permanent(status_error))
                     }
-                }
+                },
             }
         };
 
-        if should_retry {
-            let backoff_strategy = ExponentialBackoff {
-                max_elapsed_time: self.retry_period,
-                ..Default::default()
-            };
-
-            retry(backoff_strategy, graphql_operation).map_err(|e| match e {
-                BackoffError::Permanent(reqwest_error)
-                | BackoffError::Transient {
-                    err: reqwest_error,
-                    retry_after: _,
-                } => RoverClientError::SendRequest {
-                    source: reqwest_error,
-                    endpoint_kind,
-                },
-            })
+        let result = if should_retry {
+            self.retry_policy.retry(graphql_operation)
         } else {
             graphql_operation().map_err(|e| match e {
-                BackoffError::Permanent(reqwest_error)
-                | BackoffError::Transient {
-                    err: reqwest_error,
-                    retry_after: _,
-                } => RoverClientError::SendRequest {
-                    source: reqwest_error,
-                    endpoint_kind,
-                },
+                BackoffError::Permanent(err) | BackoffError::Transient { err, .. } => err,
             })
-        }
+        };
+        tracing::Span::current().record("rover.retries", attempts.get().saturating_sub(1));
+        result.map_err(|reqwest_error| RoverClientError::SendRequest {
+            source: reqwest_error,
+            endpoint_kind,
+        })
     }
 
     /// To be used internally or by other implementations of a GraphQL client.
@@ -190,132 +328,34 @@ impl GraphQLClient {
     /// body.data, it will also error, as this shouldn't be possible.
     ///
     /// If successful, it will return body.data, unwrapped
-    pub(crate) fn handle_response<Q: GraphQLQuery>(
+    pub(crate) fn handle_response_data<T: DeserializeOwned>(
         response: Response,
         endpoint_kind: EndpointKind,
-    ) -> Result<Q::ResponseData, RoverClientError> {
+    ) -> Result<T, RoverClientError> {
         let response_status = response.status();
         tracing::debug!(response_status = ?response_status, response_headers = ?response.headers());
-        match response.json::<GraphQLResponse<Q::ResponseData>>() {
-            Ok(response_body) => {
-                if let Some(response_body_errors) = response_body.errors {
-                    handle_graphql_body_errors(response_body_errors)?;
-                }
-                match response_status {
-                    StatusCode::OK => {
-                        response_body
-                            .data
-                            .ok_or_else(|| RoverClientError::MalformedResponse {
-                                null_field: "data".to_string(),
-                            })
-                    }
-                    status_code => Err(RoverClientError::ClientError {
-                        msg: status_code.to_string(),
-                    }),
-                }
-            }
-            Err(e) => {
-                if response_status.is_success() {
-                    Err(RoverClientError::SendRequest {
-                        source: e,
-                        endpoint_kind,
-                    })
-                } else {
-                    Err(RoverClientError::ClientError {
-                        msg: response_status.to_string(),
-                    })
-                }
-            }
-        }
-    }
-}
-
-fn handle_graphql_body_errors(errors: Vec<GraphQLError>) -> Result<(), RoverClientError> {
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        tracing::debug!("GraphQL response errors: {:?}", errors);
-        if errors[0].message == "406: Not Acceptable" {
-            Err(RoverClientError::MalformedKey)
-        } else {
-            Err(RoverClientError::GraphQl {
-                msg: errors
-                    .into_iter()
-                    .map(|error| error.message)
-                    .collect::<Vec<String>>()
-                    .join("\n"),
-            })
-        }
-    }
-}
-
-/// Downcasts the given err source into T.
-fn get_source_error_type<T: std::error::Error + 'static>(
-    err: &dyn std::error::Error,
-) -> Option<&T> {
-    let mut source = err.source();
-
-    while let Some(err) = source {
-        if let Some(hyper_err) = err.downcast_ref::<T>() {
-            return Some(hyper_err);
-        }
-
-        source = err.source();
+        let response_body =
+            response
+                .json::<GraphQLResponse<T>>()
+                .map_err(|source| RoverClientError::SendRequest {
+                    source,
+                    endpoint_kind,
+                });
+        handle_status_and_body(response_status, response_body)
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use std::error::Error;
+    use std::time::Duration;
 
+    use camino::Utf8PathBuf;
     use httpmock::prelude::*;
+    use serde_json::json;
 
     use super::*;
 
-    #[test]
-    fn it_is_ok_on_empty_errors() {
-        let errors = vec![];
-        assert!(handle_graphql_body_errors(errors).is_ok());
-    }
-
-    #[test]
-    fn it_returns_malformed_key() {
-        let errors = vec![GraphQLError {
-            message: "406: Not Acceptable".to_string(),
-            locations: None,
-            extensions: None,
-            path: None,
-        }];
-        let expected_error = RoverClientError::MalformedKey.to_string();
-        let actual_error = handle_graphql_body_errors(errors).unwrap_err().to_string();
-        assert_eq!(actual_error, expected_error);
-    }
-
-    #[test]
-    fn it_returns_random_graphql_error() {
-        let errors = vec![
-            GraphQLError {
-                message: "Something went wrong".to_string(),
-                locations: None,
-                extensions: None,
-                path: None,
-            },
-            GraphQLError {
-                message: "Something else went wrong".to_string(),
-                locations: None,
-                extensions: None,
-                path: None,
-            },
-        ];
-        let expected_error = RoverClientError::GraphQl {
-            msg: format!("{}\n{}", errors[0].message, errors[1].message),
-        }
-        .to_string();
-        let actual_error = handle_graphql_body_errors(errors).unwrap_err().to_string();
-        assert_eq!(actual_error, expected_error);
-    }
-
     #[test]
     fn test_successful_response() {
         let server = MockServer::start();
@@ -329,7 +369,7 @@ mod tests {
         let graphql_client = GraphQLClient::new(
             &server.url(success_path),
             client,
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let response = graphql_client.execute(
@@ -345,6 +385,80 @@ mod tests {
         assert!(response.is_ok())
     }
 
+    #[test]
+    fn test_get_transport() {
+        let server = MockServer::start();
+        let get_mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/graphql")
+                .query_param("query", "query Q { __typename }")
+                .query_param("operationName", "Q");
+            then.status(200)
+                .json_body(json!({ "data": { "__typename": "Query" } }));
+        });
+
+        let graphql_client = GraphQLClient::new(
+            &server.url("/graphql"),
+            ReqwestClient::new(),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
+        )
+        .with_transport(GraphQLTransport::Get);
+
+        let data: Value = graphql_client
+            .post_document(
+                "query Q { __typename }",
+                "Q",
+                &mut HeaderMap::new(),
+                false,
+                EndpointKind::Customer,
+            )
+            .unwrap();
+
+        get_mock.assert();
+        assert_eq!(data, json!({ "__typename": "Query" }));
+    }
+
+    #[test]
+    fn it_records_and_replays_responses() {
+        let server = MockServer::start();
+        let graphql_mock = server.mock(|when, then| {
+            when.method(POST).path("/graphql");
+            then.status(200)
+                .json_body(json!({ "data": { "__typename": "Query" } }));
+        });
+        let fixtures = tempfile::tempdir().unwrap();
+        let fixtures_dir = Utf8PathBuf::try_from(fixtures.path().to_path_buf()).unwrap();
+        let client = |fixtures: FixtureMode| {
+            GraphQLClient::new(
+                &server.url("/graphql"),
+                ReqwestClient::new(),
+                RetryPolicy::default(),
+            )
+            .with_fixtures(fixtures)
+        };
+        let post = |client: &GraphQLClient, operation_name: &str| {
+            client.post_document::<Value>(
+                &format!("query {operation_name} {{ __typename }}"),
+                operation_name,
+                &mut HeaderMap::new(),
+                false,
+                EndpointKind::ApolloStudio,
+            )
+        };
+
+        let recorded = post(&client(FixtureMode::Record(fixtures_dir.clone())), "Q").unwrap();
+        let replaying = client(FixtureMode::Replay(fixtures_dir));
+        let replayed = post(&replaying, "Q").unwrap();
+
+        graphql_mock.assert_hits(1);
+        assert_eq!(recorded, json!({ "__typename": "Query" }));
+        assert_eq!(replayed, recorded);
+        assert!(matches!(
+            post(&replaying, "Unrecorded"),
+            Err(RoverClientError::NoRecordedResponse { operation_name, .. }) if operation_name == "Unrecorded"
+        ));
+    }
+
     #[test]
     fn test_unrecoverable_server_error() {
         let server = MockServer::start();
@@ -358,7 +472,7 @@ mod tests {
         let graphql_client = GraphQLClient::new(
             &server.url(internal_server_error_path),
             client,
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let response = graphql_client.execute(
@@ -387,7 +501,7 @@ mod tests {
         let graphql_client = GraphQLClient::new(
             &server.url(not_found_path),
             client,
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let response = graphql_client.execute(
@@ -397,9 +511,8 @@ mod tests {
             EndpointKind::ApolloStudio,
         );
 
-        let mock_hits = not_found_mock.hits();
-
-        assert!(mock_hits > 1);
+        // 404 isn't one of the statuses retried by default
+        not_found_mock.assert_hits(1);
 
         let error = response.expect_err("Response didn't error");
         assert!(error.to_string().contains("Not Found"));
@@ -423,7 +536,7 @@ mod tests {
         let graphql_client = GraphQLClient::new(
             &server.url(timeout_path),
             client,
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let response = graphql_client.execute(
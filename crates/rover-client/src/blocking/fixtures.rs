@@ -0,0 +1,187 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use reqwest::StatusCode;
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::RoverClientError;
+
+/// Whether a [`GraphQLClient`](crate::blocking::GraphQLClient) records the responses it
+/// receives to fixture files, or replays responses from them instead of using the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Sends requests as usual, and writes each response to a fixture in this directory
+    Record(Utf8PathBuf),
+    /// Serves responses from the fixtures in this directory, failing any request without one
+    Replay(Utf8PathBuf),
+}
+
+/// A recorded response to one GraphQL operation with particular variables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Fixture {
+    pub(crate) operation_name: String,
+    pub(crate) variables: Value,
+    pub(crate) status: u16,
+    pub(crate) response: Value,
+}
+
+impl Fixture {
+    pub(crate) fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK)
+    }
+}
+
+/// The operation name and variables of a GraphQL request body, which identify its fixture
+pub(crate) struct FixtureKey {
+    operation_name: String,
+    variables: Value,
+}
+
+impl FixtureKey {
+    pub(crate) fn from_request_body(request_body: &str) -> Result<FixtureKey, RoverClientError> {
+        let body: Value = serde_json::from_str(request_body)?;
+        // anonymous operations are told apart by their document instead of their name
+        let operation_name = match body.get("operationName").and_then(Value::as_str) {
+            Some(operation_name) => operation_name.to_string(),
+            None => {
+                let query = body
+                    .get("query")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                format!("anonymous-{}", &hash(query)[..8])
+            }
+        };
+        let variables = match body.get("variables") {
+            None | Some(Value::Null) => Value::Object(Default::default()),
+            Some(variables) => variables.clone(),
+        };
+        Ok(FixtureKey {
+            operation_name,
+            variables,
+        })
+    }
+
+    /// Fixtures are named after their operation, with a hash of the variables so that the
+    /// same operation can be recorded for several graphs
+    pub(crate) fn path(&self, dir: &Utf8Path) -> Utf8PathBuf {
+        let hash = hash(&canonical(&self.variables).to_string());
+        dir.join(format!("{}-{}.json", self.operation_name, &hash[..16]))
+    }
+
+    pub(crate) fn record(
+        self,
+        dir: &Utf8Path,
+        status: StatusCode,
+        response: &Value,
+    ) -> Result<(), RoverClientError> {
+        let path = self.path(dir);
+        let fixture = Fixture {
+            operation_name: self.operation_name,
+            variables: self.variables,
+            status: status.as_u16(),
+            response: response.clone(),
+        };
+        tracing::debug!("recording {} to {}", fixture.operation_name, path);
+        Fs::write_file(&path, serde_json::to_string_pretty(&fixture)?).map_err(|e| {
+            RoverClientError::AdhocError {
+                msg: format!("Could not record a response to {}: {}", path, e),
+            }
+        })
+    }
+
+    pub(crate) fn replay(self, dir: &Utf8Path) -> Result<Fixture, RoverClientError> {
+        let path = self.path(dir);
+        if !path.exists() {
+            return Err(RoverClientError::NoRecordedResponse {
+                operation_name: self.operation_name,
+                variables: self.variables,
+                path: path.to_string(),
+            });
+        }
+        tracing::debug!("replaying {} from {}", self.operation_name, path);
+        let contents = Fs::read_file(&path).map_err(|e| RoverClientError::AdhocError {
+            msg: format!("Could not read the recorded response in {}: {}", path, e),
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+fn hash(contents: &str) -> String {
+    format!("{:x}", Sha256::digest(contents.as_bytes()))
+}
+
+/// `value` with the keys of every object sorted, so equal variables always hash the same
+fn canonical(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonical(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.iter().map(canonical).collect()),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::graphql::get_raw_request_body;
+
+    #[test]
+    fn it_names_fixtures_after_the_operation_and_variables() {
+        let dir = Utf8Path::new("fixtures");
+        let key = |body: Value| FixtureKey::from_request_body(&body.to_string()).unwrap();
+
+        let path = key(json!({
+            "query": "query SubgraphFetchQuery { __typename }",
+            "operationName": "SubgraphFetchQuery",
+            "variables": { "graphRef": "my-graph@current", "subgraphName": "products" }
+        }))
+        .path(dir);
+        assert_eq!(path.parent(), Some(dir));
+        assert!(path.file_name().unwrap().starts_with("SubgraphFetchQuery-"));
+
+        let reordered = key(json!({
+            "operationName": "SubgraphFetchQuery",
+            "variables": { "subgraphName": "products", "graphRef": "my-graph@current" }
+        }))
+        .path(dir);
+        assert_eq!(path, reordered);
+
+        let other_subgraph = key(json!({
+            "operationName": "SubgraphFetchQuery",
+            "variables": { "graphRef": "my-graph@current", "subgraphName": "reviews" }
+        }))
+        .path(dir);
+        assert_ne!(path, other_subgraph);
+
+        let anonymous = key(json!({ "query": "{ me { id } }" })).path(dir);
+        assert!(anonymous.file_name().unwrap().starts_with("anonymous-"));
+        assert_ne!(
+            anonymous,
+            key(json!({ "query": "{ me { name } }" })).path(dir)
+        );
+    }
+
+    #[test]
+    fn it_keys_raw_operations_by_the_chosen_operation() {
+        let dir = Utf8Path::new("fixtures");
+        let document = "query Me { me { id } } query Variants { graph(id: \"g\") { id } }";
+        let key = |operation_name| {
+            let body = get_raw_request_body(document, operation_name, json!({})).unwrap();
+            FixtureKey::from_request_body(&body).unwrap().path(dir)
+        };
+        assert!(key(Some("Me")).file_name().unwrap().starts_with("Me-"));
+        assert_ne!(key(Some("Me")), key(Some("Variants")));
+    }
+}
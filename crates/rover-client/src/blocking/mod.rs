@@ -1,7 +1,12 @@
 mod client;
+pub(crate) mod fixtures;
+pub(crate) mod retry;
 mod studio_client;
+mod transport;
+mod websocket;
 
 pub use client::GraphQLClient;
+pub use fixtures::FixtureMode;
+pub use retry::{RetryPolicy, DEFAULT_MAX_ELAPSED_TIME, DEFAULT_RETRY_STATUSES};
 pub use studio_client::StudioClient;
-
-pub(crate) const CLIENT_NAME: &str = "rover-client";
+pub use transport::GraphQLTransport;
@@ -0,0 +1,268 @@
+use std::future::Future;
+use std::time::{Duration, Instant, SystemTime};
+
+use backoff::{backoff::Backoff, Error as BackoffError, ExponentialBackoff};
+use reqwest::{
+    blocking::{RequestBuilder, Response},
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+
+/// How long requests are retried for when no other limit is configured
+pub const DEFAULT_MAX_ELAPSED_TIME: Duration = Duration::from_secs(30);
+
+/// The HTTP statuses that are retried when no others are configured. These are the
+/// statuses a server uses when a request may well succeed if it's sent again later.
+pub const DEFAULT_RETRY_STATUSES: [StatusCode; 5] = [
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::INTERNAL_SERVER_ERROR,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// Controls how failed requests are retried: for how long, how many times, and which
+/// HTTP statuses are worth retrying. Requests are retried with exponential backoff and
+/// jitter, or after the delay a server asks for with a `Retry-After` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_elapsed_time: Duration,
+    max_retries: Option<u32>,
+    retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_elapsed_time: DEFAULT_MAX_ELAPSED_TIME,
+            max_retries: None,
+            retry_statuses: DEFAULT_RETRY_STATUSES.to_vec(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Stops retrying once this long has passed since the first attempt
+    pub fn with_max_elapsed_time(self, max_elapsed_time: Duration) -> Self {
+        Self {
+            max_elapsed_time,
+            ..self
+        }
+    }
+
+    /// Stops retrying after this many retries, however long they took
+    pub fn with_max_retries(self, max_retries: u32) -> Self {
+        Self {
+            max_retries: Some(max_retries),
+            ..self
+        }
+    }
+
+    /// Retries responses with these HTTP statuses, instead of the defaults
+    pub fn with_retry_statuses(self, retry_statuses: Vec<StatusCode>) -> Self {
+        Self {
+            retry_statuses,
+            ..self
+        }
+    }
+
+    pub fn max_elapsed_time(&self) -> Duration {
+        self.max_elapsed_time
+    }
+
+    pub fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+
+    pub fn retry_statuses(&self) -> &[StatusCode] {
+        &self.retry_statuses
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Runs `operation` until it succeeds, fails permanently, or this policy runs out of retries.
+    /// Transient failures are retried after their `retry_after` if they have one, or after an
+    /// exponentially increasing, randomized interval if they don't.
+    pub fn retry<T, E>(
+        &self,
+        mut operation: impl FnMut() -> Result<T, BackoffError<E>>,
+    ) -> Result<T, E> {
+        let started = Instant::now();
+        let mut retries = 0;
+        backoff::retry(self.backoff_strategy(), || match operation() {
+            Err(BackoffError::Transient { err, retry_after }) => {
+                let out_of_retries = self.max_retries.is_some_and(|max| retries >= max);
+                // `backoff` honours a `retry_after` however long it is, so it's checked here
+                let out_of_time = retry_after.is_some_and(|retry_after| {
+                    started.elapsed() + retry_after > self.max_elapsed_time
+                });
+                if out_of_retries || out_of_time {
+                    Err(BackoffError::Permanent(err))
+                } else {
+                    retries += 1;
+                    tracing::debug!(retries, ?retry_after, "retrying request");
+                    Err(BackoffError::Transient { err, retry_after })
+                }
+            }
+            result => result,
+        })
+        .map_err(|e| match e {
+            BackoffError::Permanent(err) | BackoffError::Transient { err, .. } => err,
+        })
+    }
+
+    /// Like [`RetryPolicy::retry`], but for async operations, waiting between attempts
+    /// without blocking the thread
+    pub async fn retry_async<T, E, F>(&self, mut operation: impl FnMut() -> F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, BackoffError<E>>>,
+    {
+        let started = Instant::now();
+        let mut retries = 0;
+        let mut backoff_strategy = self.backoff_strategy();
+        loop {
+            let (err, retry_after) = match operation().await {
+                Ok(result) => return Ok(result),
+                Err(BackoffError::Permanent(err)) => return Err(err),
+                Err(BackoffError::Transient { err, retry_after }) => (err, retry_after),
+            };
+            if self.max_retries.is_some_and(|max| retries >= max) {
+                return Err(err);
+            }
+            let delay = match retry_after {
+                Some(retry_after) if started.elapsed() + retry_after > self.max_elapsed_time => {
+                    return Err(err)
+                }
+                Some(retry_after) => retry_after,
+                None => match backoff_strategy.next_backoff() {
+                    Some(delay) => delay,
+                    None => return Err(err),
+                },
+            };
+            retries += 1;
+            tracing::debug!(retries, ?delay, "retrying request");
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn backoff_strategy(&self) -> ExponentialBackoff {
+        ExponentialBackoff {
+            max_elapsed_time: Some(self.max_elapsed_time),
+            ..Default::default()
+        }
+    }
+
+    /// Sends `request`, retrying connection failures, timeouts, and responses with a
+    /// retryable status. Once out of retries, the last response is returned whatever
+    /// its status, so callers should still check it.
+    pub fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        // requests with streaming bodies can't be sent more than once
+        if request.try_clone().is_none() {
+            return request.send();
+        }
+
+        enum Failure {
+            Response(Box<Response>),
+            Error(reqwest::Error),
+        }
+
+        let result = self.retry(|| {
+            let attempt = request.try_clone().expect("request was cloned above");
+            match attempt.send() {
+                Ok(response) if self.is_retryable_status(response.status()) => {
+                    let retry_after = retry_after(response.headers());
+                    Err(BackoffError::Transient {
+                        err: Failure::Response(Box::new(response)),
+                        retry_after,
+                    })
+                }
+                Ok(response) => Ok(response),
+                Err(e) if e.is_connect() || e.is_timeout() => {
+                    Err(BackoffError::transient(Failure::Error(e)))
+                }
+                Err(e) => Err(BackoffError::Permanent(Failure::Error(e))),
+            }
+        });
+        match result {
+            Ok(response) => Ok(response),
+            Err(Failure::Response(response)) => Ok(*response),
+            Err(Failure::Error(e)) => Err(e),
+        }
+    }
+}
+
+/// How long a response asks to wait before retrying, from its `Retry-After` header
+/// in either delay-seconds or HTTP-date form
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()?
+            .duration_since(SystemTime::now())
+            .ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use reqwest::blocking::Client;
+
+    use super::*;
+
+    #[test]
+    fn it_honours_retry_after() {
+        let server = MockServer::start();
+        let limited = server.mock(|when, then| {
+            when.method(GET).path("/limited");
+            then.status(429).header("Retry-After", "0");
+        });
+
+        let policy = RetryPolicy::default().with_max_retries(2);
+        let response = policy
+            .send(Client::new().get(server.url("/limited")))
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        limited.assert_hits(3);
+    }
+
+    #[test]
+    fn it_gives_up_when_retry_after_is_too_long() {
+        let server = MockServer::start();
+        let unavailable = server.mock(|when, then| {
+            when.method(GET).path("/unavailable");
+            then.status(503).header("Retry-After", "120");
+        });
+
+        let policy = RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(5));
+        let response = policy
+            .send(Client::new().get(server.url("/unavailable")))
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        unavailable.assert_hits(1);
+    }
+
+    #[test]
+    fn it_only_retries_retryable_statuses() {
+        let server = MockServer::start();
+        let bad_gateway = server.mock(|when, then| {
+            when.method(GET).path("/bad-gateway");
+            then.status(502).header("Retry-After", "0");
+        });
+
+        let policy = RetryPolicy::default()
+            .with_retry_statuses(vec![StatusCode::TOO_MANY_REQUESTS])
+            .with_max_retries(3);
+        let response = policy
+            .send(Client::new().get(server.url("/bad-gateway")))
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        bad_gateway.assert_hits(1);
+    }
+}
@@ -1,15 +1,16 @@
 use crate::{
-    blocking::{GraphQLClient, CLIENT_NAME},
+    blocking::{FixtureMode, GraphQLClient, RetryPolicy},
     error::EndpointKind,
+    graphql::get_studio_headers,
     RoverClientError,
 };
 
 use houston::{Credential, CredentialOrigin};
-use std::time::Duration;
 
 use graphql_client::GraphQLQuery;
 use reqwest::blocking::Client as ReqwestClient;
-use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
 
 /// Represents a client for making GraphQL requests to Apollo Studio.
 pub struct StudioClient {
@@ -28,16 +29,23 @@ impl StudioClient {
         version: &str,
         is_sudo: bool,
         client: ReqwestClient,
-        retry_period: Option<Duration>,
+        retry_policy: RetryPolicy,
     ) -> StudioClient {
         StudioClient {
             credential,
-            client: GraphQLClient::new(graphql_endpoint, client, retry_period),
+            client: GraphQLClient::new(graphql_endpoint, client, retry_policy),
             version: version.to_string(),
             is_sudo,
         }
     }
 
+    /// Records each response from Apollo Studio to a fixture file, or replays responses
+    /// from fixture files without using the network.
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> StudioClient {
+        self.client = self.client.with_fixtures(fixtures);
+        self
+    }
+
     /// Client method for making a GraphQL request to Apollo Studio.
     ///
     /// Takes one argument, `variables`. Returns a Response or a RoverClientError.
@@ -64,37 +72,35 @@ impl StudioClient {
             .post_no_retry::<Q>(variables, &mut header_map, EndpointKind::ApolloStudio)
     }
 
+    /// Runs any query or mutation against Apollo Studio, including ones rover has no
+    /// operation for, and returns the response's `data` as JSON.
+    ///
+    /// `operation_name` picks the operation to run when `query` defines several.
+    /// `variables` must be a JSON object if given. GraphQL errors in the response are
+    /// returned as [`RoverClientError::GraphQl`].
+    pub fn execute_raw(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Option<Value>,
+    ) -> Result<Value, RoverClientError> {
+        let mut header_map = self.build_studio_headers()?;
+        self.client.post_raw(
+            query,
+            operation_name,
+            variables.unwrap_or_else(|| Value::Object(Default::default())),
+            &mut header_map,
+            EndpointKind::ApolloStudio,
+        )
+    }
+
     /// Function for building a [HeaderMap] for making http requests. Use for making
     /// requests to Apollo Studio. We're leaving this separate from `build` since we
     /// need to be able to mark the api_key as sensitive (at the bottom)
     ///
     /// Takes an `api_key` and a `client_version`, and returns a [HeaderMap].
     pub fn build_studio_headers(&self) -> Result<HeaderMap, RoverClientError> {
-        let mut headers = HeaderMap::new();
-
-        // The headers "apollographql-client-name" and "apollographql-client-version"
-        // are used for client identification in Apollo Studio.
-
-        // This provides metrics in Studio that help keep track of what parts of the schema
-        // Rover uses, which ensures future changes to the API do not break Rover users.
-        // more info here:
-        // https://www.apollographql.com/docs/studio/client-awareness/#using-apollo-server-and-apollo-client
-
-        let client_name = HeaderValue::from_str(CLIENT_NAME)?;
-        headers.insert("apollographql-client-name", client_name);
-        tracing::debug!(?self.version);
-        let client_version = HeaderValue::from_str(&self.version)?;
-        headers.insert("apollographql-client-version", client_version);
-
-        let mut api_key = HeaderValue::from_str(&self.credential.api_key)?;
-        api_key.set_sensitive(true);
-        headers.insert("x-api-key", api_key);
-
-        if self.is_sudo {
-            headers.insert("apollo-sudo", HeaderValue::from_str("true")?);
-        }
-
-        Ok(headers)
+        get_studio_headers(&self.credential, &self.version, self.is_sudo)
     }
 
     pub fn get_credential_origin(&self) -> CredentialOrigin {
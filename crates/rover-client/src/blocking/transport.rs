@@ -0,0 +1,69 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// How a [`GraphQLClient`](crate::blocking::GraphQLClient) sends requests to its endpoint.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphQLTransport {
+    /// A POST request with a JSON body
+    #[default]
+    Post,
+    /// A GET request with the query, operation name and variables as query parameters
+    Get,
+    /// A single operation over GraphQL over WebSocket, using either the
+    /// `graphql-transport-ws` or the legacy `graphql-ws` subprotocol
+    WebSocket,
+}
+
+impl FromStr for GraphQLTransport {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "post" => Ok(GraphQLTransport::Post),
+            "get" => Ok(GraphQLTransport::Get),
+            "websocket" | "ws" => Ok(GraphQLTransport::WebSocket),
+            _ => Err(format!(
+                "'{input}' is not a valid transport, expected one of 'post', 'get' or 'websocket'"
+            )),
+        }
+    }
+}
+
+impl Display for GraphQLTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GraphQLTransport::Post => "post",
+            GraphQLTransport::Get => "get",
+            GraphQLTransport::WebSocket => "websocket",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_through_strings() {
+        for transport in [
+            GraphQLTransport::Post,
+            GraphQLTransport::Get,
+            GraphQLTransport::WebSocket,
+        ] {
+            assert_eq!(
+                GraphQLTransport::from_str(&transport.to_string()),
+                Ok(transport)
+            );
+            assert_eq!(
+                serde_json::to_value(transport).unwrap(),
+                serde_json::Value::String(transport.to_string())
+            );
+        }
+        assert_eq!("WS".parse(), Ok(GraphQLTransport::WebSocket));
+        assert!("put".parse::<GraphQLTransport>().is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use backoff::Error as BackoffError;
+use reqwest::header::{HeaderMap, HeaderValue, SEC_WEBSOCKET_PROTOCOL};
+use serde_json::{json, Value};
+use tungstenite::handshake::client::Request;
+use tungstenite::{
+    client::IntoClientRequest, stream::MaybeTlsStream, HandshakeError, Message, WebSocket,
+};
+
+use crate::{blocking::RetryPolicy, RoverClientError};
+
+/// How long to wait to connect and for each message when no timeout is configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Only one operation is sent per connection, so its id never changes
+const OPERATION_ID: &str = "1";
+
+type Socket = WebSocket<MaybeTlsStream<std::net::TcpStream>>;
+
+/// The GraphQL over WebSocket subprotocols Rover can speak. Both are offered
+/// during the handshake, and the server picks the one it supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subprotocol {
+    /// `graphql-transport-ws`, implemented by the `graphql-ws` library
+    GraphQLTransportWs,
+    /// `graphql-ws`, implemented by the legacy `subscriptions-transport-ws` library
+    GraphQLWs,
+}
+
+impl Subprotocol {
+    fn from_header(value: &str) -> Option<Self> {
+        match value.trim() {
+            "graphql-transport-ws" => Some(Subprotocol::GraphQLTransportWs),
+            "graphql-ws" => Some(Subprotocol::GraphQLWs),
+            _ => None,
+        }
+    }
+
+    fn subscribe_message(&self) -> &'static str {
+        match self {
+            Subprotocol::GraphQLTransportWs => "subscribe",
+            Subprotocol::GraphQLWs => "start",
+        }
+    }
+
+    fn result_message(&self) -> &'static str {
+        match self {
+            Subprotocol::GraphQLTransportWs => "next",
+            Subprotocol::GraphQLWs => "data",
+        }
+    }
+
+    fn stop_message(&self) -> &'static str {
+        match self {
+            Subprotocol::GraphQLTransportWs => "complete",
+            Subprotocol::GraphQLWs => "stop",
+        }
+    }
+}
+
+/// Sends a single GraphQL request over a WebSocket and returns the first result
+/// the server sends for it, in the same shape as an HTTP response body.
+///
+/// `request_body` is the JSON body that would have been POSTed. Only connecting
+/// is retried, since an operation that was sent may already have had side effects.
+/// `timeout` bounds connecting and each read and write on the socket.
+pub(crate) fn execute(
+    endpoint: &str,
+    request_body: &str,
+    header_map: &HeaderMap,
+    should_retry: bool,
+    retry_policy: &RetryPolicy,
+    timeout: Option<Duration>,
+) -> Result<Value, RoverClientError> {
+    let timeout = timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let payload: Value = serde_json::from_str(request_body)?;
+    let url = websocket_url(endpoint);
+    tracing::debug!("Opening WebSocket to {}", url);
+
+    let connect = || {
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| BackoffError::Permanent(websocket_error(e)))?;
+        request.headers_mut().extend(header_map.clone());
+        // tungstenite doesn't trim the offered subprotocols when checking the
+        // server's choice, so they're separated without spaces
+        request.headers_mut().insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static("graphql-transport-ws,graphql-ws"),
+        );
+        let stream = connect_tcp(&request, timeout)
+            .map_err(|e| BackoffError::transient(websocket_error(tungstenite::Error::Io(e))))?;
+        tungstenite::client_tls(request, stream).map_err(|e| match e {
+            HandshakeError::Failure(e @ tungstenite::Error::Io(_)) => {
+                BackoffError::transient(websocket_error(e))
+            }
+            HandshakeError::Failure(e) => BackoffError::Permanent(websocket_error(e)),
+            // the stream is blocking, so the handshake is never interrupted
+            HandshakeError::Interrupted(_) => {
+                BackoffError::Permanent(RoverClientError::ClientError {
+                    msg: "The WebSocket handshake was interrupted".to_string(),
+                })
+            }
+        })
+    };
+    let connection = if should_retry {
+        retry_policy.retry(connect)
+    } else {
+        connect().map_err(|e| match e {
+            BackoffError::Permanent(err) | BackoffError::Transient { err, .. } => err,
+        })
+    };
+    let (mut socket, response) = connection?;
+
+    let subprotocol = response
+        .headers()
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Subprotocol::from_header)
+        .unwrap_or(Subprotocol::GraphQLTransportWs);
+    tracing::debug!(?subprotocol, "WebSocket connected");
+
+    let result = run_operation(&mut socket, subprotocol, payload);
+    let _ = socket.close(None);
+    result
+}
+
+fn run_operation(
+    socket: &mut Socket,
+    subprotocol: Subprotocol,
+    payload: Value,
+) -> Result<Value, RoverClientError> {
+    send(socket, json!({ "type": "connection_init", "payload": {} }))?;
+    loop {
+        let message = read(socket)?;
+        let message_type = message.get("type").and_then(Value::as_str).unwrap_or("");
+        match message_type {
+            "connection_ack" => send(
+                socket,
+                json!({
+                    "id": OPERATION_ID,
+                    "type": subprotocol.subscribe_message(),
+                    "payload": payload,
+                }),
+            )?,
+            "ping" => send(socket, json!({ "type": "pong" }))?,
+            "connection_error" => {
+                return Err(RoverClientError::ClientError {
+                    msg: format!(
+                        "The server rejected the WebSocket connection: {}",
+                        message.get("payload").unwrap_or(&Value::Null)
+                    ),
+                })
+            }
+            "error" => {
+                // `graphql-transport-ws` sends a list of errors, `graphql-ws` a single error,
+                // either way they're reported like the errors in an HTTP response
+                let errors = match message.get("payload") {
+                    Some(Value::Array(errors)) => Value::Array(errors.clone()),
+                    Some(error) => json!([error]),
+                    None => json!([{ "message": "Unknown error" }]),
+                };
+                return Ok(json!({ "errors": errors }));
+            }
+            "complete" => {
+                return Err(RoverClientError::ClientError {
+                    msg: "The server completed the operation without sending a result".to_string(),
+                })
+            }
+            t if t == subprotocol.result_message() => {
+                let _ = send(
+                    socket,
+                    json!({ "id": OPERATION_ID, "type": subprotocol.stop_message() }),
+                );
+                return Ok(message.get("payload").cloned().unwrap_or(Value::Null));
+            }
+            // `ka` and `pong` keep the connection alive and need no response
+            _ => tracing::trace!(message = %message, "ignoring WebSocket message"),
+        }
+    }
+}
+
+fn send(socket: &mut Socket, message: Value) -> Result<(), RoverClientError> {
+    tracing::trace!(message = %message, "sending WebSocket message");
+    socket
+        .send(Message::Text(message.to_string()))
+        .map_err(websocket_error)
+}
+
+/// Reads the next GraphQL message, skipping WebSocket control frames
+fn read(socket: &mut Socket) -> Result<Value, RoverClientError> {
+    loop {
+        match socket.read().map_err(websocket_error)? {
+            Message::Text(text) => return Ok(serde_json::from_str(&text)?),
+            Message::Binary(bytes) => return Ok(serde_json::from_slice(&bytes)?),
+            Message::Close(frame) => {
+                return Err(RoverClientError::ClientError {
+                    msg: match frame {
+                        Some(frame) => format!(
+                            "The server closed the WebSocket connection: {} {}",
+                            frame.code, frame.reason
+                        ),
+                        None => "The server closed the WebSocket connection".to_string(),
+                    },
+                })
+            }
+            Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+        }
+    }
+}
+
+/// Opens a TCP connection to the request's host, giving up on each address after
+/// `timeout`. Reads and writes on the returned stream also time out after `timeout`,
+/// which bounds the TLS and WebSocket handshakes as well as the operation itself.
+fn connect_tcp(request: &Request, timeout: Duration) -> io::Result<TcpStream> {
+    let uri = request.uri();
+    let host = uri
+        .host()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the URL has no host"))?;
+    // IPv6 hosts are bracketed in URLs but not when resolving them
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+        Some("wss") => 443,
+        _ => 80,
+    });
+
+    let mut last_error = None;
+    for address in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("could not resolve {host}"))
+    }))
+}
+
+/// Lets the same `http(s)://` endpoint be used for every transport
+fn websocket_url(endpoint: &str) -> String {
+    if let Some(rest) = endpoint.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        endpoint.to_string()
+    }
+}
+
+fn websocket_error(error: tungstenite::Error) -> RoverClientError {
+    RoverClientError::ClientError {
+        msg: format!("WebSocket error: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+
+    use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    use super::*;
+
+    /// Accepts one connection with `subprotocol` and answers the operation with `result`
+    #[allow(clippy::result_large_err)]
+    fn serve_once(subprotocol: &'static str, result: Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept_hdr(
+                stream,
+                |request: &Request, mut response: Response| -> Result<Response, ErrorResponse> {
+                    assert_eq!(request.headers()["x-api-key"], "secret");
+                    response.headers_mut().insert(
+                        SEC_WEBSOCKET_PROTOCOL,
+                        HeaderValue::from_static(subprotocol),
+                    );
+                    Ok(response)
+                },
+            )
+            .unwrap();
+            let protocol = Subprotocol::from_header(subprotocol).unwrap();
+            loop {
+                let message: Value = match socket.read() {
+                    Ok(Message::Text(text)) => serde_json::from_str(&text).unwrap(),
+                    _ => break,
+                };
+                let reply = match message["type"].as_str().unwrap() {
+                    "connection_init" => json!({ "type": "connection_ack" }),
+                    t if t == protocol.subscribe_message() => {
+                        assert_eq!(message["payload"]["operationName"], "Q");
+                        json!({ "id": message["id"], "type": protocol.result_message(), "payload": result })
+                    }
+                    _ => continue,
+                };
+                socket.send(Message::Text(reply.to_string())).unwrap();
+            }
+        });
+        format!("http://{address}/graphql")
+    }
+
+    fn headers() -> HeaderMap {
+        let mut header_map = HeaderMap::new();
+        header_map.insert("x-api-key", HeaderValue::from_static("secret"));
+        header_map
+    }
+
+    #[test]
+    fn it_runs_an_operation_with_either_subprotocol() {
+        for subprotocol in ["graphql-transport-ws", "graphql-ws"] {
+            let result = json!({ "data": { "__typename": "Query" } });
+            let endpoint = serve_once(subprotocol, result.clone());
+            let response = execute(
+                &endpoint,
+                r#"{"query":"query Q { __typename }","operationName":"Q","variables":{}}"#,
+                &headers(),
+                false,
+                &RetryPolicy::default(),
+                None,
+            )
+            .unwrap();
+            assert_eq!(response, result);
+        }
+    }
+
+    #[test]
+    fn it_times_out_when_the_server_never_answers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}", listener.local_addr().unwrap());
+        // accept the connection but never complete the handshake
+        let server = thread::spawn(move || listener.accept().unwrap());
+
+        let started = std::time::Instant::now();
+        let response = execute(
+            &endpoint,
+            r#"{"query":"query Q { __typename }","operationName":"Q","variables":{}}"#,
+            &headers(),
+            false,
+            &RetryPolicy::default(),
+            Some(Duration::from_millis(200)),
+        );
+        assert!(response.is_err());
+        assert!(started.elapsed() < DEFAULT_TIMEOUT);
+        drop(server.join());
+    }
+
+    #[test]
+    fn it_converts_http_endpoints() {
+        assert_eq!(
+            websocket_url("https://example.com/graphql"),
+            "wss://example.com/graphql"
+        );
+        assert_eq!(
+            websocket_url("http://localhost:4000"),
+            "ws://localhost:4000"
+        );
+        assert_eq!(websocket_url("ws://localhost:4000"), "ws://localhost:4000");
+    }
+}
@@ -1,6 +1,10 @@
+use graphql_client::Error as GraphQLError;
+use serde_json::{json, Map, Value};
 use thiserror::Error;
 
-use crate::shared::{CheckTaskStatus, CheckWorkflowResponse, GraphRef, LintResponse};
+use crate::shared::{
+    BatchCheckWorkflowResponse, CheckTaskStatus, CheckWorkflowResponse, GraphRef, LintResponse,
+};
 
 use apollo_federation_types::build::BuildErrors;
 
@@ -12,6 +16,9 @@ pub enum RoverClientError {
     GraphQl {
         /// The encountered GraphQL error.
         msg: String,
+
+        /// The errors the endpoint returned, including their paths and extensions
+        errors: Vec<GraphQLError>,
     },
 
     /// Failed to parse Introspection Response coming from server.
@@ -159,6 +166,13 @@ pub enum RoverClientError {
         check_response: Box<CheckWorkflowResponse>,
     },
 
+    /// This error occurs when one or more subgraphs in a batch of checks failed.
+    #[error("{}", batch_check_workflow_error_msg(.batch_response))]
+    BatchCheckWorkflowFailure {
+        graph_ref: GraphRef,
+        batch_response: Box<BatchCheckWorkflowResponse>,
+    },
+
     /// While linting the proposed schema, some rule violations were found
     #[error("While linting the proposed schema, some rule violations were found")]
     LintFailures { lint_response: LintResponse },
@@ -236,6 +250,78 @@ pub enum RoverClientError {
 
     #[error("You've encountered a rate limit.")]
     RateLimitExceeded,
+
+    /// Replaying recorded responses, there was no recording for a request.
+    #[error("No response to the \"{operation_name}\" operation with these variables was recorded at {path}")]
+    NoRecordedResponse {
+        operation_name: String,
+        variables: Value,
+        path: String,
+    },
+}
+
+impl RoverClientError {
+    /// The machine-readable details of this error, like the graph ref, subgraph or
+    /// HTTP status it concerns, to include in JSON output alongside its message
+    pub fn fields(&self) -> Map<String, Value> {
+        let fields = match self {
+            RoverClientError::GraphQl { errors, .. } => json!({ "graphql_errors": errors }),
+            RoverClientError::SendRequest {
+                source,
+                endpoint_kind,
+            } => json!({
+                "http_status": source.status().map(|status| status.as_u16()),
+                "endpoint_kind": endpoint_kind.to_string(),
+            }),
+            RoverClientError::OrganizationNotFound { graph_id }
+            | RoverClientError::GraphIdNotFound { graph_id } => json!({ "graph_id": graph_id }),
+            RoverClientError::NoSubgraphInGraph {
+                invalid_subgraph,
+                valid_subgraphs,
+            } => json!({ "subgraph": invalid_subgraph, "valid_subgraphs": valid_subgraphs }),
+            RoverClientError::NoSchemaForVariant {
+                graph_ref,
+                valid_variants,
+                ..
+            } => json!({ "graph_ref": graph_ref.to_string(), "valid_variants": valid_variants }),
+            RoverClientError::SubgraphBuildErrors {
+                subgraph,
+                graph_ref,
+                ..
+            }
+            | RoverClientError::MissingRoutingUrlError {
+                subgraph_name: subgraph,
+                graph_ref,
+            } => json!({ "graph_ref": graph_ref.to_string(), "subgraph": subgraph }),
+            RoverClientError::GraphNotFound { graph_ref }
+            | RoverClientError::NoSupergraphBuilds { graph_ref, .. }
+            | RoverClientError::ExpectedFederatedGraph { graph_ref, .. }
+            | RoverClientError::ExpectedContractVariant { graph_ref }
+            | RoverClientError::CheckWorkflowFailure { graph_ref, .. }
+            | RoverClientError::BatchCheckWorkflowFailure { graph_ref, .. }
+            | RoverClientError::InvalidInputError { graph_ref }
+            | RoverClientError::NoPersistedQueryList { graph_ref, .. } => {
+                json!({ "graph_ref": graph_ref.to_string() })
+            }
+            RoverClientError::PersistedQueryListIdNotFound {
+                graph_id, list_id, ..
+            } => json!({ "graph_id": graph_id, "list_id": list_id }),
+            RoverClientError::MalformedResponse { null_field } => {
+                json!({ "null_field": null_field })
+            }
+            RoverClientError::ChecksTimeoutError { url } => json!({ "url": url }),
+            RoverClientError::NoRecordedResponse {
+                operation_name,
+                variables,
+                path,
+            } => json!({ "operation_name": operation_name, "variables": variables, "path": path }),
+            _ => Value::Null,
+        };
+        match fields {
+            Value::Object(fields) => fields,
+            _ => Map::new(),
+        }
+    }
 }
 
 fn contract_publish_errors_msg(msgs: &[String], no_launch: &bool) -> String {
@@ -263,6 +349,17 @@ pub enum EndpointKind {
     Orbiter,
 }
 
+impl std::fmt::Display for EndpointKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            EndpointKind::ApolloStudio => "apollo_studio",
+            EndpointKind::Customer => "customer",
+            EndpointKind::Orbiter => "orbiter",
+        };
+        write!(f, "{kind}")
+    }
+}
+
 fn check_workflow_error_msg(check_response: &CheckWorkflowResponse) -> String {
     let failed_tasks: Vec<&str> = [
         if let Some(operations_response) = &check_response.maybe_operations_response {
@@ -323,3 +420,16 @@ fn check_workflow_error_msg(check_response: &CheckWorkflowResponse) -> String {
         }
     }
 }
+
+fn batch_check_workflow_error_msg(batch_response: &BatchCheckWorkflowResponse) -> String {
+    let failed_subgraphs = batch_response.failed_subgraphs();
+    let plural = match failed_subgraphs.len() {
+        1 => "",
+        _ => "s",
+    };
+    format!(
+        "The changes you proposed caused checks to fail for subgraph{}: {}.",
+        plural,
+        failed_subgraphs.join(", ")
+    )
+}
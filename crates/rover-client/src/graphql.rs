@@ -0,0 +1,297 @@
+use backoff::Error as BackoffError;
+use graphql_client::{Error as GraphQLError, GraphQLQuery, Response as GraphQLResponse};
+use houston::Credential;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    StatusCode,
+};
+use serde_json::{json, Value};
+
+use crate::RoverClientError;
+
+pub(crate) const JSON_CONTENT_TYPE: &str = "application/json";
+
+pub(crate) const CLIENT_NAME: &str = "rover-client";
+
+/// The JSON body of a request for a query generated by `graphql_client`
+pub(crate) fn get_request_body<Q: GraphQLQuery>(
+    variables: Q::Variables,
+) -> Result<String, RoverClientError> {
+    let body = Q::build_query(variables);
+    Ok(serde_json::to_string(&body)?)
+}
+
+/// The JSON body of a request for a query built at runtime
+pub(crate) fn get_document_request_body(
+    query: &str,
+    operation_name: &str,
+) -> Result<String, RoverClientError> {
+    get_raw_request_body(
+        query,
+        Some(operation_name),
+        Value::Object(Default::default()),
+    )
+}
+
+/// The JSON body of a request for a query document and variables that rover knows
+/// nothing about, as run by [`StudioClient::execute_raw`](crate::blocking::StudioClient::execute_raw)
+pub(crate) fn get_raw_request_body(
+    query: &str,
+    operation_name: Option<&str>,
+    variables: Value,
+) -> Result<String, RoverClientError> {
+    if !variables.is_object() {
+        return Err(RoverClientError::AdhocError {
+            msg: format!(
+                "GraphQL variables must be a JSON object, not `{}`",
+                variables
+            ),
+        });
+    }
+    Ok(serde_json::to_string(&json!({
+        "query": query,
+        "operationName": operation_name,
+        "variables": variables,
+    }))?)
+}
+
+/// Builds a [HeaderMap] for making requests to Apollo Studio.
+///
+/// The api key is marked as sensitive so it's never logged.
+pub(crate) fn get_studio_headers(
+    credential: &Credential,
+    version: &str,
+    is_sudo: bool,
+) -> Result<HeaderMap, RoverClientError> {
+    let mut headers = HeaderMap::new();
+
+    // The headers "apollographql-client-name" and "apollographql-client-version"
+    // are used for client identification in Apollo Studio.
+
+    // This provides metrics in Studio that help keep track of what parts of the schema
+    // Rover uses, which ensures future changes to the API do not break Rover users.
+    // more info here:
+    // https://www.apollographql.com/docs/studio/client-awareness/#using-apollo-server-and-apollo-client
+
+    let client_name = HeaderValue::from_str(CLIENT_NAME)?;
+    headers.insert("apollographql-client-name", client_name);
+    tracing::debug!(?version);
+    let client_version = HeaderValue::from_str(version)?;
+    headers.insert("apollographql-client-version", client_version);
+
+    let mut api_key = HeaderValue::from_str(&credential.api_key)?;
+    api_key.set_sensitive(true);
+    headers.insert("x-api-key", api_key);
+
+    if is_sudo {
+        headers.insert("apollo-sudo", HeaderValue::from_str("true")?);
+    }
+
+    Ok(headers)
+}
+
+/// Converts a GraphQL request body into the query parameters of a GET request,
+/// as described by the GraphQL over HTTP spec.
+pub(crate) fn get_query_params(
+    request_body: &str,
+) -> Result<Vec<(String, String)>, RoverClientError> {
+    let body: Value = serde_json::from_str(request_body)?;
+    let mut query_params = Vec::new();
+    for key in ["query", "operationName", "variables", "extensions"] {
+        match body.get(key) {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(object)) if object.is_empty() => {}
+            Some(Value::String(value)) => query_params.push((key.to_string(), value.clone())),
+            Some(value) => query_params.push((key.to_string(), value.to_string())),
+        }
+    }
+    Ok(query_params)
+}
+
+/// Whether a request that failed to send might succeed if it's sent again
+pub(crate) fn classify_send_error(client_error: reqwest::Error) -> BackoffError<reqwest::Error> {
+    if client_error.is_timeout() || client_error.is_connect() {
+        BackoffError::transient(client_error)
+    } else if client_error.is_body()
+        || client_error.is_decode()
+        || client_error.is_builder()
+        || client_error.is_redirect()
+    {
+        BackoffError::Permanent(client_error)
+    } else if client_error.is_request() {
+        if let Some(hyper_error) = get_source_error_type::<hyper::Error>(&client_error) {
+            if hyper_error.is_incomplete_message() {
+                BackoffError::transient(client_error)
+            } else {
+                BackoffError::Permanent(client_error)
+            }
+        } else {
+            BackoffError::Permanent(client_error)
+        }
+    } else {
+        BackoffError::Permanent(client_error)
+    }
+}
+
+/// Returns the `data` of a response with `response_status`, or the errors in its body,
+/// or an error for its status if the body couldn't be parsed
+pub(crate) fn handle_status_and_body<T>(
+    response_status: StatusCode,
+    response_body: Result<GraphQLResponse<T>, RoverClientError>,
+) -> Result<T, RoverClientError> {
+    match response_body {
+        Ok(response_body) => match response_status {
+            StatusCode::OK => handle_response_body(response_body),
+            status_code => {
+                if let Some(response_body_errors) = response_body.errors {
+                    handle_graphql_body_errors(response_body_errors)?;
+                }
+                Err(RoverClientError::ClientError {
+                    msg: status_code.to_string(),
+                })
+            }
+        },
+        Err(e) => {
+            if response_status.is_success() {
+                Err(e)
+            } else {
+                Err(RoverClientError::ClientError {
+                    msg: response_status.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Returns the `data` of a successful response, or its errors
+pub(crate) fn handle_response_body<T>(
+    response_body: GraphQLResponse<T>,
+) -> Result<T, RoverClientError> {
+    if let Some(response_body_errors) = response_body.errors {
+        handle_graphql_body_errors(response_body_errors)?;
+    }
+    response_body
+        .data
+        .ok_or_else(|| RoverClientError::MalformedResponse {
+            null_field: "data".to_string(),
+        })
+}
+
+pub(crate) fn handle_graphql_body_errors(
+    errors: Vec<GraphQLError>,
+) -> Result<(), RoverClientError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        tracing::debug!("GraphQL response errors: {:?}", errors);
+        if errors[0].message == "406: Not Acceptable" {
+            Err(RoverClientError::MalformedKey)
+        } else {
+            Err(RoverClientError::GraphQl {
+                msg: errors
+                    .iter()
+                    .map(|error| error.message.as_str())
+                    .collect::<Vec<&str>>()
+                    .join("\n"),
+                errors,
+            })
+        }
+    }
+}
+
+/// Downcasts the given err source into T.
+fn get_source_error_type<T: std::error::Error + 'static>(
+    err: &dyn std::error::Error,
+) -> Option<&T> {
+    let mut source = err.source();
+
+    while let Some(err) = source {
+        if let Some(hyper_err) = err.downcast_ref::<T>() {
+            return Some(hyper_err);
+        }
+
+        source = err.source();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_is_ok_on_empty_errors() {
+        let errors = vec![];
+        assert!(handle_graphql_body_errors(errors).is_ok());
+    }
+
+    #[test]
+    fn it_returns_malformed_key() {
+        let errors = vec![GraphQLError {
+            message: "406: Not Acceptable".to_string(),
+            locations: None,
+            extensions: None,
+            path: None,
+        }];
+        let expected_error = RoverClientError::MalformedKey.to_string();
+        let actual_error = handle_graphql_body_errors(errors).unwrap_err().to_string();
+        assert_eq!(actual_error, expected_error);
+    }
+
+    #[test]
+    fn it_returns_random_graphql_error() {
+        let errors = vec![
+            GraphQLError {
+                message: "Something went wrong".to_string(),
+                locations: None,
+                extensions: None,
+                path: None,
+            },
+            GraphQLError {
+                message: "Something else went wrong".to_string(),
+                locations: None,
+                extensions: None,
+                path: None,
+            },
+        ];
+        let expected_error = RoverClientError::GraphQl {
+            msg: format!("{}\n{}", errors[0].message, errors[1].message),
+            errors: Vec::new(),
+        }
+        .to_string();
+        let actual_error = handle_graphql_body_errors(errors).unwrap_err().to_string();
+        assert_eq!(actual_error, expected_error);
+    }
+
+    #[test]
+    fn it_only_accepts_variables_objects() {
+        let body = get_raw_request_body("{ me { id } }", None, json!({ "id": 1 })).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({ "query": "{ me { id } }", "operationName": null, "variables": { "id": 1 } })
+        );
+        assert!(matches!(
+            get_raw_request_body("{ me { id } }", None, json!([1])),
+            Err(RoverClientError::AdhocError { .. })
+        ));
+    }
+
+    #[test]
+    fn it_builds_get_query_params() {
+        let query_params = get_query_params(
+            r#"{"query":"query Q($id: ID) { node(id: $id) { id } }","operationName":"Q","variables":{"id":"1"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            query_params,
+            vec![
+                (
+                    "query".to_string(),
+                    "query Q($id: ID) { node(id: $id) { id } }".to_string()
+                ),
+                ("operationName".to_string(), "Q".to_string()),
+                ("variables".to_string(), r#"{"id":"1"}"#.to_string()),
+            ]
+        );
+    }
+}
@@ -3,10 +3,14 @@
 //! HTTP client for making GraphQL requests for the Rover CLI tool.
 
 mod error;
+mod graphql;
 
 /// Module related to blocking http client.
 pub mod blocking;
 
+/// Module related to the async http client.
+pub mod nonblocking;
+
 /// Module for client related errors.
 pub use error::{EndpointKind, RoverClientError};
 
@@ -0,0 +1,282 @@
+use backoff::Error as BackoffError;
+use graphql_client::GraphQLQuery;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client as ReqwestClient, Response,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::blocking::{fixtures::FixtureKey, retry::retry_after};
+use crate::error::{EndpointKind, RoverClientError};
+use crate::graphql::{
+    classify_send_error, get_document_request_body, get_raw_request_body, get_request_body,
+    handle_status_and_body, JSON_CONTENT_TYPE,
+};
+use crate::nonblocking::{FixtureMode, RetryPolicy};
+
+/// Represents a generic GraphQL client for making async http requests.
+///
+/// Requests are always POSTed; use the [blocking](crate::blocking::GraphQLClient) client
+/// for GET or WebSocket transports.
+pub struct GraphQLClient {
+    graphql_endpoint: String,
+    client: ReqwestClient,
+    retry_policy: RetryPolicy,
+    fixtures: Option<FixtureMode>,
+}
+
+impl GraphQLClient {
+    /// Construct a new [GraphQLClient] from a `graphql_endpoint`.
+    pub fn new(
+        graphql_endpoint: &str,
+        client: ReqwestClient,
+        retry_policy: RetryPolicy,
+    ) -> GraphQLClient {
+        GraphQLClient {
+            graphql_endpoint: graphql_endpoint.to_string(),
+            client,
+            retry_policy,
+            fixtures: None,
+        }
+    }
+
+    /// Records each response to a fixture file, or replays responses from fixture files
+    /// without using the network.
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> GraphQLClient {
+        self.fixtures = Some(fixtures);
+        self
+    }
+
+    /// Client method for making a GraphQL request.
+    ///
+    /// Takes one argument, `variables`. Returns an optional response.
+    /// Automatically retries requests.
+    pub async fn post<Q>(
+        &self,
+        variables: Q::Variables,
+        header_map: &mut HeaderMap,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q: GraphQLQuery,
+    {
+        let request_body = get_request_body::<Q>(variables)?;
+        self.send::<Q::ResponseData>(request_body, header_map, true, endpoint_kind)
+            .await
+    }
+
+    /// Client method for making a GraphQL request.
+    ///
+    /// Takes one argument, `variables`. Returns an optional response.
+    /// Does not automatically retry requests.
+    pub async fn post_no_retry<Q>(
+        &self,
+        variables: Q::Variables,
+        header_map: &mut HeaderMap,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Q::ResponseData, RoverClientError>
+    where
+        Q: GraphQLQuery,
+    {
+        let request_body = get_request_body::<Q>(variables)?;
+        self.send::<Q::ResponseData>(request_body, header_map, false, endpoint_kind)
+            .await
+    }
+
+    /// Client method for making a GraphQL request whose query is built at runtime
+    /// rather than generated by `graphql_client`.
+    ///
+    /// Takes the query document and the name of the operation to run. Returns the
+    /// response's `data`, deserialized into `T`.
+    pub async fn post_document<T>(
+        &self,
+        query: &str,
+        operation_name: &str,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError>
+    where
+        T: DeserializeOwned,
+    {
+        let request_body = get_document_request_body(query, operation_name)?;
+        self.send::<T>(request_body, header_map, should_retry, endpoint_kind)
+            .await
+    }
+
+    /// Client method for making a GraphQL request with any query document and variables.
+    ///
+    /// Returns the response's `data` as JSON. Automatically retries requests.
+    pub async fn post_raw(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Value,
+        header_map: &mut HeaderMap,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Value, RoverClientError> {
+        let request_body = get_raw_request_body(query, operation_name, variables)?;
+        self.send::<Value>(request_body, header_map, true, endpoint_kind)
+            .await
+    }
+
+    /// Sends `request_body`, or replays its fixture, and returns the response's `data`.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request_body: String,
+        header_map: &mut HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<T, RoverClientError> {
+        if let Some(FixtureMode::Replay(dir)) = &self.fixtures {
+            let fixture = FixtureKey::from_request_body(&request_body)?.replay(dir)?;
+            let status = fixture.status();
+            let response_body = serde_json::from_value(fixture.response).map_err(Into::into);
+            return handle_status_and_body(status, response_body);
+        }
+
+        let fixture_key = match &self.fixtures {
+            Some(FixtureMode::Record(_)) => Some(FixtureKey::from_request_body(&request_body)?),
+            _ => None,
+        };
+        header_map.append("Content-Type", HeaderValue::from_str(JSON_CONTENT_TYPE)?);
+        let response = self
+            .execute(request_body, header_map, should_retry, endpoint_kind)
+            .await?;
+        let status = response.status();
+        let response_body = match response.json::<Value>().await {
+            Ok(response_body) => response_body,
+            Err(source) if status.is_success() => {
+                return Err(RoverClientError::SendRequest {
+                    source,
+                    endpoint_kind,
+                })
+            }
+            Err(_) => {
+                return Err(RoverClientError::ClientError {
+                    msg: status.to_string(),
+                })
+            }
+        };
+        tracing::debug!(?response_body);
+        if let (Some(FixtureMode::Record(dir)), Some(fixture_key)) = (&self.fixtures, fixture_key) {
+            fixture_key.record(dir, status, &response_body)?;
+        }
+        let response_body = serde_json::from_value(response_body).map_err(Into::into);
+        handle_status_and_body(status, response_body)
+    }
+
+    async fn execute(
+        &self,
+        request_body: String,
+        header_map: &HeaderMap,
+        should_retry: bool,
+        endpoint_kind: EndpointKind,
+    ) -> Result<Response, RoverClientError> {
+        tracing::trace!(request_headers = ?header_map);
+        tracing::debug!("Request Body: {}", request_body);
+        let graphql_operation = || async {
+            let response = self
+                .client
+                .post(&self.graphql_endpoint)
+                .headers(header_map.clone())
+                .body(request_body.clone())
+                .send()
+                .await;
+
+            match response {
+                Err(client_error) => Err(classify_send_error(client_error)),
+                Ok(success) => match success.error_for_status_ref().err() {
+                    None => Ok(success),
+                    Some(status_error)
+                        if self.retry_policy.is_retryable_status(success.status()) =>
+                    {
+                        Err(BackoffError::Transient {
+                            retry_after: retry_after(success.headers()),
+                            err: status_error,
+                        })
+                    }
+                    Some(status_error) => {
+                        if let Ok(text) = success.text().await {
+                            tracing::debug!("{}", text);
+                        }
+                        Err(BackoffError::Permanent(status_error))
+                    }
+                },
+            }
+        };
+
+        let result = if should_retry {
+            self.retry_policy.retry_async(graphql_operation).await
+        } else {
+            graphql_operation().await.map_err(|e| match e {
+                BackoffError::Permanent(err) | BackoffError::Transient { err, .. } => err,
+            })
+        };
+        result.map_err(|reqwest_error| RoverClientError::SendRequest {
+            source: reqwest_error,
+            endpoint_kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use httpmock::{Method::POST, MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_retries_until_the_server_recovers() {
+        let server = MockServer::start_async().await;
+        let unavailable = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/graphql");
+                then.status(503).header("Retry-After", "0");
+            })
+            .await;
+
+        let client = GraphQLClient::new(
+            &server.url("/graphql"),
+            ReqwestClient::new(),
+            RetryPolicy::default()
+                .with_max_elapsed_time(Duration::from_secs(3))
+                .with_max_retries(1),
+        );
+        let result = client
+            .post_document::<Value>(
+                "{ __typename }",
+                "Typename",
+                &mut HeaderMap::new(),
+                true,
+                EndpointKind::Customer,
+            )
+            .await;
+        assert!(result.is_err());
+        unavailable.assert_hits_async(2).await;
+        unavailable.delete_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method(POST).path("/graphql");
+                then.status(200)
+                    .json_body(json!({ "data": { "__typename": "Query" } }));
+            })
+            .await;
+        let data = client
+            .post_document::<Value>(
+                "{ __typename }",
+                "Typename",
+                &mut HeaderMap::new(),
+                true,
+                EndpointKind::Customer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(data, json!({ "__typename": "Query" }));
+    }
+}
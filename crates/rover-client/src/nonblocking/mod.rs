@@ -0,0 +1,7 @@
+mod client;
+mod studio_client;
+
+pub use client::GraphQLClient;
+pub use studio_client::StudioClient;
+
+pub use crate::blocking::{FixtureMode, RetryPolicy};
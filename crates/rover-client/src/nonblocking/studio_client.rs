@@ -0,0 +1,128 @@
+use crate::{
+    error::EndpointKind,
+    graphql::get_studio_headers,
+    nonblocking::{FixtureMode, GraphQLClient, RetryPolicy},
+    RoverClientError,
+};
+
+use houston::{Credential, CredentialOrigin};
+
+use graphql_client::GraphQLQuery;
+use reqwest::header::HeaderMap;
+use reqwest::Client as ReqwestClient;
+use serde_json::Value;
+
+/// Represents a client for making async GraphQL requests to Apollo Studio.
+pub struct StudioClient {
+    pub credential: Credential,
+    client: GraphQLClient,
+    version: String,
+    is_sudo: bool,
+}
+
+impl StudioClient {
+    /// Construct a new [StudioClient] from an `api_key`, a `uri`, and a `version`.
+    /// For use in Rover, the `uri` is usually going to be to Apollo Studio
+    pub fn new(
+        credential: Credential,
+        graphql_endpoint: &str,
+        version: &str,
+        is_sudo: bool,
+        client: ReqwestClient,
+        retry_policy: RetryPolicy,
+    ) -> StudioClient {
+        StudioClient {
+            credential,
+            client: GraphQLClient::new(graphql_endpoint, client, retry_policy),
+            version: version.to_string(),
+            is_sudo,
+        }
+    }
+
+    /// Records each response from Apollo Studio to a fixture file, or replays responses
+    /// from fixture files without using the network.
+    pub fn with_fixtures(mut self, fixtures: FixtureMode) -> StudioClient {
+        self.client = self.client.with_fixtures(fixtures);
+        self
+    }
+
+    /// Client method for making a GraphQL request to Apollo Studio.
+    ///
+    /// Takes one argument, `variables`. Returns a Response or a RoverClientError.
+    /// Automatically retries requests.
+    pub async fn post<Q: GraphQLQuery>(
+        &self,
+        variables: Q::Variables,
+    ) -> Result<Q::ResponseData, RoverClientError> {
+        let mut header_map = self.build_studio_headers()?;
+        self.client
+            .post::<Q>(variables, &mut header_map, EndpointKind::ApolloStudio)
+            .await
+    }
+
+    /// Client method for making a GraphQL request to Apollo Studio.
+    ///
+    /// Takes one argument, `variables`. Returns a Response or a RoverClientError.
+    /// Does not automatically retry requests.
+    pub async fn post_no_retry<Q: GraphQLQuery>(
+        &self,
+        variables: Q::Variables,
+    ) -> Result<Q::ResponseData, RoverClientError> {
+        let mut header_map = self.build_studio_headers()?;
+        self.client
+            .post_no_retry::<Q>(variables, &mut header_map, EndpointKind::ApolloStudio)
+            .await
+    }
+
+    /// Runs any query or mutation against Apollo Studio, including ones rover has no
+    /// operation for, and returns the response's `data` as JSON.
+    ///
+    /// `operation_name` picks the operation to run when `query` defines several.
+    /// `variables` must be a JSON object if given. GraphQL errors in the response are
+    /// returned as [`RoverClientError::GraphQl`].
+    pub async fn execute_raw(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Option<Value>,
+    ) -> Result<Value, RoverClientError> {
+        let mut header_map = self.build_studio_headers()?;
+        self.client
+            .post_raw(
+                query,
+                operation_name,
+                variables.unwrap_or_else(|| Value::Object(Default::default())),
+                &mut header_map,
+                EndpointKind::ApolloStudio,
+            )
+            .await
+    }
+
+    /// Builds the [HeaderMap] for requests to Apollo Studio, with the api key marked
+    /// as sensitive.
+    pub fn build_studio_headers(&self) -> Result<HeaderMap, RoverClientError> {
+        get_studio_headers(&self.credential, &self.version, self.is_sudo)
+    }
+
+    pub fn get_credential_origin(&self) -> CredentialOrigin {
+        self.credential.origin.clone()
+    }
+}
+
+#[cfg(test)]
+impl StudioClient {
+    /// A client for `graphql_endpoint` with a placeholder API key
+    pub(crate) fn for_tests(graphql_endpoint: &str) -> StudioClient {
+        StudioClient::new(
+            Credential {
+                api_key: "test-key".to_string(),
+                origin: CredentialOrigin::EnvVar,
+            },
+            graphql_endpoint,
+            "0.0.0",
+            false,
+            ReqwestClient::new(),
+            RetryPolicy::default(),
+        )
+    }
+}
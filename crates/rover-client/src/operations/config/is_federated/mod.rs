@@ -1,5 +1,5 @@
 mod runner;
 mod types;
 
-pub(crate) use runner::run;
+pub(crate) use runner::{run, run_async};
 pub(crate) use types::IsFederatedInput;
@@ -1,6 +1,7 @@
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::config::is_federated::IsFederatedInput;
 use crate::shared::GraphRef;
 use crate::RoverClientError;
@@ -28,6 +29,15 @@ pub(crate) fn run(
     build_response(data, graph_ref)
 }
 
+pub(crate) async fn run_async(
+    input: IsFederatedInput,
+    client: &AsyncStudioClient,
+) -> Result<bool, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let data = client.post::<IsFederatedGraph>(input.into()).await?;
+    build_response(data, graph_ref)
+}
+
 fn build_response(
     data: is_federated_graph::ResponseData,
     graph_ref: GraphRef,
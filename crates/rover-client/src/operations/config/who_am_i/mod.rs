@@ -1,5 +1,5 @@
 mod runner;
 mod types;
 
-pub use runner::run;
+pub use runner::{run, run_async};
 pub use types::{Actor, ConfigWhoAmIInput, RegistryIdentity};
@@ -1,4 +1,5 @@
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::config::who_am_i::{
     types::{QueryActorType, QueryResponseData, RegistryIdentity},
     Actor, ConfigWhoAmIInput,
@@ -33,6 +34,14 @@ pub fn run(
     get_identity_from_response_data(response_data, client.get_credential_origin())
 }
 
+pub async fn run_async(
+    input: ConfigWhoAmIInput,
+    client: &AsyncStudioClient,
+) -> Result<RegistryIdentity, RoverClientError> {
+    let response_data = client.post::<ConfigWhoAmIQuery>(input.into()).await?;
+    get_identity_from_response_data(response_data, client.get_credential_origin())
+}
+
 fn get_identity_from_response_data(
     response_data: QueryResponseData,
     credential_origin: CredentialOrigin,
@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::graph::check_workflow::types::{CheckWorkflowInput, QueryResponseData};
 use crate::shared::{
     CheckWorkflowResponse, Diagnostic, GraphRef, LintCheckResponse, OperationCheckResponse,
@@ -68,6 +69,41 @@ pub fn run(
     }
 }
 
+/// Like [`run`], but waits between polls without blocking the thread.
+pub async fn run_async(
+    input: CheckWorkflowInput,
+    client: &AsyncStudioClient,
+) -> Result<CheckWorkflowResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let mut url: Option<String> = None;
+    let now = Instant::now();
+    loop {
+        let result = client
+            .post::<GraphCheckWorkflowQuery>(input.clone().into())
+            .await;
+        match result {
+            Ok(data) => {
+                let graph = data.clone().graph.ok_or(RoverClientError::GraphNotFound {
+                    graph_ref: graph_ref.clone(),
+                })?;
+                if let Some(check_workflow) = graph.check_workflow {
+                    if !matches!(check_workflow.status, CheckWorkflowStatus::PENDING) {
+                        return get_check_response_from_data(data, graph_ref);
+                    }
+                }
+                url = get_target_url_from_data(data);
+            }
+            Err(e) => {
+                eprintln!("error while checking status of check: {e}\nthis error may be transient... retrying");
+            }
+        }
+        if now.elapsed() > Duration::from_secs(input.checks_timeout_seconds) {
+            return Err(RoverClientError::ChecksTimeoutError { url });
+        }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+}
+
 fn get_check_response_from_data(
     data: QueryResponseData,
     graph_ref: GraphRef,
@@ -1,6 +1,7 @@
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::graph::fetch::GraphFetchInput;
 use crate::shared::{FetchResponse, GraphRef, Sdl, SdlType};
 use crate::RoverClientError;
@@ -40,6 +41,21 @@ pub fn run(
     })
 }
 
+pub async fn run_async(
+    input: GraphFetchInput,
+    client: &AsyncStudioClient,
+) -> Result<FetchResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<GraphFetchQuery>(input.into()).await?;
+    let sdl_contents = get_schema_from_response_data(response_data, graph_ref)?;
+    Ok(FetchResponse {
+        sdl: Sdl {
+            contents: sdl_contents,
+            r#type: SdlType::Graph,
+        },
+    })
+}
+
 fn get_schema_from_response_data(
     response_data: graph_fetch_query::ResponseData,
     graph_ref: GraphRef,
@@ -67,10 +83,42 @@ fn get_schema_from_response_data(
 
 #[cfg(test)]
 mod tests {
+    use httpmock::{Method::POST, MockServer};
     use serde_json::json;
 
     use super::*;
 
+    #[tokio::test]
+    async fn run_async_fetches_the_schema() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/graphql")
+                    .body_contains(r#""operationName":"GraphFetchQuery""#);
+                then.status(200).json_body(json!({ "data": {
+                    "frontendUrlRoot": "https://studio.apollographql.com",
+                    "graph": {
+                        "variant": {
+                            "latestPublication": {
+                                "schema": { "document": "type Query { hello: String }" }
+                            }
+                        },
+                        "variants": [{ "name": "current" }]
+                    }
+                }}));
+            })
+            .await;
+
+        let client = AsyncStudioClient::for_tests(&server.url("/graphql"));
+        let input = GraphFetchInput {
+            graph_ref: mock_graph_ref(),
+        };
+        let response = run_async(input, &client).await.unwrap();
+        assert_eq!(response.sdl.contents, "type Query { hello: String }");
+        mock.assert_async().await;
+    }
+
     #[test]
     fn get_schema_from_response_data_works() {
         let json_response = json!({
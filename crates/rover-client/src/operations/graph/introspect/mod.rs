@@ -1,7 +1,10 @@
+mod query;
 mod runner;
 mod schema;
 mod types;
 
-pub use runner::run;
+pub use runner::{from_json, run, run_json};
 pub use schema::Schema;
-pub use types::{GraphIntrospectInput, GraphIntrospectResponse};
+pub use types::{
+    GraphIntrospectInput, GraphIntrospectResponse, IntrospectionOptions, DEFAULT_TYPE_DEPTH,
+};
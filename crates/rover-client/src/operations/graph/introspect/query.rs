@@ -0,0 +1,205 @@
+use std::fmt::Write;
+
+use crate::operations::graph::introspect::types::IntrospectionOptions;
+
+pub(crate) const OPERATION_NAME: &str = "GraphIntrospectQuery";
+
+/// Builds the introspection query for a set of [`IntrospectionOptions`]. The default
+/// options request the same fields as the query Rover used before they were configurable.
+pub(crate) fn build_query(options: &IntrospectionOptions) -> String {
+    let description = if options.include_descriptions {
+        "\n    description"
+    } else {
+        ""
+    };
+    let include_deprecated = if options.include_deprecated_input_values {
+        "(includeDeprecated: true)"
+    } else {
+        ""
+    };
+    let input_value_deprecation = if options.include_deprecated_input_values {
+        "\n  isDeprecated\n  deprecationReason"
+    } else {
+        ""
+    };
+    let is_repeatable = if options.include_is_repeatable {
+        "\n      isRepeatable"
+    } else {
+        ""
+    };
+    let specified_by_url = if options.include_specified_by_url {
+        "\n  specifiedByURL"
+    } else {
+        ""
+    };
+
+    let mut query = String::new();
+    // writing to a String can't fail
+    let _ = write!(
+        query,
+        r#"query {OPERATION_NAME} {{
+  __schema {{
+    queryType {{
+      name
+    }}
+    mutationType {{
+      name
+    }}
+    subscriptionType {{
+      name
+    }}
+    types {{
+      ...FullType
+    }}
+    directives {{
+      name{directive_description}{is_repeatable}
+      locations
+      args{include_deprecated} {{
+        ...InputValue
+      }}
+    }}
+  }}
+}}
+
+fragment FullType on __Type {{
+  kind
+  name{type_description}{specified_by_url}
+  fields(includeDeprecated: true) {{
+    name{description}
+    args{include_deprecated} {{
+      ...InputValue
+    }}
+    type {{
+      ...TypeRef
+    }}
+    isDeprecated
+    deprecationReason
+  }}
+  inputFields{include_deprecated} {{
+    ...InputValue
+  }}
+  interfaces {{
+    ...TypeRef
+  }}
+  enumValues(includeDeprecated: true) {{
+    name{description}
+    isDeprecated
+    deprecationReason
+  }}
+  possibleTypes {{
+    ...TypeRef
+  }}
+}}
+
+fragment InputValue on __InputValue {{
+  name{type_description}
+  type {{
+    ...TypeRef
+  }}
+  defaultValue{input_value_deprecation}
+}}
+
+fragment TypeRef on __Type {{
+{type_ref}}}
+"#,
+        directive_description = description.replace("\n    ", "\n      "),
+        type_description = description.replace("\n    ", "\n  "),
+        type_ref = type_ref(options.type_depth),
+    );
+    query
+}
+
+/// `kind` and `name`, wrapped in `depth` levels of `ofType`
+fn type_ref(depth: usize) -> String {
+    let mut selection = String::new();
+    for level in 0..=depth {
+        let indent = "  ".repeat(level + 1);
+        let _ = write!(selection, "{indent}kind\n{indent}name\n");
+        if level < depth {
+            let _ = writeln!(selection, "{indent}ofType {{");
+        }
+    }
+    for level in (0..depth).rev() {
+        let indent = "  ".repeat(level + 1);
+        let _ = writeln!(selection, "{indent}}}");
+    }
+    selection
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_nests_type_refs_to_the_requested_depth() {
+        assert_eq!(
+            type_ref(2),
+            indoc! {"
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                }
+              }
+            "}
+            .lines()
+            .map(|line| format!("  {line}\n"))
+            .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn it_builds_a_parseable_query_for_every_option() {
+        let options = [
+            IntrospectionOptions::default(),
+            IntrospectionOptions {
+                type_depth: 12,
+                include_descriptions: false,
+                include_deprecated_input_values: true,
+                include_is_repeatable: true,
+                include_specified_by_url: true,
+            },
+        ];
+        for options in options {
+            let query = build_query(&options);
+            let tree = apollo_parser::Parser::new(&query).parse();
+            assert!(tree.errors().next().is_none(), "{query}");
+            assert_eq!(
+                query.contains("description"),
+                options.include_descriptions,
+                "{query}"
+            );
+            assert_eq!(
+                query.contains("isRepeatable"),
+                options.include_is_repeatable
+            );
+            assert_eq!(
+                query.contains("specifiedByURL"),
+                options.include_specified_by_url
+            );
+            assert_eq!(
+                query.matches("ofType {").count(),
+                options.type_depth,
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_requests_deprecated_input_values() {
+        let query = build_query(&IntrospectionOptions {
+            include_deprecated_input_values: true,
+            ..Default::default()
+        });
+        assert!(query.contains("inputFields(includeDeprecated: true)"));
+        assert!(query.contains("args(includeDeprecated: true)"));
+        assert!(query.contains("defaultValue\n  isDeprecated\n  deprecationReason"));
+    }
+}
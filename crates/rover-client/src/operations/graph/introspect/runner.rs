@@ -1,24 +1,12 @@
 use crate::blocking::GraphQLClient;
 use crate::error::{EndpointKind, RoverClientError};
+use crate::operations::graph::introspect::query::{build_query, OPERATION_NAME};
 use crate::operations::graph::introspect::{types::*, Schema};
 
-use graphql_client::GraphQLQuery;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::Value;
 
-use std::convert::{Into, TryFrom};
-
-#[derive(GraphQLQuery)]
-#[graphql(
-    query_path = "src/operations/graph/introspect/introspect_query.graphql",
-    schema_path = "src/operations/graph/introspect/introspect_schema.graphql",
-    response_derives = "PartialEq, Eq, Debug, Serialize, Deserialize",
-    deprecated = "warn"
-)]
-
-/// This struct is used to generate the module containing `Variables` and
-/// `ResponseData` structs.
-/// Snake case of this name is the mod name. i.e. graph_introspect_query
-pub(crate) struct GraphIntrospectQuery;
+use std::convert::TryFrom;
 
 /// The main function to be used from this module. This function fetches a
 /// schema from apollo studio and returns it in either sdl (default) or json format
@@ -27,7 +15,18 @@ pub fn run(
     client: &GraphQLClient,
     should_retry: bool,
 ) -> Result<GraphIntrospectResponse, RoverClientError> {
-    let variables = input.clone().into();
+    let response_data = run_json(input, client, should_retry)?;
+    build_response(serde_json::from_value(response_data)?)
+}
+
+/// Fetches the introspection result without converting it to SDL, returning the
+/// `data` of the response exactly as the server sent it.
+pub fn run_json(
+    input: GraphIntrospectInput,
+    client: &GraphQLClient,
+    should_retry: bool,
+) -> Result<Value, RoverClientError> {
+    let query = build_query(&input.options);
     let mut header_map = HeaderMap::new();
     for (header_key, header_value) in input.headers {
         header_map.insert(
@@ -35,26 +34,103 @@ pub fn run(
             HeaderValue::from_str(&header_value)?,
         );
     }
-    let response_data = if should_retry {
-        client.post::<GraphIntrospectQuery>(variables, &mut header_map, EndpointKind::Customer)
-    } else {
-        client.post_no_retry::<GraphIntrospectQuery>(
-            variables,
-            &mut header_map,
-            EndpointKind::Customer,
-        )
-    }?;
-
-    build_response(response_data)
+    client.post_document::<Value>(
+        &query,
+        OPERATION_NAME,
+        &mut header_map,
+        should_retry,
+        EndpointKind::Customer,
+    )
+}
+
+/// Converts a saved introspection result to SDL. Accepts either an entire
+/// GraphQL response or just its `data`.
+pub fn from_json(introspection: Value) -> Result<GraphIntrospectResponse, RoverClientError> {
+    let response_data = match introspection {
+        Value::Object(mut response) if response.contains_key("data") => {
+            match response.remove("data") {
+                Some(Value::Null) | None => {
+                    let errors = response
+                        .get("errors")
+                        .and_then(Value::as_array)
+                        .map(|errors| {
+                            errors
+                                .iter()
+                                .filter_map(|error| error.get("message")?.as_str())
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        })
+                        .unwrap_or_default();
+                    return Err(RoverClientError::IntrospectionError {
+                        msg: format!("The introspection result has no data. {}", errors)
+                            .trim_end()
+                            .to_string(),
+                    });
+                }
+                Some(data) => data,
+            }
+        }
+        data => data,
+    };
+    build_response(serde_json::from_value(response_data).map_err(|e| {
+        RoverClientError::IntrospectionError {
+            msg: format!("The introspection result is malformed: {}", e),
+        }
+    })?)
 }
 
 fn build_response(
     response: QueryResponseData,
 ) -> Result<GraphIntrospectResponse, RoverClientError> {
-    match Schema::try_from(response) {
-        Ok(schema) => Ok(GraphIntrospectResponse {
-            schema_sdl: schema.encode(),
-        }),
-        Err(msg) => Err(RoverClientError::IntrospectionError { msg: msg.into() }),
+    match Schema::try_from(response).and_then(Schema::encode) {
+        Ok(schema_sdl) => Ok(GraphIntrospectResponse { schema_sdl }),
+        Err(msg) => Err(RoverClientError::IntrospectionError { msg }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn introspection_data() -> Value {
+        json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "types": [{
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [{
+                        "name": "hello",
+                        "args": [],
+                        "type": { "kind": "SCALAR", "name": "String", "ofType": null },
+                        "isDeprecated": false
+                    }],
+                    "interfaces": []
+                }],
+                "directives": []
+            }
+        })
+    }
+
+    #[test]
+    fn it_converts_saved_data_and_responses() {
+        let from_data = from_json(introspection_data()).unwrap();
+        let from_response = from_json(json!({ "data": introspection_data() })).unwrap();
+
+        assert_eq!(from_data, from_response);
+        assert!(from_data.schema_sdl.contains("hello: String"));
+    }
+
+    #[test]
+    fn it_reports_errors_in_saved_responses() {
+        let error = from_json(json!({
+            "data": null,
+            "errors": [{ "message": "introspection is disabled" }]
+        }))
+        .unwrap_err();
+
+        assert!(error.to_string().contains("introspection is disabled"));
     }
 }
@@ -11,18 +11,10 @@ use apollo_encoder::{
 use serde::Deserialize;
 use std::convert::TryFrom;
 
-use crate::operations::graph::introspect::runner::graph_introspect_query;
-
-type FullTypeField = graph_introspect_query::FullTypeFields;
-type FullTypeInputField = graph_introspect_query::FullTypeInputFields;
-type FullTypeFieldArg = graph_introspect_query::FullTypeFieldsArgs;
-type IntrospectionResult = graph_introspect_query::ResponseData;
-type SchemaMutationType = graph_introspect_query::GraphIntrospectQuerySchemaMutationType;
-type SchemaQueryType = graph_introspect_query::GraphIntrospectQuerySchemaQueryType;
-type SchemaType = graph_introspect_query::GraphIntrospectQuerySchemaTypes;
-type SchemaDirective = graph_introspect_query::GraphIntrospectQuerySchemaDirectives;
-type SchemaSubscriptionType = graph_introspect_query::GraphIntrospectQuerySchemaSubscriptionType;
-type __TypeKind = graph_introspect_query::__TypeKind;
+use crate::operations::graph::introspect::types::{
+    Field, FullType, InputValue, IntrospectionDirective, QueryResponseData, RootType, TypeKind,
+    TypeRef,
+};
 
 // Represents GraphQL types we will not be encoding to SDL.
 const GRAPHQL_NAMED_TYPES: [&str; 13] = [
@@ -41,6 +33,11 @@ const GRAPHQL_NAMED_TYPES: [&str; 13] = [
     "ID",
 ];
 
+const INCOMPLETE_TYPE_REF: &str =
+    "A type reference is nested deeper than the introspection query requested";
+
+const TRY_LARGER_DEPTH: &str = "Try again with a larger type depth.";
+
 // Represents GraphQL directives we will not be encoding to SDL.
 const SPECIFIED_DIRECTIVES: [&str; 3] = ["skip", "include", "deprecated"];
 
@@ -50,16 +47,18 @@ const SPECIFIED_DIRECTIVES: [&str; 3] = ["skip", "include", "deprecated"];
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Schema {
-    types: Vec<SchemaType>,
-    directives: Vec<SchemaDirective>,
-    mutation_type: Option<SchemaMutationType>,
-    query_type: SchemaQueryType,
-    subscription_type: Option<SchemaSubscriptionType>,
+    types: Vec<FullType>,
+    directives: Vec<IntrospectionDirective>,
+    mutation_type: Option<RootType>,
+    query_type: RootType,
+    subscription_type: Option<RootType>,
 }
 
 impl Schema {
     /// Encode Schema into an SDL.
-    pub fn encode(self) -> String {
+    ///
+    /// Errors if a type reference is incomplete, which `try_from` checks for already.
+    pub fn encode(self) -> Result<String, String> {
         let mut sdl = SDL::new();
 
         // When we have a defined mutation and subscription, we record
@@ -89,46 +88,54 @@ impl Schema {
         }
 
         // Exclude GraphQL directives like 'skip' and 'include' before encoding directives.
-        self.directives
+        for directive in self
+            .directives
             .into_iter()
             .filter(|directive| !SPECIFIED_DIRECTIVES.contains(&directive.name.as_str()))
-            .for_each(|directive| Self::encode_directives(directive, &mut sdl));
+        {
+            Self::encode_directives(directive, &mut sdl)?;
+        }
 
         // Exclude GraphQL named types like __Schema before encoding full type.
-        self.types
+        for type_ in self
+            .types
             .into_iter()
             .filter(|type_| match type_.name.as_deref() {
                 Some(name) => !GRAPHQL_NAMED_TYPES.contains(&name),
                 None => false,
             })
-            .for_each(|type_| Self::encode_full_type(type_, &mut sdl));
+        {
+            Self::encode_full_type(type_, &mut sdl)?;
+        }
 
-        sdl.to_string()
+        Ok(sdl.to_string())
     }
 
-    fn encode_directives(directive: SchemaDirective, sdl: &mut SDL) {
+    fn encode_directives(directive: IntrospectionDirective, sdl: &mut SDL) -> Result<(), String> {
         let mut directive_ = DirectiveDefinition::new(directive.name);
         if let Some(desc) = directive.description {
             directive_.description(desc);
         }
         for arg in directive.args {
-            let input_value = Self::encode_arg(arg);
+            let input_value = Self::encode_arg(arg)?;
             directive_.arg(input_value);
         }
 
         for location in directive.locations {
-            // Location is of a __DirectiveLocation enum that doesn't implement
-            // Display (meaning we can't just do .to_string). This next line
-            // just forces it into a String with format! debug.
-            directive_.location(format!("{:?}", location));
+            directive_.location(location);
+        }
+
+        if directive.is_repeatable == Some(true) {
+            directive_.repeatable();
         }
 
-        sdl.directive(directive_)
+        sdl.directive(directive_);
+        Ok(())
     }
 
-    fn encode_full_type(type_: SchemaType, sdl: &mut SDL) {
+    fn encode_full_type(type_: FullType, sdl: &mut SDL) -> Result<(), String> {
         match type_.kind {
-            __TypeKind::OBJECT => {
+            TypeKind::Object => {
                 let mut object_def = ObjectDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     object_def.description(desc);
@@ -140,26 +147,26 @@ impl Schema {
                 }
                 if let Some(field) = type_.fields {
                     for f in field {
-                        let field_def = Self::encode_field(f);
+                        let field_def = Self::encode_field(f)?;
                         object_def.field(field_def);
                     }
                     sdl.object(object_def);
                 }
             }
-            __TypeKind::INPUT_OBJECT => {
+            TypeKind::InputObject => {
                 let mut input_def = InputObjectDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     input_def.description(desc);
                 }
                 if let Some(field) = type_.input_fields {
                     for f in field {
-                        let input_field_def = Self::encode_input_field(f);
+                        let input_field_def = Self::encode_input_field(f)?;
                         input_def.field(input_field_def);
                     }
                     sdl.input_object(input_def);
                 }
             }
-            __TypeKind::INTERFACE => {
+            TypeKind::Interface => {
                 let mut interface_def = InterfaceDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     interface_def.description(desc);
@@ -171,20 +178,25 @@ impl Schema {
                 }
                 if let Some(field) = type_.fields {
                     for f in field {
-                        let field_def = Self::encode_field(f);
+                        let field_def = Self::encode_field(f)?;
                         interface_def.field(field_def);
                     }
                     sdl.interface(interface_def);
                 }
             }
-            __TypeKind::SCALAR => {
+            TypeKind::Scalar => {
                 let mut scalar_def = ScalarDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     scalar_def.description(desc);
                 }
+                if let Some(url) = type_.specified_by_url {
+                    let mut specified_by = Directive::new(String::from("specifiedBy"));
+                    specified_by.arg(Argument::new(String::from("url"), Value::String(url)));
+                    scalar_def.directive(specified_by);
+                }
                 sdl.scalar(scalar_def);
             }
-            __TypeKind::UNION => {
+            TypeKind::Union => {
                 let mut union_def = UnionDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     union_def.description(desc);
@@ -196,7 +208,7 @@ impl Schema {
                 }
                 sdl.union(union_def);
             }
-            __TypeKind::ENUM => {
+            TypeKind::Enum => {
                 let mut enum_def = EnumDefinition::new(type_.name.unwrap_or_default());
                 if let Some(desc) = type_.description {
                     enum_def.description(desc);
@@ -220,14 +232,15 @@ impl Schema {
             }
             _ => (),
         }
+        Ok(())
     }
 
-    fn encode_field(field: FullTypeField) -> FieldDefinition {
-        let ty = Self::encode_type(field.type_);
+    fn encode_field(field: Field) -> Result<FieldDefinition, String> {
+        let ty = Self::encode_type(field.type_)?;
         let mut field_def = FieldDefinition::new(field.name, ty);
 
         for value in field.args {
-            let field_value = Self::encode_arg(value);
+            let field_value = Self::encode_arg(value)?;
             field_def.arg(field_value);
         }
 
@@ -237,150 +250,110 @@ impl Schema {
         if let Some(desc) = field.description {
             field_def.description(desc);
         }
-        field_def
+        Ok(field_def)
     }
 
-    fn encode_input_field(field: FullTypeInputField) -> InputField {
-        let ty = Self::encode_type(field.type_);
+    fn encode_input_field(field: InputValue) -> Result<InputField, String> {
+        let ty = Self::encode_type(field.type_)?;
         let mut field_def = InputField::new(field.name, ty);
 
         if let Some(default_value) = field.default_value {
             field_def.default_value(default_value);
         }
+        if field.is_deprecated {
+            field_def.directive(create_deprecated_directive(field.deprecation_reason));
+        }
         if let Some(desc) = field.description {
             field_def.description(desc);
         }
-        field_def
+        Ok(field_def)
     }
 
-    fn encode_arg(value: FullTypeFieldArg) -> InputValueDefinition {
-        let ty = Self::encode_type(value.type_);
+    fn encode_arg(value: InputValue) -> Result<InputValueDefinition, String> {
+        let ty = Self::encode_type(value.type_)?;
         let mut value_def = InputValueDefinition::new(value.name, ty);
 
         if let Some(default_value) = value.default_value {
             value_def.default_value(default_value);
         }
+        if value.is_deprecated {
+            value_def.directive(create_deprecated_directive(value.deprecation_reason));
+        }
         if let Some(desc) = value.description {
             value_def.description(desc);
         }
-        value_def
+        Ok(value_def)
     }
 
-    fn encode_type(ty: impl OfType) -> Type_ {
-        use graph_introspect_query::__TypeKind::*;
-        match ty.kind() {
-            SCALAR | OBJECT | INTERFACE | UNION | ENUM | INPUT_OBJECT => Type_::NamedType {
-                name: ty.name().unwrap().to_string(),
-            },
-            NON_NULL => {
-                let ty = Self::encode_type(ty.of_type().unwrap());
+    fn encode_type(ty: TypeRef) -> Result<Type_, String> {
+        let incomplete = || INCOMPLETE_TYPE_REF.to_string();
+        Ok(match ty.kind {
+            TypeKind::NonNull => {
+                let ty = Self::encode_type(*ty.of_type.ok_or_else(incomplete)?)?;
                 Type_::NonNull { ty: Box::new(ty) }
             }
-            LIST => {
-                let ty = Self::encode_type(ty.of_type().unwrap());
+            TypeKind::List => {
+                let ty = Self::encode_type(*ty.of_type.ok_or_else(incomplete)?)?;
                 Type_::List { ty: Box::new(ty) }
             }
-            Other(ty) => panic!("Unknown type: {}", ty),
-        }
+            _ => Type_::NamedType {
+                name: ty.name.ok_or_else(incomplete)?,
+            },
+        })
     }
 }
 
-impl TryFrom<IntrospectionResult> for Schema {
-    type Error = &'static str;
+impl TryFrom<QueryResponseData> for Schema {
+    type Error = String;
 
-    fn try_from(src: IntrospectionResult) -> Result<Self, Self::Error> {
+    fn try_from(src: QueryResponseData) -> Result<Self, Self::Error> {
         match src.schema {
-            Some(s) => Ok(Self {
-                types: s.types,
-                directives: s.directives,
-                mutation_type: s.mutation_type,
-                query_type: s.query_type,
-                subscription_type: s.subscription_type,
-            }),
-            None => Err("Schema not found in Introspection Result."),
-        }
-    }
-}
-
-/// This trait is used to be able to iterate over ofType fields in
-/// IntrospectionResponse.
-pub trait OfType {
-    type TypeRef: OfType;
-
-    fn kind(&self) -> &__TypeKind;
-    fn name(&self) -> Option<&str>;
-    fn of_type(self) -> Option<Self::TypeRef>;
-}
-
-macro_rules! impl_of_type {
-    ($target:ty, $assoc:ty) => {
-        impl OfType for $target {
-            type TypeRef = $assoc;
-
-            fn kind(&self) -> &__TypeKind {
-                &self.kind
-            }
-
-            fn name(&self) -> Option<&str> {
-                self.name.as_deref()
-            }
-
-            fn of_type(self) -> Option<Self::TypeRef> {
-                self.of_type
+            Some(s) => {
+                let incomplete = s
+                    .types
+                    .iter()
+                    .find_map(incomplete_type_ref)
+                    .or_else(|| s.directives.iter().find_map(incomplete_directive_ref));
+                if let Some(name) = incomplete {
+                    return Err(format!(
+                        "{} in {}. {}",
+                        INCOMPLETE_TYPE_REF, name, TRY_LARGER_DEPTH
+                    ));
+                }
+                Ok(Self {
+                    types: s.types,
+                    directives: s.directives,
+                    mutation_type: s.mutation_type,
+                    query_type: s.query_type,
+                    subscription_type: s.subscription_type,
+                })
             }
+            None => Err("Schema not found in Introspection Result.".to_string()),
         }
-    };
-}
-
-impl_of_type!(
-    graph_introspect_query::TypeRef,
-    graph_introspect_query::TypeRefOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfType,
-    graph_introspect_query::TypeRefOfTypeOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfTypeOfType,
-    graph_introspect_query::TypeRefOfTypeOfTypeOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfTypeOfTypeOfType,
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfType,
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfType,
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfType
-);
-
-impl_of_type!(
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfType,
-    graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfTypeOfType
-);
-
-// NOTE(lrlna): This is a **hack**. This makes sure that the last possible
-// generated ofType by graphql_client can return a None for of_type method.
-impl OfType for graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfTypeOfType {
-    type TypeRef = graph_introspect_query::TypeRefOfTypeOfTypeOfTypeOfTypeOfTypeOfTypeOfType;
-
-    fn kind(&self) -> &graph_introspect_query::__TypeKind {
-        &self.kind
     }
+}
 
-    fn name(&self) -> Option<&str> {
-        self.name.as_deref()
+/// The name of the type if any of its type references are incomplete
+fn incomplete_type_ref(type_: &FullType) -> Option<String> {
+    let fields = type_.fields.iter().flatten();
+    let mut type_refs = fields.clone().map(|field| &field.type_).chain(
+        fields
+            .flat_map(|field| field.args.iter())
+            .chain(type_.input_fields.iter().flatten())
+            .map(|value| &value.type_),
+    );
+    if type_refs.any(|type_ref| !type_ref.is_complete()) {
+        Some(type_.name.clone().unwrap_or_default())
+    } else {
+        None
     }
+}
 
-    fn of_type(self) -> Option<Self::TypeRef> {
+/// The name of the directive, prefixed with `@`, if any of its arguments' type references are incomplete
+fn incomplete_directive_ref(directive: &IntrospectionDirective) -> Option<String> {
+    if directive.args.iter().any(|arg| !arg.type_.is_complete()) {
+        Some(format!("@{}", directive.name))
+    } else {
         None
     }
 }
@@ -404,8 +377,6 @@ mod tests {
     use std::convert::TryFrom;
     use std::fs::File;
 
-    use crate::operations::graph::introspect::types::QueryResponseData;
-
     #[test]
     fn it_builds_simple_schema() {
         let file = File::open("src/operations/graph/introspect/fixtures/simple.json").unwrap();
@@ -414,7 +385,7 @@ mod tests {
         let data = res.data.unwrap();
         let schema = Schema::try_from(data).unwrap();
         assert_eq!(
-            schema.encode(),
+            schema.encode().unwrap(),
             indoc! { r#"
         "The `Upload` scalar type represents a file upload."
         scalar Upload
@@ -451,7 +422,7 @@ mod tests {
         let data = res.data.unwrap();
         let schema = Schema::try_from(data).unwrap();
         assert_eq!(
-            schema.encode(),
+            schema.encode().unwrap(),
             indoc! { r#"
         schema {
           query: Root
@@ -1423,7 +1394,7 @@ mod tests {
         let data = res.data.unwrap();
         let schema = Schema::try_from(data).unwrap();
         assert_eq!(
-            schema.encode(),
+            schema.encode().unwrap(),
             indoc! { r#"
              type Query {
                "Fetch a simple list of products with an offset"
@@ -1578,4 +1549,134 @@ mod tests {
          "#}
         )
     }
+
+    fn named(kind: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    #[test]
+    fn it_encodes_optional_introspection_fields() {
+        let data: QueryResponseData = serde_json::from_value(serde_json::json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "mutationType": null,
+                "subscriptionType": null,
+                "types": [
+                    {
+                        "kind": "OBJECT",
+                        "name": "Query",
+                        "fields": [{
+                            "name": "products",
+                            "args": [{
+                                "name": "legacy",
+                                "type": named("SCALAR", "Boolean"),
+                                "defaultValue": null,
+                                "isDeprecated": true,
+                                "deprecationReason": null
+                            }],
+                            "type": named("SCALAR", "DateTime"),
+                            "isDeprecated": false,
+                            "deprecationReason": null
+                        }],
+                        "interfaces": []
+                    },
+                    {
+                        "kind": "SCALAR",
+                        "name": "DateTime",
+                        "specifiedByURL": "https://tools.ietf.org/html/rfc3339"
+                    },
+                    {
+                        "kind": "INPUT_OBJECT",
+                        "name": "ProductInput",
+                        "inputFields": [{
+                            "name": "oldId",
+                            "type": named("SCALAR", "ID"),
+                            "defaultValue": null,
+                            "isDeprecated": true,
+                            "deprecationReason": "Use id"
+                        }]
+                    }
+                ],
+                "directives": [{
+                    "name": "key",
+                    "isRepeatable": true,
+                    "locations": ["OBJECT"],
+                    "args": [{
+                        "name": "fields",
+                        "type": named("SCALAR", "String"),
+                        "defaultValue": null
+                    }]
+                }]
+            }
+        }))
+        .unwrap();
+
+        let sdl = Schema::try_from(data).unwrap().encode().unwrap();
+
+        assert!(
+            sdl.contains("products(legacy: Boolean @deprecated): DateTime"),
+            "{sdl}"
+        );
+        assert!(
+            sdl.contains(
+                r#"scalar DateTime @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")"#
+            ),
+            "{sdl}"
+        );
+        assert!(
+            sdl.contains(r#"oldId: ID @deprecated(reason: "Use id")"#),
+            "{sdl}"
+        );
+        assert!(sdl.contains("repeatable on OBJECT"), "{sdl}");
+    }
+
+    #[test]
+    fn it_errors_on_type_refs_deeper_than_requested() {
+        let data: QueryResponseData = serde_json::from_value(serde_json::json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "types": [{
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [{
+                        "name": "matrix",
+                        "args": [],
+                        "type": { "kind": "LIST", "name": null, "ofType": null },
+                        "isDeprecated": false
+                    }]
+                }],
+                "directives": []
+            }
+        }))
+        .unwrap();
+
+        let error = Schema::try_from(data).unwrap_err();
+        assert!(error.contains("Query"), "{error}");
+    }
+
+    #[test]
+    fn it_errors_on_directive_args_deeper_than_requested() {
+        let deep_arg = serde_json::json!({
+            "name": "fields",
+            "type": { "kind": "NON_NULL", "name": null, "ofType": null },
+            "defaultValue": null
+        });
+        let data: QueryResponseData = serde_json::from_value(serde_json::json!({
+            "__schema": {
+                "queryType": { "name": "Query" },
+                "types": [],
+                "directives": [{
+                    "name": "key",
+                    "locations": ["OBJECT"],
+                    "args": [deep_arg.clone()]
+                }]
+            }
+        }))
+        .unwrap();
+        let error = Schema::try_from(data).unwrap_err();
+        assert!(error.contains("@key"), "{error}");
+
+        let incomplete = serde_json::from_value(deep_arg).unwrap();
+        assert!(Schema::encode_arg(incomplete).is_err());
+    }
 }
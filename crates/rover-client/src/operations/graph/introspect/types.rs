@@ -1,18 +1,41 @@
 use std::collections::HashMap;
 
-use crate::operations::graph::introspect::runner::graph_introspect_query;
+use serde::{Deserialize, Serialize};
 
-pub(crate) type QueryResponseData = graph_introspect_query::ResponseData;
-pub(crate) type QueryVariables = graph_introspect_query::Variables;
+/// The depth of `ofType` nesting requested by default, enough for types like `[[String!]!]!`
+pub const DEFAULT_TYPE_DEPTH: usize = 7;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GraphIntrospectInput {
     pub headers: HashMap<String, String>,
+    pub options: IntrospectionOptions,
 }
 
-impl From<GraphIntrospectInput> for QueryVariables {
-    fn from(_input: GraphIntrospectInput) -> Self {
-        Self {}
+/// Controls which parts of the introspection schema are requested. The defaults
+/// produce a query every spec-compliant server accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntrospectionOptions {
+    /// How many levels of `ofType` to request for each type reference
+    pub type_depth: usize,
+    /// Request descriptions for types, fields, arguments and directives
+    pub include_descriptions: bool,
+    /// Request deprecated arguments and input fields, along with their deprecation
+    pub include_deprecated_input_values: bool,
+    /// Request `isRepeatable` on directives
+    pub include_is_repeatable: bool,
+    /// Request `specifiedByURL` on scalars
+    pub include_specified_by_url: bool,
+}
+
+impl Default for IntrospectionOptions {
+    fn default() -> Self {
+        Self {
+            type_depth: DEFAULT_TYPE_DEPTH,
+            include_descriptions: true,
+            include_deprecated_input_values: false,
+            include_is_repeatable: false,
+            include_specified_by_url: false,
+        }
     }
 }
 
@@ -20,3 +43,119 @@ impl From<GraphIntrospectInput> for QueryVariables {
 pub struct GraphIntrospectResponse {
     pub schema_sdl: String,
 }
+
+/// The `data` of an introspection response. The query is built at runtime from
+/// [`IntrospectionOptions`], so these types are written by hand rather than generated,
+/// and every field a query may leave out is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryResponseData {
+    #[serde(rename = "__schema")]
+    pub schema: Option<IntrospectionSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntrospectionSchema {
+    pub query_type: RootType,
+    pub mutation_type: Option<RootType>,
+    pub subscription_type: Option<RootType>,
+    pub types: Vec<FullType>,
+    pub directives: Vec<IntrospectionDirective>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootType {
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntrospectionDirective {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_repeatable: Option<bool>,
+    pub locations: Vec<String>,
+    pub args: Vec<InputValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullType {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "specifiedByURL")]
+    pub specified_by_url: Option<String>,
+    pub fields: Option<Vec<Field>>,
+    pub input_fields: Option<Vec<InputValue>>,
+    pub interfaces: Option<Vec<TypeRef>>,
+    pub enum_values: Option<Vec<EnumValue>>,
+    pub possible_types: Option<Vec<TypeRef>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Field {
+    pub name: String,
+    pub description: Option<String>,
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub type_: TypeRef,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: TypeRef,
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    /// Whether every list and non-null wrapper has the type it wraps, which is
+    /// not the case when the reference is nested deeper than the query's type depth.
+    pub fn is_complete(&self) -> bool {
+        match (&self.kind, &self.of_type) {
+            (TypeKind::List | TypeKind::NonNull, Some(of_type)) => of_type.is_complete(),
+            (TypeKind::List | TypeKind::NonNull, None) => false,
+            (_, _) => self.name.is_some(),
+        }
+    }
+}
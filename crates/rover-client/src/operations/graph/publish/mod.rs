@@ -1,7 +1,7 @@
 mod runner;
 mod types;
 
-pub use runner::run;
+pub use runner::{run, run_async};
 pub use types::{
     ChangeSummary, FieldChanges, GraphPublishInput, GraphPublishResponse, TypeChanges,
 };
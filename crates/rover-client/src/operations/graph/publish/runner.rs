@@ -1,6 +1,7 @@
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::graph::publish::types::{ChangeSummary, FieldChanges, TypeChanges};
 use crate::operations::graph::publish::{GraphPublishInput, GraphPublishResponse};
 use crate::shared::GraphRef;
@@ -32,6 +33,16 @@ pub fn run(
     build_response(publish_response)
 }
 
+pub async fn run_async(
+    input: GraphPublishInput,
+    client: &AsyncStudioClient,
+) -> Result<GraphPublishResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let data = client.post::<GraphPublishMutation>(input.into()).await?;
+    let publish_response = get_publish_response_from_data(data, graph_ref)?;
+    build_response(publish_response)
+}
+
 fn get_publish_response_from_data(
     data: graph_publish_mutation::ResponseData,
     graph_ref: GraphRef,
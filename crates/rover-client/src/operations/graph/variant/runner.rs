@@ -1,5 +1,7 @@
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::graph::variant::VariantListInput;
+use crate::shared::GraphRef;
 use crate::RoverClientError;
 
 use graphql_client::*;
@@ -21,6 +23,22 @@ pub(crate) struct VariantListQuery;
 pub fn run(input: VariantListInput, client: &StudioClient) -> Result<(), RoverClientError> {
     let graph_ref = input.graph_ref.clone();
     let response_data = client.post::<VariantListQuery>(input.into())?;
+    check_variant_exists(response_data, graph_ref)
+}
+
+pub async fn run_async(
+    input: VariantListInput,
+    client: &AsyncStudioClient,
+) -> Result<(), RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<VariantListQuery>(input.into()).await?;
+    check_variant_exists(response_data, graph_ref)
+}
+
+fn check_variant_exists(
+    response_data: variant_list_query::ResponseData,
+    graph_ref: GraphRef,
+) -> Result<(), RoverClientError> {
     let graph = response_data.graph.ok_or(RoverClientError::GraphNotFound {
         graph_ref: graph_ref.clone(),
     })?;
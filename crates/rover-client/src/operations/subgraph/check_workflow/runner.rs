@@ -4,6 +4,7 @@ use apollo_federation_types::build::BuildError;
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::subgraph::check_workflow::types::QueryResponseData;
 use crate::shared::{
     CheckWorkflowResponse, Diagnostic, DownstreamCheckResponse, GraphRef, LintCheckResponse,
@@ -39,6 +40,9 @@ use self::subgraph_check_workflow_query::{
 /// Snake case of this name is the mod name. i.e. subgraph_check_workflow_query
 pub(crate) struct SubgraphCheckWorkflowQuery;
 
+/// How long to wait between polls of the check workflow
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 /// The main function to be used from this module.
 /// This function takes a proposed schema and validates it against a published
 /// schema.
@@ -47,31 +51,92 @@ pub fn run(
     subgraph: String,
     client: &StudioClient,
 ) -> Result<CheckWorkflowResponse, RoverClientError> {
-    let graph_ref = input.graph_ref.clone();
-    let mut url: Option<String> = None;
-    let now = Instant::now();
+    let mut poller = CheckWorkflowPoller::new(input, subgraph);
+    loop {
+        let result = client.post::<SubgraphCheckWorkflowQuery>(poller.variables());
+        if let Some(response) = poller.handle(result, |e| {
+            eprintln!("error while checking status of check: {e}\nthis error may be transient... retrying");
+        }) {
+            return response;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Like [`run`], but waits between polls without blocking the thread.
+pub async fn run_async(
+    input: CheckWorkflowInput,
+    subgraph: String,
+    client: &AsyncStudioClient,
+) -> Result<CheckWorkflowResponse, RoverClientError> {
+    let mut poller = CheckWorkflowPoller::new(input, subgraph);
     loop {
-        let result = client.post::<SubgraphCheckWorkflowQuery>(input.clone().into());
+        let result = client
+            .post::<SubgraphCheckWorkflowQuery>(poller.variables())
+            .await;
+        if let Some(response) = poller.handle(result, |e| {
+            tracing::warn!("error while checking status of check, retrying: {e}");
+        }) {
+            return response;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Polls a check workflow until it finishes or times out, for both [`run`] and [`run_async`]
+struct CheckWorkflowPoller {
+    input: CheckWorkflowInput,
+    subgraph: String,
+    started: Instant,
+    url: Option<String>,
+}
+
+impl CheckWorkflowPoller {
+    fn new(input: CheckWorkflowInput, subgraph: String) -> Self {
+        Self {
+            input,
+            subgraph,
+            started: Instant::now(),
+            url: None,
+        }
+    }
+
+    fn variables(&self) -> QueryVariables {
+        self.input.clone().into()
+    }
+
+    /// Returns the check's result once it has finished or timed out, or `None` to poll again.
+    /// Errors from a poll are passed to `on_retry`, since they may be transient.
+    fn handle(
+        &mut self,
+        result: Result<QueryResponseData, RoverClientError>,
+        on_retry: impl FnOnce(&RoverClientError),
+    ) -> Option<Result<CheckWorkflowResponse, RoverClientError>> {
+        let graph_ref = self.input.graph_ref.clone();
         match result {
             Ok(data) => {
-                let graph = data.clone().graph.ok_or(RoverClientError::GraphNotFound {
-                    graph_ref: graph_ref.clone(),
-                })?;
-                if let Some(check_workflow) = graph.check_workflow {
+                let Some(graph) = &data.graph else {
+                    return Some(Err(RoverClientError::GraphNotFound { graph_ref }));
+                };
+                if let Some(check_workflow) = &graph.check_workflow {
                     if !matches!(check_workflow.status, CheckWorkflowStatus::PENDING) {
-                        return get_check_response_from_data(data, graph_ref, subgraph);
+                        return Some(get_check_response_from_data(
+                            data,
+                            graph_ref,
+                            self.subgraph.clone(),
+                        ));
                     }
                 }
-                url = get_target_url_from_data(data);
-            }
-            Err(e) => {
-                eprintln!("error while checking status of check: {e}\nthis error may be transient... retrying");
+                self.url = get_target_url_from_data(data);
             }
+            Err(e) => on_retry(&e),
         }
-        if now.elapsed() > Duration::from_secs(input.checks_timeout_seconds) {
-            return Err(RoverClientError::ChecksTimeoutError { url });
+        if self.started.elapsed() > Duration::from_secs(self.input.checks_timeout_seconds) {
+            return Some(Err(RoverClientError::ChecksTimeoutError {
+                url: self.url.take(),
+            }));
         }
-        std::thread::sleep(Duration::from_secs(5));
+        None
     }
 }
 
@@ -408,3 +473,59 @@ fn get_downstream_response_from_result(
         None => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use httpmock::{Method::POST, MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn input() -> CheckWorkflowInput {
+        CheckWorkflowInput {
+            graph_ref: GraphRef {
+                name: "mygraph".to_string(),
+                variant: "current".to_string(),
+            },
+            workflow_id: "workflow".to_string(),
+            checks_timeout_seconds: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn run_async_returns_a_finished_check() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/graphql")
+                    .body_contains(r#""operationName":"SubgraphCheckWorkflowQuery""#);
+                then.status(200).json_body(json!({ "data": {
+                    "graph": { "checkWorkflow": { "status": "PASSED", "tasks": [] } }
+                }}));
+            })
+            .await;
+
+        let client = AsyncStudioClient::for_tests(&server.url("/graphql"));
+        let response = run_async(input(), "products".to_string(), &client)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.default_target_url,
+            "https://studio.apollographql.com/graph/mygraph/variant/current/checks/variant"
+        );
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn it_times_out_while_the_check_is_pending() {
+        let mut poller = CheckWorkflowPoller::new(input(), "products".to_string());
+        let mut retried = false;
+        let result = poller.handle(Err(RoverClientError::InvalidKey), |_| retried = true);
+        assert!(retried);
+        assert!(matches!(
+            result,
+            Some(Err(RoverClientError::ChecksTimeoutError { url: None }))
+        ));
+    }
+}
@@ -7,7 +7,7 @@ use crate::shared::{ChangeSeverity, GraphRef};
 
 use self::subgraph_check_workflow_query::CheckWorkflowTaskStatus;
 
-type QueryVariables = subgraph_check_workflow_query::Variables;
+pub(crate) type QueryVariables = subgraph_check_workflow_query::Variables;
 pub(crate) type QueryResponseData = subgraph_check_workflow_query::ResponseData;
 
 pub type ProposalsCheckTaskUnion = self::subgraph_check_workflow_query::SubgraphCheckWorkflowQueryGraphCheckWorkflowTasksOnProposalsCheckTask;
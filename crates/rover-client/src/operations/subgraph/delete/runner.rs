@@ -1,4 +1,5 @@
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::subgraph::delete::types::*;
 use crate::shared::GraphRef;
 use crate::RoverClientError;
@@ -33,6 +34,16 @@ pub fn run(
     Ok(build_response(data))
 }
 
+pub async fn run_async(
+    input: SubgraphDeleteInput,
+    client: &AsyncStudioClient,
+) -> Result<SubgraphDeleteResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<SubgraphDeleteMutation>(input.into()).await?;
+    let data = get_delete_data_from_response(response_data, graph_ref)?;
+    Ok(build_response(data))
+}
+
 fn get_delete_data_from_response(
     response_data: subgraph_delete_mutation::ResponseData,
     graph_ref: GraphRef,
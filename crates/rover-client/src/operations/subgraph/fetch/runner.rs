@@ -1,5 +1,6 @@
 use super::types::*;
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::shared::{FetchResponse, Sdl, SdlType};
 use crate::RoverClientError;
 
@@ -29,6 +30,15 @@ pub fn run(
     get_sdl_from_response_data(input, response_data)
 }
 
+pub async fn run_async(
+    input: SubgraphFetchInput,
+    client: &AsyncStudioClient,
+) -> Result<FetchResponse, RoverClientError> {
+    let variables = input.clone().into();
+    let response_data = client.post::<SubgraphFetchQuery>(variables).await?;
+    get_sdl_from_response_data(input, response_data)
+}
+
 fn get_sdl_from_response_data(
     input: SubgraphFetchInput,
     response_data: SubgraphFetchResponseData,
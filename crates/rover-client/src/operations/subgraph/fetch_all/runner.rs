@@ -1,6 +1,7 @@
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::RoverClientError;
 
 use super::types::*;
@@ -29,6 +30,15 @@ pub fn run(
     get_subgraphs_from_response_data(input, response_data)
 }
 
+pub async fn run_async(
+    input: SubgraphFetchAllInput,
+    client: &AsyncStudioClient,
+) -> Result<Vec<Subgraph>, RoverClientError> {
+    let variables = input.clone().into();
+    let response_data = client.post::<SubgraphFetchAllQuery>(variables).await?;
+    get_subgraphs_from_response_data(input, response_data)
+}
+
 fn get_subgraphs_from_response_data(
     input: SubgraphFetchAllInput,
     response_data: SubgraphFetchAllResponseData,
@@ -1,6 +1,7 @@
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::subgraph::list::types::*;
 use crate::shared::GraphRef;
 use crate::RoverClientError;
@@ -37,6 +38,21 @@ pub fn run(
     })
 }
 
+pub async fn run_async(
+    input: SubgraphListInput,
+    client: &AsyncStudioClient,
+) -> Result<SubgraphListResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<SubgraphListQuery>(input.into()).await?;
+    let root_url = response_data.frontend_url_root.clone();
+    let subgraphs = get_subgraphs_from_response_data(response_data, graph_ref.clone())?;
+    Ok(SubgraphListResponse {
+        subgraphs: format_subgraphs(&subgraphs),
+        root_url,
+        graph_ref,
+    })
+}
+
 fn get_subgraphs_from_response_data(
     response_data: QueryResponseData,
     graph_ref: GraphRef,
@@ -1,5 +1,6 @@
 use super::types::*;
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::graph::variant::VariantListInput;
 use crate::operations::{
     config::is_federated::{self, IsFederatedInput},
@@ -30,8 +31,6 @@ pub fn run(
     input: SubgraphPublishInput,
     client: &StudioClient,
 ) -> Result<SubgraphPublishResponse, RoverClientError> {
-    let graph_ref = input.graph_ref.clone();
-    let variables: MutationVariables = input.clone().into();
     // We don't want to implicitly convert non-federated graph to supergraphs.
     // Error here if no --convert flag is passed _and_ the current context
     // is non-federated. Add a suggestion to require a --convert flag.
@@ -39,38 +38,81 @@ pub fn run(
         // first, check if the variant exists _at all_
         // if it doesn't exist, there is no graph schema to "convert"
         // so don't require --convert in this case, just publish the subgraph
-        let variant_exists = variant::run(
-            VariantListInput {
-                graph_ref: graph_ref.clone(),
-            },
-            client,
-        )
-        .is_ok();
-
-        if variant_exists {
+        let variant_exists = variant::run(variant_input(&input), client).is_ok();
+        let is_federated = if variant_exists {
             // check if subgraphs have ever been published to this graph ref
-            let is_federated = is_federated::run(
-                IsFederatedInput {
-                    graph_ref: graph_ref.clone(),
-                },
-                client,
-            )?;
+            Some(is_federated::run(is_federated_input(&input), client)?)
+        } else {
+            None
+        };
+        check_federated(&input, is_federated)?;
+    }
+    let data = client.post::<SubgraphPublishMutation>(input.clone().into())?;
+    parse_response(data, input.graph_ref)
+}
 
-            if !is_federated {
-                return Err(RoverClientError::ExpectedFederatedGraph {
-                    graph_ref,
-                    can_operation_convert: true,
-                });
-            }
+/// Like [`run`], but with the async client
+pub async fn run_async(
+    input: SubgraphPublishInput,
+    client: &AsyncStudioClient,
+) -> Result<SubgraphPublishResponse, RoverClientError> {
+    // see `run` for why these checks are made
+    if !input.convert_to_federated_graph {
+        let variant_exists = variant::run_async(variant_input(&input), client)
+            .await
+            .is_ok();
+        let is_federated = if variant_exists {
+            Some(is_federated::run_async(is_federated_input(&input), client).await?)
         } else {
+            None
+        };
+        check_federated(&input, is_federated)?;
+    }
+    let data = client
+        .post::<SubgraphPublishMutation>(input.clone().into())
+        .await?;
+    parse_response(data, input.graph_ref)
+}
+
+fn variant_input(input: &SubgraphPublishInput) -> VariantListInput {
+    VariantListInput {
+        graph_ref: input.graph_ref.clone(),
+    }
+}
+
+fn is_federated_input(input: &SubgraphPublishInput) -> IsFederatedInput {
+    IsFederatedInput {
+        graph_ref: input.graph_ref.clone(),
+    }
+}
+
+/// Errors if the variant exists but isn't federated, where `is_federated` is `None`
+/// when the variant doesn't exist yet
+fn check_federated(
+    input: &SubgraphPublishInput,
+    is_federated: Option<bool>,
+) -> Result<(), RoverClientError> {
+    match is_federated {
+        Some(false) => Err(RoverClientError::ExpectedFederatedGraph {
+            graph_ref: input.graph_ref.clone(),
+            can_operation_convert: true,
+        }),
+        Some(true) => Ok(()),
+        None => {
             tracing::debug!(
                 "Publishing new subgraph {} to {}",
                 &input.subgraph,
                 &input.graph_ref
             );
+            Ok(())
         }
     }
-    let data = client.post::<SubgraphPublishMutation>(variables)?;
+}
+
+fn parse_response(
+    data: ResponseData,
+    graph_ref: GraphRef,
+) -> Result<SubgraphPublishResponse, RoverClientError> {
     let publish_response = get_publish_response_from_data(data, graph_ref)?;
     Ok(build_response(publish_response))
 }
@@ -118,7 +160,67 @@ fn build_response(publish_response: UpdateResponse) -> SubgraphPublishResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::GitContext;
+    use httpmock::{Method::POST, MockServer};
     use serde_json::json;
+
+    #[tokio::test]
+    async fn run_async_refuses_to_convert_a_non_federated_graph() {
+        let server = MockServer::start_async().await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/graphql")
+                    .body_contains(r#""operationName":"VariantListQuery""#);
+                then.status(200).json_body(json!({ "data": {
+                    "frontendUrlRoot": "https://studio.apollographql.com",
+                    "graph": { "variants": [{ "name": "current" }] }
+                }}));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/graphql")
+                    .body_contains(r#""operationName":"IsFederatedGraph""#);
+                then.status(200).json_body(json!({ "data": {
+                    "graph": { "variant": { "subgraphs": null } }
+                }}));
+            })
+            .await;
+        let publish = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/graphql")
+                    .body_contains(r#""operationName":"SubgraphPublishMutation""#);
+                then.status(500);
+            })
+            .await;
+
+        let client = AsyncStudioClient::for_tests(&server.url("/graphql"));
+        let input = SubgraphPublishInput {
+            graph_ref: GraphRef {
+                name: "mygraph".to_string(),
+                variant: "current".to_string(),
+            },
+            subgraph: "products".to_string(),
+            url: None,
+            schema: "type Query { hello: String }".to_string(),
+            git_context: GitContext {
+                branch: None,
+                author: None,
+                commit: None,
+                remote_url: None,
+            },
+            convert_to_federated_graph: false,
+        };
+        let result = run_async(input, &client).await;
+        assert!(matches!(
+            result,
+            Err(RoverClientError::ExpectedFederatedGraph { .. })
+        ));
+        publish.assert_hits_async(0).await;
+    }
     #[test]
     fn build_response_works_with_composition_errors() {
         let json_response = json!({
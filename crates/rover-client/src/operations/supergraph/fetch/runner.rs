@@ -2,6 +2,7 @@ use apollo_federation_types::build::BuildError;
 use graphql_client::*;
 
 use crate::blocking::StudioClient;
+use crate::nonblocking::StudioClient as AsyncStudioClient;
 use crate::operations::supergraph::fetch::SupergraphFetchInput;
 use crate::shared::{FetchResponse, GraphRef, Sdl, SdlType};
 use crate::RoverClientError;
@@ -35,6 +36,15 @@ pub fn run(
     get_supergraph_sdl_from_response_data(response_data, graph_ref)
 }
 
+pub async fn run_async(
+    input: SupergraphFetchInput,
+    client: &AsyncStudioClient,
+) -> Result<FetchResponse, RoverClientError> {
+    let graph_ref = input.graph_ref.clone();
+    let response_data = client.post::<SupergraphFetchQuery>(input.into()).await?;
+    get_supergraph_sdl_from_response_data(response_data, graph_ref)
+}
+
 fn get_supergraph_sdl_from_response_data(
     response_data: supergraph_fetch_query::ResponseData,
     graph_ref: GraphRef,
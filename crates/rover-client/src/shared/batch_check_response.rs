@@ -0,0 +1,217 @@
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::shared::{CheckRequestSuccessResult, CheckWorkflowResponse};
+
+/// BatchCheckWorkflowResponse is the return type of `rover subgraph batch-check`,
+/// aggregating the check workflows of several subgraphs into a single report.
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct BatchCheckWorkflowResponse {
+    /// The result of each subgraph check, sorted by subgraph name
+    pub results: Vec<SubgraphCheckResult>,
+    /// Subgraphs whose proposed schema matched the published schema and were not checked
+    pub skipped_subgraphs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub struct SubgraphCheckResult {
+    pub subgraph: String,
+    pub outcome: SubgraphCheckOutcome,
+}
+
+#[derive(Debug, Serialize, Clone, Eq, PartialEq)]
+pub enum SubgraphCheckOutcome {
+    /// The check was started in the background and was not waited on
+    Started(CheckRequestSuccessResult),
+    Passed(Box<CheckWorkflowResponse>),
+    Failed(Box<CheckWorkflowResponse>),
+    /// The check could not be run or did not finish, e.g. a composition error or a timeout
+    Errored {
+        message: String,
+    },
+}
+
+impl AsRef<str> for SubgraphCheckOutcome {
+    fn as_ref(&self) -> &str {
+        match self {
+            SubgraphCheckOutcome::Started(_) => "STARTED",
+            SubgraphCheckOutcome::Passed(_) => "PASSED",
+            SubgraphCheckOutcome::Failed(_) => "FAILED",
+            SubgraphCheckOutcome::Errored { .. } => "ERRORED",
+        }
+    }
+}
+
+impl BatchCheckWorkflowResponse {
+    /// Returns true if any of the subgraph checks failed or could not be completed
+    pub fn has_failures(&self) -> bool {
+        self.results.iter().any(|result| {
+            matches!(
+                result.outcome,
+                SubgraphCheckOutcome::Failed(_) | SubgraphCheckOutcome::Errored { .. }
+            )
+        })
+    }
+
+    pub fn failed_subgraphs(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|result| {
+                matches!(
+                    result.outcome,
+                    SubgraphCheckOutcome::Failed(_) | SubgraphCheckOutcome::Errored { .. }
+                )
+            })
+            .map(|result| result.subgraph.as_str())
+            .collect()
+    }
+
+    pub fn get_output(&self) -> String {
+        let mut msg = String::new();
+
+        for result in &self.results {
+            msg.push_str(&format!(
+                "\n{} [{}]:\n",
+                Style::Heading.paint(format!("Subgraph '{}'", result.subgraph)),
+                match &result.outcome {
+                    SubgraphCheckOutcome::Passed(_) => Style::Success.paint(&result.outcome),
+                    SubgraphCheckOutcome::Started(_) => Style::Pending.paint(&result.outcome),
+                    SubgraphCheckOutcome::Failed(_) | SubgraphCheckOutcome::Errored { .. } => {
+                        Style::Failure.paint(&result.outcome)
+                    }
+                }
+            ));
+            match &result.outcome {
+                SubgraphCheckOutcome::Started(started) => msg.push_str(&format!(
+                    "Check successfully started with workflow ID: {}\nView full details at {}\n",
+                    started.workflow_id, started.target_url
+                )),
+                SubgraphCheckOutcome::Passed(check_response)
+                | SubgraphCheckOutcome::Failed(check_response) => {
+                    msg.push_str(&check_response.get_output());
+                    msg.push('\n');
+                }
+                SubgraphCheckOutcome::Errored { message } => {
+                    msg.push_str(message);
+                    msg.push('\n');
+                }
+            }
+        }
+
+        if !self.skipped_subgraphs.is_empty() {
+            msg.push_str(&format!(
+                "\nSkipped {} unchanged subgraph(s): {}\n",
+                self.skipped_subgraphs.len(),
+                self.skipped_subgraphs.join(", ")
+            ));
+        }
+
+        let failed = self.failed_subgraphs();
+        msg.push_str(&format!(
+            "\nChecked {} subgraph(s): {} succeeded, {} failed.",
+            self.results.len(),
+            self.results.len() - failed.len(),
+            failed.len()
+        ));
+
+        msg
+    }
+
+    pub fn get_json(&self) -> Value {
+        let mut subgraphs: Value = json!({});
+
+        for result in &self.results {
+            subgraphs[&result.subgraph] = match &result.outcome {
+                SubgraphCheckOutcome::Started(started) => json!({
+                    "status": result.outcome.as_ref(),
+                    "check": started.get_json(),
+                }),
+                SubgraphCheckOutcome::Passed(check_response)
+                | SubgraphCheckOutcome::Failed(check_response) => json!({
+                    "status": result.outcome.as_ref(),
+                    "check": check_response.get_json(),
+                }),
+                SubgraphCheckOutcome::Errored { message } => json!({
+                    "status": result.outcome.as_ref(),
+                    "error": message,
+                }),
+            };
+        }
+
+        json!({
+            "subgraphs": subgraphs,
+            "skipped_subgraphs": self.skipped_subgraphs,
+            "failed_subgraphs": self.failed_subgraphs(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_response() -> CheckWorkflowResponse {
+        CheckWorkflowResponse {
+            default_target_url: "https://studio.apollographql.com".to_string(),
+            maybe_core_schema_modified: Some(false),
+            maybe_operations_response: None,
+            maybe_lint_response: None,
+            maybe_proposals_response: None,
+            maybe_downstream_response: None,
+        }
+    }
+
+    #[test]
+    fn it_reports_failures_across_subgraphs() {
+        let response = BatchCheckWorkflowResponse {
+            results: vec![
+                SubgraphCheckResult {
+                    subgraph: "inventory".to_string(),
+                    outcome: SubgraphCheckOutcome::Passed(Box::new(check_response())),
+                },
+                SubgraphCheckResult {
+                    subgraph: "products".to_string(),
+                    outcome: SubgraphCheckOutcome::Errored {
+                        message: "The check workflow took too long to run.".to_string(),
+                    },
+                },
+            ],
+            skipped_subgraphs: vec!["users".to_string()],
+        };
+
+        assert!(response.has_failures());
+        assert_eq!(response.failed_subgraphs(), vec!["products"]);
+        assert_eq!(
+            response.get_json(),
+            json!({
+                "subgraphs": {
+                    "inventory": {
+                        "status": "PASSED",
+                        "check": { "core_schema_modified": false, "tasks": {} },
+                    },
+                    "products": {
+                        "status": "ERRORED",
+                        "error": "The check workflow took too long to run.",
+                    },
+                },
+                "skipped_subgraphs": ["users"],
+                "failed_subgraphs": ["products"],
+            })
+        );
+    }
+
+    #[test]
+    fn it_does_not_fail_when_all_checks_pass() {
+        let response = BatchCheckWorkflowResponse {
+            results: vec![SubgraphCheckResult {
+                subgraph: "inventory".to_string(),
+                outcome: SubgraphCheckOutcome::Passed(Box::new(check_response())),
+            }],
+            skipped_subgraphs: Vec::new(),
+        };
+
+        assert!(!response.has_failures());
+        assert!(response.failed_subgraphs().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 mod async_check_response;
+mod batch_check_response;
 mod check_response;
 mod fetch_response;
 mod git_context;
@@ -6,6 +7,9 @@ mod graph_ref;
 mod lint_response;
 
 pub use async_check_response::CheckRequestSuccessResult;
+pub use batch_check_response::{
+    BatchCheckWorkflowResponse, SubgraphCheckOutcome, SubgraphCheckResult,
+};
 pub use check_response::{
     ChangeSeverity, CheckConfig, CheckTaskStatus, CheckWorkflowResponse, DownstreamCheckResponse,
     LintCheckResponse, OperationCheckResponse, ProposalsCheckResponse, ProposalsCheckSeverityLevel,
@@ -5,7 +5,9 @@
 mod error;
 mod report;
 mod session;
+mod spool;
 
 pub use error::SputnikError;
 pub use report::Report;
-pub use session::{Command, Session};
+pub use session::{Command, Session, FIELDS};
+pub use spool::{read_spooled_reports, spool_report, MAX_SPOOLED_REPORTS};
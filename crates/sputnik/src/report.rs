@@ -47,6 +47,12 @@ pub trait Report {
 
     /// returns the Client to use when sending telemetry data
     fn client(&self) -> Result<Client, SputnikError>;
+
+    /// returns the location a copy of the most recent reports is kept,
+    /// so users can inspect them. by default, reports aren't kept.
+    fn spool_path(&self) -> Result<Option<Utf8PathBuf>, SputnikError> {
+        Ok(None)
+    }
 }
 
 fn get_or_write_machine_id(path: &Utf8PathBuf) -> Result<Uuid, SputnikError> {
@@ -13,9 +13,11 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::env;
 use std::fmt::Debug;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::time::Duration;
 
-use crate::{Report, SputnikError};
+use crate::{spool::spool_report, Report, SputnikError};
 
 // set timeout to 100 ms to prevent blocking for too long on reporting; 30ms p99
 const REPORT_TIMEOUT: Duration = Duration::from_millis(100);
@@ -56,6 +58,42 @@ pub struct Session {
     client: Client,
 }
 
+/// FIELDS describes every field in a serialized Session, for users
+/// auditing what is reported. Keep it in sync with the structs below.
+pub const FIELDS: &[(&str, &str)] = &[
+    (
+        "command.name",
+        "The subcommand that was run, such as `subgraph check`",
+    ),
+    (
+        "command.arguments",
+        "The arguments the command was run with and their values, except for arguments whose values are always left out, such as graph refs and file paths",
+    ),
+    (
+        "machine_id",
+        "A random ID generated the first time the CLI runs on a machine and stored in its config directory",
+    ),
+    ("session_id", "A random ID generated for each invocation"),
+    (
+        "cwd_hash",
+        "A SHA-256 hash of the directory the command was run in",
+    ),
+    (
+        "remote_url_hash",
+        "A SHA-256 hash of the Git remote URL of the repository the command was run in, if any",
+    ),
+    (
+        "platform.os",
+        "The operating system the command was run on, or `wsl`",
+    ),
+    ("platform.arch", "The CPU architecture of the machine"),
+    (
+        "platform.continuous_integration",
+        "The CI vendor the command was run in, if any",
+    ),
+    ("cli_version", "The version of the CLI"),
+];
+
 /// Platform represents the platform the CLI is being run from
 #[derive(Debug, Serialize)]
 pub struct Platform {
@@ -86,6 +124,7 @@ struct ReportingInfo {
     is_telemetry_enabled: bool,
     endpoint: Url,
     user_agent: String,
+    spool_path: Option<Utf8PathBuf>,
 }
 
 impl Session {
@@ -99,6 +138,7 @@ impl Session {
             is_telemetry_enabled: app.is_telemetry_enabled()?,
             endpoint: app.endpoint()?,
             user_agent: app.user_agent(),
+            spool_path: app.spool_path()?,
         };
         let current_dir = Utf8PathBuf::try_from(env::current_dir()?)?;
         let session_id = Uuid::new_v4();
@@ -138,8 +178,32 @@ impl Session {
         })
     }
 
-    /// sends anonymous usage data to the endpoint defined in ReportingInfo.
+    /// sends anonymous usage data to the endpoint defined in ReportingInfo,
+    /// after keeping a copy of it in the spool, if there is one.
+    /// `file://` endpoints have each report appended to the file as a line of JSON.
     pub fn report(&self) -> Result<(), SputnikError> {
+        let body = serde_json::to_string(&self)?;
+        if let Some(spool_path) = &self.reporting_info.spool_path {
+            // the spool is only for inspecting reports, so it shouldn't stop them being sent
+            if let Err(spool_error) = spool_report(spool_path, &serde_json::to_value(self)?) {
+                tracing::debug!(?spool_error);
+            }
+        }
+
+        if !self.reporting_info.is_telemetry_enabled {
+            return Ok(());
+        }
+
+        if self.reporting_info.endpoint.scheme() == "file" {
+            let path = self.reporting_info.endpoint.to_file_path().map_err(|_| {
+                anyhow::anyhow!("{} is not a file path", self.reporting_info.endpoint)
+            })?;
+            tracing::debug!("Appending to {}", path.display());
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", body)?;
+            return Ok(());
+        }
+
         // Disable telemetry by default when not a production release.
         // TODO: consider whether we want to disable non-production telemetry or at least document
         // the reasoning for not using it
@@ -147,18 +211,15 @@ impl Session {
             return Ok(());
         }
 
-        if self.reporting_info.is_telemetry_enabled {
-            let body = serde_json::to_string(&self)?;
-            tracing::debug!("POSTing to {}", &self.reporting_info.endpoint);
-            tracing::debug!("{}", body);
-            self.client
-                .post(self.reporting_info.endpoint.clone())
-                .body(body)
-                .header("User-Agent", &self.reporting_info.user_agent)
-                .header("Content-Type", "application/json")
-                .timeout(REPORT_TIMEOUT)
-                .send()?;
-        }
+        tracing::debug!("POSTing to {}", &self.reporting_info.endpoint);
+        tracing::debug!("{}", body);
+        self.client
+            .post(self.reporting_info.endpoint.clone())
+            .body(body)
+            .header("User-Agent", &self.reporting_info.user_agent)
+            .header("Content-Type", "application/json")
+            .timeout(REPORT_TIMEOUT)
+            .send()?;
 
         Ok(())
     }
@@ -220,6 +281,7 @@ mod tests {
                 is_telemetry_enabled: true,
                 endpoint: Url::parse(format!("http://0.0.0.0/{}", report_path()).as_str()).unwrap(),
                 user_agent: user_agent().into(),
+                spool_path: None,
             },
             client: Client::new(),
         }
@@ -297,4 +359,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[rstest]
+    fn it_spools_and_writes_reports_to_file_sinks(mut session: Session) {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let spool_path = Utf8PathBuf::try_from(fixture.path().join("spool.jsonl")).unwrap();
+        let sink_path = fixture.path().join("sink.jsonl");
+        session.reporting_info.spool_path = Some(spool_path.clone());
+        session.reporting_info.endpoint = Url::from_file_path(&sink_path).unwrap();
+
+        session.report().unwrap();
+        session.report().unwrap();
+
+        let spooled = crate::read_spooled_reports(&spool_path, 10).unwrap();
+        assert_eq!(spooled.len(), 2);
+        assert_eq!(spooled[0]["command"]["name"], "test-command");
+        let sunk = std::fs::read_to_string(sink_path).unwrap();
+        assert_eq!(sunk.lines().count(), 2);
+
+        session.reporting_info.is_telemetry_enabled = false;
+        session.report().unwrap();
+        assert_eq!(
+            crate::read_spooled_reports(&spool_path, 10).unwrap().len(),
+            3
+        );
+    }
+
+    #[rstest]
+    fn it_documents_every_field(session: Session) {
+        let report = serde_json::to_value(&session).unwrap();
+        for (key, value) in report.as_object().unwrap() {
+            let documented = match value {
+                serde_json::Value::Object(fields) => fields.keys().all(|field| {
+                    FIELDS
+                        .iter()
+                        .any(|(name, _)| *name == format!("{key}.{field}"))
+                }),
+                _ => FIELDS.iter().any(|(name, _)| name == key),
+            };
+            assert!(documented, "{} is not documented in FIELDS", key);
+        }
+    }
 }
@@ -0,0 +1,79 @@
+use camino::Utf8Path;
+use rover_std::Fs;
+
+use std::fs;
+
+use crate::SputnikError;
+
+/// the number of reports kept in the spool, oldest first
+pub const MAX_SPOOLED_REPORTS: usize = 50;
+
+/// appends a report to the spool at `path`, dropping the oldest reports
+/// so that no more than MAX_SPOOLED_REPORTS are kept.
+pub fn spool_report(path: &Utf8Path, report: &serde_json::Value) -> Result<(), SputnikError> {
+    let mut lines: Vec<String> = match Fs::read_file(path) {
+        Ok(contents) => contents.lines().map(str::to_string).collect(),
+        Err(_) => Vec::new(),
+    };
+    lines.push(serde_json::to_string(report)?);
+    let first_kept = lines.len().saturating_sub(MAX_SPOOLED_REPORTS);
+    let mut contents = lines[first_kept..].join("\n");
+    contents.push('\n');
+
+    // write the new spool next to the old one and swap them, so that
+    // `rover telemetry show` never reads a half-written spool
+    let tmp_path = path.with_extension("jsonl.tmp");
+    Fs::write_file(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// reads the `last` most recent reports from the spool at `path`, oldest first.
+/// reports that can't be parsed are skipped.
+pub fn read_spooled_reports(
+    path: &Utf8Path,
+    last: usize,
+) -> Result<Vec<serde_json::Value>, SputnikError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = Fs::read_file(path)?;
+    let reports: Vec<serde_json::Value> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let first_kept = reports.len().saturating_sub(last);
+    Ok(reports[first_kept..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use camino::Utf8PathBuf;
+    use serde_json::json;
+
+    #[test]
+    fn it_keeps_the_most_recent_reports() {
+        let fixture = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(fixture.path().join("telemetry.jsonl")).unwrap();
+        assert!(read_spooled_reports(&path, 5).unwrap().is_empty());
+
+        for session in 0..MAX_SPOOLED_REPORTS + 3 {
+            spool_report(&path, &json!({ "session": session })).unwrap();
+        }
+
+        let reports = read_spooled_reports(&path, usize::MAX).unwrap();
+        assert_eq!(reports.len(), MAX_SPOOLED_REPORTS);
+        assert_eq!(reports[0], json!({ "session": 3 }));
+
+        let reports = read_spooled_reports(&path, 2).unwrap();
+        assert_eq!(
+            reports,
+            vec![
+                json!({ "session": MAX_SPOOLED_REPORTS + 1 }),
+                json!({ "session": MAX_SPOOLED_REPORTS + 2 })
+            ]
+        );
+    }
+}
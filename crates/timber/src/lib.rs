@@ -5,10 +5,23 @@
 
 use clap::ValueEnum;
 use std::io;
-use tracing_subscriber::fmt;
+use std::sync::OnceLock;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 
 pub use tracing_core::Level;
 
+mod logs;
+mod otlp;
+
+pub use logs::{recent_logs, LogFilter, LogFormat, LogOptions};
+pub use otlp::{TraceDestination, TraceExporter, TraceLayer};
+
+// the exporter set up by `init`, if traces are being exported
+static TRACE_EXPORTER: OnceLock<TraceExporter> = OnceLock::new();
+
 #[derive(Clone, ValueEnum)]
 pub(crate) enum RoverLogLevel {
     Trace,
@@ -31,14 +44,35 @@ impl std::fmt::Display for RoverLogLevel {
 /// Initializes a global tracing subscriber that formats
 /// all logs produced by an application that calls init,
 /// and all logs produced by libraries consumed by that application.
-pub fn init(level: Option<Level>) {
-    // by default, no logs are printed.
-    if let Some(level) = level {
-        let format = fmt::format().without_time().pretty();
-        fmt()
-            .with_max_level(level)
-            .event_format(format)
-            .with_writer(io::stderr)
-            .init();
+///
+/// The most recent logs at the info level and above are always kept in memory,
+/// for `recent_logs`.
+/// If `traces` is set, spans at the info level and above are also recorded,
+/// to be exported there by `export_traces`.
+pub fn init(logs: LogOptions, traces: Option<TraceDestination>) -> io::Result<()> {
+    let recent = logs::recent_layer();
+    let logs = logs.layer()?;
+    let traces = traces.map(|traces| {
+        TRACE_EXPORTER
+            .get_or_init(|| TraceExporter::new(traces, "rover"))
+            .layer()
+            .with_filter(LevelFilter::INFO)
+    });
+    // by default, no logs are printed, but the most recent are kept for crash reports.
+    tracing_subscriber::registry()
+        .with(logs)
+        .with(recent)
+        .with(traces)
+        .init();
+    Ok(())
+}
+
+/// Exports the spans that have closed since `init` was called with a trace destination,
+/// or since the last export, sending them to a collector with `client`.
+/// Does nothing if traces aren't being exported.
+pub fn export_traces(client: &reqwest::blocking::Client) -> io::Result<()> {
+    match TRACE_EXPORTER.get() {
+        Some(exporter) => exporter.export(client),
+        None => Ok(()),
     }
 }
@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use clap::ValueEnum;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::{DefaultFields, Format, Full};
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{fmt as format, EnvFilter, Layer, Registry};
+
+use tracing_core::Subscriber;
+
+// the level logs are written at when a log file is passed without a filter
+const DEFAULT_LOG_FILE_FILTER: &str = "info";
+
+// how many of the most recent log lines are kept in memory, for crash reports
+const MAX_RECENT_LOGS: usize = 200;
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// The most recent log lines, oldest first, whether or not logs are being written anywhere.
+/// Only lines at the info level and above are kept.
+pub fn recent_logs() -> Vec<String> {
+    match RECENT_LOGS.lock() {
+        Ok(recent) => recent.iter().cloned().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+struct RecentLogsWriter;
+
+impl io::Write for RecentLogsWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut recent) = RECENT_LOGS.lock() {
+            for line in String::from_utf8_lossy(buf).lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if recent.len() == MAX_RECENT_LOGS {
+                    recent.pop_front();
+                }
+                recent.push_back(line.to_string());
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// How each log line is formatted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable, multi-line logs
+    #[default]
+    Pretty,
+
+    /// Human-readable logs, one line each
+    Compact,
+
+    /// JSON objects, one line each, with timestamps and the spans they were logged in
+    Json,
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self.to_possible_value() {
+            Some(possible_value) => possible_value.get_name().to_string(),
+            None => "unknown".to_string(),
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+/// Which logs are written, as `EnvFilter` directives: a level such as `debug`, or a
+/// comma-separated list of `target=level` directives such as `rover=debug,reqwest=warn`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter(String);
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EnvFilter::builder()
+            .parse(s)
+            .map(|_| LogFilter(s.to_string()))
+            .map_err(|e| format!("invalid log filter '{}': {}", s, e))
+    }
+}
+
+impl fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl LogFilter {
+    fn env_filter(&self) -> EnvFilter {
+        // directives are checked when the filter is parsed
+        EnvFilter::builder().parse_lossy(&self.0)
+    }
+}
+
+/// Which logs are written, how they're formatted, and where they're written to.
+#[derive(Debug, Default, Clone)]
+pub struct LogOptions {
+    /// Which logs are written. No logs are written if this isn't set, unless `file` is,
+    /// in which case logs at the info level and above are written.
+    pub filter: Option<LogFilter>,
+
+    /// How each log line is formatted
+    pub format: LogFormat,
+
+    /// The file logs are appended to, instead of being written to stderr
+    pub file: Option<PathBuf>,
+}
+
+impl LogOptions {
+    /// The layer that writes logs as these options say, or `None` if no logs are written.
+    pub(crate) fn layer(&self) -> io::Result<Option<Box<dyn Layer<Registry> + Send + Sync>>> {
+        let filter = match (&self.filter, &self.file) {
+            (Some(filter), _) => filter.env_filter(),
+            (None, Some(_)) => EnvFilter::new(DEFAULT_LOG_FILE_FILTER),
+            (None, None) => return Ok(None),
+        };
+        let layer = match &self.file {
+            Some(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                // files are read later, so record when each line was logged, without colors
+                formatted(
+                    format::layer()
+                        .with_ansi(false)
+                        .with_writer(Mutex::new(file)),
+                    self.format,
+                )
+            }
+            None => formatted(
+                format::layer().without_time().with_writer(io::stderr),
+                self.format,
+            ),
+        };
+        Ok(Some(layer.with_filter(filter).boxed()))
+    }
+}
+
+/// The layer that keeps the most recent log lines in memory, for `recent_logs`.
+/// Debug and trace lines aren't kept, since they can include request headers and bodies.
+pub(crate) fn recent_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    format::layer()
+        .compact()
+        .with_ansi(false)
+        .with_writer(|| RecentLogsWriter)
+        .with_filter(LevelFilter::INFO)
+}
+
+fn formatted<T, W>(
+    layer: format::Layer<Registry, DefaultFields, Format<Full, T>, W>,
+    log_format: LogFormat,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    T: FormatTime + Send + Sync + 'static,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match log_format {
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        // JSON logs always have timestamps, since they're for aggregating
+        LogFormat::Json => layer
+            .with_timer(SystemTime)
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_log_filters() {
+        assert!("debug".parse::<LogFilter>().is_ok());
+        assert!("rover=debug,reqwest=warn".parse::<LogFilter>().is_ok());
+        assert!("rover=loud".parse::<LogFilter>().is_err());
+    }
+
+    #[test]
+    fn it_writes_json_logs_to_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rover.log");
+        let logs = LogOptions {
+            filter: Some("rover=debug,warn".parse().unwrap()),
+            format: LogFormat::Json,
+            file: Some(path.clone()),
+        };
+        let subscriber = tracing_subscriber::registry().with(logs.layer().unwrap());
+        tracing::subscriber::with_default(subscriber, || {
+            let _span =
+                tracing::info_span!(target: "rover::cli", "command", command = "subgraph publish")
+                    .entered();
+            tracing::debug!(target: "rover::command", attempt = 1, "publishing");
+            tracing::debug!(target: "reqwest::connect", "connecting");
+        });
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert!(line["timestamp"].is_string());
+        assert_eq!(line["level"], "DEBUG");
+        assert_eq!(line["target"], "rover::command");
+        assert_eq!(line["fields"]["message"], "publishing");
+        assert_eq!(line["fields"]["attempt"], 1);
+        assert_eq!(line["span"]["name"], "command");
+        assert_eq!(line["span"]["name"], line["spans"][0]["name"]);
+    }
+
+    #[test]
+    fn it_keeps_the_most_recent_logs() {
+        let subscriber = tracing_subscriber::registry().with(recent_layer());
+        tracing::subscriber::with_default(subscriber, || {
+            for attempt in 0..MAX_RECENT_LOGS + 5 {
+                tracing::info!(attempt, "retrying");
+            }
+            tracing::debug!("not kept");
+        });
+
+        let recent = recent_logs();
+        assert_eq!(recent.len(), MAX_RECENT_LOGS);
+        assert!(!recent.iter().any(|line| line.contains("not kept")));
+        assert!(recent[0].contains("attempt=5"));
+        assert!(recent
+            .last()
+            .unwrap()
+            .contains(&format!("attempt={}", MAX_RECENT_LOGS + 4)));
+    }
+}
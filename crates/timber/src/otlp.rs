@@ -0,0 +1,517 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+use tracing_core::field::{Field, Visit};
+use tracing_core::span::{Attributes, Id, Record};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+use uuid::Uuid;
+
+// exporting happens as the CLI exits, so don't hold it up for long if the collector is down
+const COLLECTOR_TIMEOUT: Duration = Duration::from_secs(5);
+
+// the OTLP span status codes
+const STATUS_CODE_UNSET: u8 = 0;
+const STATUS_CODE_ERROR: u8 = 2;
+
+// the OTLP span kind for spans that aren't a server or a client
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// Where finished spans are exported to, in the OTLP/JSON format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceDestination {
+    /// An OTLP/HTTP collector endpoint, such as `http://localhost:4318/v1/traces`.
+    /// Spans are POSTed to it as they are.
+    Collector(String),
+
+    /// A file that each export is appended to, as a line of JSON.
+    File(PathBuf),
+}
+
+impl FromStr for TraceDestination {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Err("a trace destination can't be empty".to_string())
+        } else if s.starts_with("http://") || s.starts_with("https://") {
+            Ok(TraceDestination::Collector(s.to_string()))
+        } else {
+            Ok(TraceDestination::File(PathBuf::from(
+                s.strip_prefix("file://").unwrap_or(s),
+            )))
+        }
+    }
+}
+
+impl fmt::Display for TraceDestination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceDestination::Collector(url) => write!(f, "{}", url),
+            TraceDestination::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Records spans as they close, so they can be exported in the OTLP/JSON format.
+/// Every span recorded by one exporter is part of the same trace.
+#[derive(Debug, Clone)]
+pub struct TraceExporter {
+    destination: TraceDestination,
+    service_name: String,
+    trace_id: String,
+    finished: Arc<Mutex<Vec<FinishedSpan>>>,
+}
+
+impl TraceExporter {
+    /// Creates an exporter for a new trace, which is reported as coming from `service_name`.
+    pub fn new(destination: TraceDestination, service_name: &str) -> TraceExporter {
+        TraceExporter {
+            destination,
+            service_name: service_name.to_string(),
+            trace_id: Uuid::new_v4().simple().to_string(),
+            finished: Arc::default(),
+        }
+    }
+
+    /// The layer that records spans for this exporter.
+    pub fn layer(&self) -> TraceLayer {
+        TraceLayer {
+            finished: self.finished.clone(),
+        }
+    }
+
+    /// Exports every span that has closed since the last export, sending them to a collector
+    /// with `client` so they go through the same proxy and TLS settings as other requests.
+    /// Spans that are still open aren't exported.
+    pub fn export(&self, client: &Client) -> io::Result<()> {
+        let spans: Vec<FinishedSpan> = match self.finished.lock() {
+            Ok(mut finished) => finished.drain(..).collect(),
+            Err(_) => return Err(io::Error::other("the trace exporter was poisoned")),
+        };
+        if spans.is_empty() {
+            return Ok(());
+        }
+        let body = self.to_otlp_json(&spans).to_string();
+        match &self.destination {
+            TraceDestination::Collector(url) => {
+                client
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .body(body)
+                    .timeout(COLLECTOR_TIMEOUT)
+                    .send()
+                    .and_then(|response| response.error_for_status())
+                    .map_err(io::Error::other)?;
+            }
+            TraceDestination::File(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", body)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn to_otlp_json(&self, spans: &[FinishedSpan]) -> Value {
+        let spans: Vec<Value> = spans
+            .iter()
+            .map(|span| {
+                let (code, message) = match &span.data.error {
+                    Some(message) => (STATUS_CODE_ERROR, message.as_str()),
+                    None => (STATUS_CODE_UNSET, ""),
+                };
+                json!({
+                    "traceId": self.trace_id,
+                    "spanId": format!("{:016x}", span.data.span_id),
+                    "parentSpanId": span
+                        .data
+                        .parent_span_id
+                        .map(|id| format!("{:016x}", id))
+                        .unwrap_or_default(),
+                    "name": span.data.name,
+                    "kind": SPAN_KIND_INTERNAL,
+                    "startTimeUnixNano": unix_nanos(span.data.start),
+                    "endTimeUnixNano": unix_nanos(span.end),
+                    "attributes": otlp_attributes(&span.data.attributes),
+                    "events": span.data.events.iter().map(|event| json!({
+                        "timeUnixNano": unix_nanos(event.time),
+                        "name": event.name,
+                        "attributes": otlp_attributes(&event.attributes),
+                    })).collect::<Vec<_>>(),
+                    "status": { "code": code, "message": message },
+                })
+            })
+            .collect();
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": otlp_attributes(&[(
+                        "service.name".to_string(),
+                        AttributeValue::String(self.service_name.clone()),
+                    )]),
+                },
+                "scopeSpans": [{
+                    "scope": { "name": env!("CARGO_PKG_NAME") },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+}
+
+/// The `tracing` layer that records spans for a TraceExporter.
+///
+/// Fields are exported as span attributes, apart from two with special meanings:
+/// `otel.name` replaces the span's name, and `error` marks the span as failed.
+#[derive(Debug)]
+pub struct TraceLayer {
+    finished: Arc<Mutex<Vec<FinishedSpan>>>,
+}
+
+impl<S> Layer<S> for TraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let parent_span_id = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|data| data.span_id)
+        });
+        let mut data = SpanData {
+            span_id: new_span_id(),
+            parent_span_id,
+            name: attrs.metadata().name().to_string(),
+            start: SystemTime::now(),
+            attributes: Vec::new(),
+            events: Vec::new(),
+            error: None,
+        };
+        attrs.record(&mut data);
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            values.record(data);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.event_span(event) else {
+            return;
+        };
+        let mut visitor = EventVisitor {
+            name: event.metadata().name().to_string(),
+            attributes: Vec::new(),
+        };
+        event.record(&mut visitor);
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            data.events.push(EventData {
+                time: SystemTime::now(),
+                name: visitor.name,
+                attributes: visitor.attributes,
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        if let Ok(mut finished) = self.finished.lock() {
+            finished.push(FinishedSpan {
+                data,
+                end: SystemTime::now(),
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FinishedSpan {
+    data: SpanData,
+    end: SystemTime,
+}
+
+#[derive(Debug)]
+struct SpanData {
+    span_id: u64,
+    parent_span_id: Option<u64>,
+    name: String,
+    start: SystemTime,
+    attributes: Vec<(String, AttributeValue)>,
+    events: Vec<EventData>,
+    error: Option<String>,
+}
+
+impl SpanData {
+    fn record_value(&mut self, field: &Field, value: AttributeValue) {
+        match field.name() {
+            "otel.name" => self.name = value.to_string(),
+            "error" => self.error = Some(value.to_string()),
+            name => set_attribute(&mut self.attributes, name, value),
+        }
+    }
+}
+
+impl Visit for SpanData {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, AttributeValue::String(value.to_string()))
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, AttributeValue::Int(value))
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, AttributeValue::from(value))
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, AttributeValue::Bool(value))
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, AttributeValue::Double(value))
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field, AttributeValue::String(format!("{:?}", value)))
+    }
+}
+
+#[derive(Debug)]
+struct EventData {
+    time: SystemTime,
+    name: String,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+struct EventVisitor {
+    name: String,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+impl EventVisitor {
+    fn record_value(&mut self, field: &Field, value: AttributeValue) {
+        match field.name() {
+            // events are named by their message, rather than the file and line they're from
+            "message" => self.name = value.to_string(),
+            name => set_attribute(&mut self.attributes, name, value),
+        }
+    }
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_value(field, AttributeValue::String(value.to_string()))
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_value(field, AttributeValue::Int(value))
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record_value(field, AttributeValue::from(value))
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record_value(field, AttributeValue::Bool(value))
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_value(field, AttributeValue::Double(value))
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_value(field, AttributeValue::String(format!("{:?}", value)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttributeValue {
+    String(String),
+    Int(i64),
+    Bool(bool),
+    Double(f64),
+}
+
+impl From<u64> for AttributeValue {
+    fn from(value: u64) -> Self {
+        // OTLP only has signed integers
+        match i64::try_from(value) {
+            Ok(value) => AttributeValue::Int(value),
+            Err(_) => AttributeValue::String(value.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::String(value) => write!(f, "{}", value),
+            AttributeValue::Int(value) => write!(f, "{}", value),
+            AttributeValue::Bool(value) => write!(f, "{}", value),
+            AttributeValue::Double(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn set_attribute(attributes: &mut Vec<(String, AttributeValue)>, key: &str, value: AttributeValue) {
+    match attributes.iter_mut().find(|(existing, _)| existing == key) {
+        Some((_, existing)) => *existing = value,
+        None => attributes.push((key.to_string(), value)),
+    }
+}
+
+fn otlp_attributes(attributes: &[(String, AttributeValue)]) -> Vec<Value> {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            // OTLP/JSON encodes 64 bit integers as strings
+            let value = match value {
+                AttributeValue::String(value) => json!({ "stringValue": value }),
+                AttributeValue::Int(value) => json!({ "intValue": value.to_string() }),
+                AttributeValue::Bool(value) => json!({ "boolValue": value }),
+                AttributeValue::Double(value) => json!({ "doubleValue": value }),
+            };
+            json!({ "key": key, "value": value })
+        })
+        .collect()
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn new_span_id() -> u64 {
+    // span IDs of all zeroes are invalid
+    loop {
+        let (span_id, _) = Uuid::new_v4().as_u64_pair();
+        if span_id != 0 {
+            return span_id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    fn record_spans(exporter: &TraceExporter) {
+        let subscriber = tracing_subscriber::registry().with(exporter.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let command = tracing::info_span!(
+                "command",
+                otel.name = "rover subgraph publish",
+                error = tracing::field::Empty
+            )
+            .entered();
+            {
+                let _request = tracing::info_span!(
+                    "graphql_request",
+                    graphql.operation.name = "SubgraphPublishMutation",
+                    http.response.status_code = 200u16,
+                )
+                .entered();
+                tracing::info!(attempt = 2, "retrying request");
+            }
+            command.record("error", "the subgraph failed to compose");
+        });
+    }
+
+    #[test]
+    fn it_parses_trace_destinations() {
+        assert_eq!(
+            "http://localhost:4318/v1/traces".parse(),
+            Ok(TraceDestination::Collector(
+                "http://localhost:4318/v1/traces".to_string()
+            ))
+        );
+        assert_eq!(
+            "file:///tmp/traces.json".parse(),
+            Ok(TraceDestination::File(PathBuf::from("/tmp/traces.json")))
+        );
+        assert_eq!(
+            "traces.json".parse(),
+            Ok(TraceDestination::File(PathBuf::from("traces.json")))
+        );
+    }
+
+    #[test]
+    fn it_exports_spans_to_a_collector() {
+        let server = MockServer::start();
+        let collector = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/traces")
+                .header("Content-Type", "application/json")
+                .body_contains("\"name\":\"rover subgraph publish\"")
+                .body_contains("\"name\":\"retrying request\"");
+            then.status(200);
+        });
+
+        let destination = TraceDestination::Collector(server.url("/v1/traces"));
+        let exporter = TraceExporter::new(destination, "rover");
+        record_spans(&exporter);
+        exporter.export(&Client::new()).unwrap();
+        collector.assert();
+
+        // nothing is left to export
+        exporter.export(&Client::new()).unwrap();
+        collector.assert_hits(1);
+    }
+
+    #[test]
+    fn it_exports_spans_to_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traces.json");
+        let exporter = TraceExporter::new(TraceDestination::File(path.clone()), "rover");
+        record_spans(&exporter);
+        exporter.export(&Client::new()).unwrap();
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let export: Value = serde_json::from_str(contents.trim()).unwrap();
+        let spans = export["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let (request, command) = (&spans[0], &spans[1]);
+        assert_eq!(request["name"], "graphql_request");
+        assert_eq!(request["parentSpanId"], command["spanId"]);
+        assert_eq!(request["traceId"], command["traceId"]);
+        assert_eq!(
+            request["attributes"][1],
+            json!({ "key": "http.response.status_code", "value": { "intValue": "200" } })
+        );
+        assert_eq!(request["events"][0]["name"], "retrying request");
+        assert_eq!(command["name"], "rover subgraph publish");
+        assert_eq!(command["parentSpanId"], "");
+        assert_eq!(
+            command["status"],
+            json!({ "code": STATUS_CODE_ERROR, "message": "the subgraph failed to compose" })
+        );
+    }
+}
@@ -2,12 +2,16 @@ use std::env;
 use std::io::{self, IsTerminal, Write};
 
 use camino::Utf8PathBuf;
+use reqwest::blocking::{RequestBuilder, Response};
 use url::Url;
 
 use rover_std::Fs;
 
 use crate::InstallerError;
 
+/// Sends a request on the installer's behalf, so callers can retry failed downloads
+pub type SendRequest<'a> = &'a dyn Fn(RequestBuilder) -> reqwest::Result<Response>;
+
 pub struct Installer {
     pub binary_name: String,
     pub force_install: bool,
@@ -47,9 +51,12 @@ impl Installer {
         plugin_name: &str,
         plugin_tarball_url: &str,
         client: &reqwest::blocking::Client,
+        no_redirect_client: &reqwest::blocking::Client,
         is_latest: bool,
+        send: SendRequest,
     ) -> Result<Option<Utf8PathBuf>, InstallerError> {
-        let version = self.get_plugin_version(plugin_tarball_url, is_latest)?;
+        let version =
+            self.get_plugin_version(plugin_tarball_url, is_latest, no_redirect_client, send)?;
 
         let bin_dir_path = self.get_bin_dir_path()?;
         if !bin_dir_path.exists() {
@@ -65,7 +72,7 @@ impl Installer {
         }
 
         let plugin_bin_path =
-            self.extract_plugin_tarball(plugin_name, plugin_tarball_url, client)?;
+            self.extract_plugin_tarball(plugin_name, plugin_tarball_url, client, send)?;
         self.write_plugin_bin_to_fs(plugin_name, &plugin_bin_path, &version)?;
 
         eprintln!(
@@ -76,19 +83,17 @@ impl Installer {
         Ok(Some(plugin_bin_destination))
     }
 
+    /// Reads the plugin version from its tarball URL, or for the latest version, from the
+    /// `X-Version` header of the redirect to it. `no_redirect_client` must not follow redirects.
     pub fn get_plugin_version(
         &self,
         plugin_tarball_url: &str,
         is_latest: bool,
+        no_redirect_client: &reqwest::blocking::Client,
+        send: SendRequest,
     ) -> Result<String, InstallerError> {
         if is_latest {
-            let no_redirect_client = reqwest::blocking::Client::builder()
-                .redirect(reqwest::redirect::Policy::none())
-                .build()?;
-            let response = no_redirect_client
-                .head(plugin_tarball_url)
-                .send()?
-                .error_for_status()?;
+            let response = send(no_redirect_client.head(plugin_tarball_url))?.error_for_status()?;
 
             if let Some(version) = response.headers().get("x-version") {
                 Ok(version
@@ -124,17 +129,23 @@ impl Installer {
 
     /// Gets the location the executable will be installed to
     pub fn get_bin_dir_path(&self) -> Result<Utf8PathBuf, InstallerError> {
-        // TODO: loop this up better with rover's environment variable management
-        let bin_dir = if let Ok(node_modules_bin) = std::env::var("APOLLO_NODE_MODULES_BIN_DIR") {
-            node_modules_bin.into()
-        } else {
-            let bin_dir = self.get_base_dir_path()?.join("bin");
+        let bin_dir = self.bin_dir_path()?;
+        if std::env::var("APOLLO_NODE_MODULES_BIN_DIR").is_err() {
             std::fs::create_dir_all(&bin_dir)?;
-            bin_dir
-        };
+        }
         Ok(bin_dir)
     }
 
+    /// The directory binaries are installed to, without creating it
+    pub fn bin_dir_path(&self) -> Result<Utf8PathBuf, InstallerError> {
+        // TODO: loop this up better with rover's environment variable management
+        if let Ok(node_modules_bin) = std::env::var("APOLLO_NODE_MODULES_BIN_DIR") {
+            Ok(node_modules_bin.into())
+        } else {
+            Ok(self.get_base_dir_path()?.join("bin"))
+        }
+    }
+
     pub(crate) fn get_base_dir_path(&self) -> Result<Utf8PathBuf, InstallerError> {
         let base_dir = if let Some(base_dir) = &self.override_install_path {
             Ok(base_dir.to_owned())
@@ -240,18 +251,17 @@ impl Installer {
         plugin_name: &str,
         plugin_tarball_url: &str,
         client: &reqwest::blocking::Client,
+        send: SendRequest,
     ) -> Result<Utf8PathBuf, InstallerError> {
         let download_dir = tempfile::Builder::new().prefix(plugin_name).tempdir()?;
         let download_dir_path = Utf8PathBuf::try_from(download_dir.into_path())?;
         let tarball_path = download_dir_path.join(format!("{}.tar.gz", plugin_name));
         let mut f = std::fs::File::create(&tarball_path)?;
-        let response_bytes = client
+        let request = client
             .get(plugin_tarball_url)
             .header(reqwest::header::USER_AGENT, "rover-client")
-            .header(reqwest::header::ACCEPT, "application/octet-stream")
-            .send()?
-            .error_for_status()?
-            .bytes()?;
+            .header(reqwest::header::ACCEPT, "application/octet-stream");
+        let response_bytes = send(request)?.error_for_status()?.bytes()?;
         f.write_all(&response_bytes[..])?;
         f.sync_all()?;
         let f = std::fs::File::open(&tarball_path)?;
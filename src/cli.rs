@@ -1,27 +1,32 @@
+use anyhow::anyhow;
 use camino::Utf8PathBuf;
-use clap::{Parser, ValueEnum};
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
 use lazycell::{AtomicLazyCell, LazyCell};
 use reqwest::blocking::Client;
 use serde::Serialize;
 
 use crate::command::{self, RoverOutput};
-use crate::options::OutputOpts;
+use crate::options::{NetworkEnv, NetworkOpts, OutputOpts, ProfileOpt, RetryEnv, RetryOpts};
 use crate::utils::{
     client::{ClientBuilder, ClientTimeout, StudioClientConfig},
+    crash_bundle::{self, BundleSnapshot},
     env::{RoverEnv, RoverEnvKey},
+    project_config::{ProjectConfig, SettingOverrides},
     stringify::option_from_display,
     version,
 };
-use crate::RoverResult;
+use crate::{RoverError, RoverErrorSuggestion, RoverResult, PKG_VERSION};
 
-use config::Config;
+use config::{Config, ProfileSettings};
 use houston as config;
+use rover_client::blocking::FixtureMode;
 use rover_client::shared::GitContext;
-use sputnik::Session;
-use timber::Level;
+use rover_std::Style;
+use sputnik::{Report, Session};
+use timber::{LogFilter, LogFormat, LogOptions, TraceDestination};
 
 use std::fmt::Display;
-use std::{io, process, thread};
+use std::{env, io, process, thread};
 
 #[derive(Debug, Serialize, Parser)]
 #[command(
@@ -57,10 +62,39 @@ pub struct Rover {
     #[clap(subcommand)]
     command: Command,
 
-    /// Specify Rover's log level
-    #[arg(long = "log", short = 'l', global = true)]
+    /// Specify Rover's log level, or which logs to write as a comma-separated list of
+    /// `target=level` directives, such as `rover=debug,reqwest=warn`
+    #[arg(
+        long = "log",
+        short = 'l',
+        global = true,
+        value_name = "LEVEL_OR_DIRECTIVES"
+    )]
     #[serde(serialize_with = "option_from_display")]
-    log_level: Option<Level>,
+    log_level: Option<LogFilter>,
+
+    /// Specify how each log line is formatted
+    #[arg(long = "log-format", global = true, default_value_t)]
+    #[serde(skip_serializing)]
+    log_format: LogFormat,
+
+    /// Append logs to this file instead of writing them to stderr.
+    /// Logs at the info level and above are written unless `--log` is passed
+    #[arg(long = "log-file", value_name = "FILE", global = true)]
+    #[serde(skip_serializing)]
+    log_file: Option<Utf8PathBuf>,
+
+    /// Export a trace of this command in the OTLP/JSON format, to a file or to
+    /// an OTLP/HTTP collector URL such as `http://localhost:4318/v1/traces`
+    #[arg(
+        long = "trace-export",
+        value_name = "FILE_OR_URL",
+        global = true,
+        env = "APOLLO_TRACE_EXPORT",
+        hide_env_values = true
+    )]
+    #[serde(skip_serializing)]
+    trace_export: Option<TraceDestination>,
 
     #[clap(flatten)]
     output_opts: OutputOpts,
@@ -85,12 +119,32 @@ pub struct Rover {
     accept_invalid_hostnames: bool,
 
     /// Configure the timeout length (in seconds) when performing HTTP(S) requests.
+    ///
+    /// Defaults to the profile's `client_timeout` setting, or 30 seconds.
+    #[arg(long = "client-timeout", global = true)]
+    client_timeout: Option<ClientTimeout>,
+
+    #[clap(flatten)]
+    retry_opts: RetryOpts,
+
+    #[clap(flatten)]
+    network_opts: NetworkOpts,
+
+    /// Record each response from GraphOS Studio to a fixture file in this directory, to `--replay` later
     #[arg(
-        long = "client-timeout",
+        long = "record",
+        value_name = "DIR",
         global = true,
-        default_value_t = ClientTimeout::default()
+        conflicts_with = "replay"
     )]
-    client_timeout: ClientTimeout,
+    #[serde(skip_serializing)]
+    record: Option<Utf8PathBuf>,
+
+    /// Serve responses to GraphOS Studio requests from the fixture files `--record`ed in this directory,
+    /// without using the network. Requests that weren't recorded fail.
+    #[arg(long = "replay", value_name = "DIR", global = true)]
+    #[serde(skip_serializing)]
+    replay: Option<Utf8PathBuf>,
 
     /// Skip checking for newer versions of rover.
     #[arg(long = "skip-update-check", global = true)]
@@ -107,18 +161,86 @@ pub struct Rover {
     #[arg(skip)]
     #[serde(skip_serializing)]
     client: AtomicLazyCell<Client>,
+
+    /// The `--profile` of the command being run, if it takes one
+    #[arg(skip)]
+    #[serde(skip_serializing)]
+    profile_name: Option<String>,
+
+    /// The closest `.rover.toml`, whose `profile` is the default for `--profile`
+    #[arg(skip)]
+    #[serde(skip_serializing)]
+    project_config: Option<ProjectConfig>,
 }
 
 impl Rover {
     pub fn run_from_args() -> RoverResult<()> {
-        Rover::parse().run()
+        // arguments are parsed before `.rover.toml` is read, so `--help` and `--version`
+        // work whatever it contains
+        let mut matches = Rover::command().get_matches();
+        let project_config = Rover::discover_project_config();
+        if let Some(profile_name) = project_config
+            .as_ref()
+            .and_then(|project| project.profile.as_deref())
+        {
+            matches = ProfileOpt::with_default(Rover::command(), profile_name).get_matches();
+        }
+        let mut rover = Rover::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+        rover.profile_name = ProfileOpt::name_from_arg_matches(&matches);
+        rover.project_config = project_config;
+        rover.run()
+    }
+
+    /// The closest `.rover.toml`. One that can't be read is ignored with a warning, so it
+    /// doesn't stop commands that don't need it.
+    fn discover_project_config() -> Option<ProjectConfig> {
+        let discovered = env::current_dir()
+            .map_err(anyhow::Error::from)
+            .and_then(|dir| Ok(Utf8PathBuf::try_from(dir)?))
+            .and_then(|dir| ProjectConfig::discover_from(&dir));
+        match discovered {
+            Ok(project_config) => project_config,
+            Err(e) => {
+                eprintln!("{} {}", Style::WarningPrefix.paint("WARN:"), e);
+                None
+            }
+        }
     }
 
     pub fn run(&self) -> RoverResult<()> {
-        timber::init(self.log_level);
+        let logs = LogOptions {
+            filter: self.log_level.clone(),
+            format: self.log_format,
+            file: self.log_file.clone().map(Utf8PathBuf::into_std_path_buf),
+        };
+        if let Err(e) = timber::init(logs, self.trace_export.clone()) {
+            let mut err = RoverError::new(anyhow!("Could not open the log file: {}", e));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Check that the directory passed to `--log-file` exists and can be written to."
+                    .to_string(),
+            ));
+            self.output_opts.handle_output(err)?;
+            process::exit(1);
+        }
         tracing::trace!(command_structure = ?self);
         self.output_opts.set_no_color();
 
+        let bundle_snapshot = BundleSnapshot::new(self);
+        robot_panic::bundle::set_context_provider(move || bundle_snapshot.context());
+
+        let command_name = self
+            .serialize_command()
+            .map(|command| command.name)
+            .unwrap_or_default();
+        let command_span = tracing::info_span!(
+            "command",
+            otel.name = %format!("rover {}", command_name),
+            rover.version = PKG_VERSION,
+            rover.error_code = tracing::field::Empty,
+            error = tracing::field::Empty,
+        )
+        .entered();
+
         // attempt to create a new `Session` to capture anonymous usage data
         let rover_output = match Session::new(self) {
             // if successful, report the usage data in the background
@@ -151,6 +273,28 @@ impl Rover {
             Err(_) => self.execute_command(),
         };
 
+        if let Err(error) = &rover_output {
+            if let Some(code) = error.code() {
+                command_span.record("rover.error_code", code.to_string());
+            }
+            command_span.record("error", error.message());
+        }
+        // the command's span has to close before it can be exported
+        drop(command_span);
+        if self.trace_export.is_some() {
+            // sent with the same proxy and certificate settings as every other request
+            let exported = self
+                .get_reqwest_client()
+                .and_then(|client| Ok(timber::export_traces(&client)?));
+            if let Err(e) = exported {
+                eprintln!(
+                    "{} Could not export the trace of this command: {}",
+                    Style::WarningPrefix.paint("WARN:"),
+                    e
+                );
+            }
+        }
+
         match rover_output {
             Ok(output) => {
                 self.output_opts.handle_output(output)?;
@@ -180,8 +324,12 @@ impl Rover {
         }
 
         match &self.command {
-            Command::Config(command) => command.run(self.get_client_config()?),
+            Command::Api(command) => command.run(self.get_client_config()?),
+            Command::Config(command) => {
+                command.run(self.get_client_config()?, self.get_setting_overrides()?)
+            }
             Command::Contract(command) => command.run(self.get_client_config()?),
+            Command::Debug(command) => command.run(BundleSnapshot::new(self)),
             Command::Dev(command) => {
                 command.run(self.get_install_override_path()?, self.get_client_config()?)
             }
@@ -190,6 +338,7 @@ impl Rover {
                 command.run(self.get_install_override_path()?, self.get_client_config()?)
             }
             Command::Docs(command) => command.run(),
+            Command::Doctor(command) => command.run(self.get_client_config()),
             Command::Graph(command) => command.run(
                 self.get_client_config()?,
                 self.get_git_context()?,
@@ -198,6 +347,7 @@ impl Rover {
             ),
             Command::Template(command) => command.run(self.get_client_config()?),
             Command::Readme(command) => command.run(self.get_client_config()?),
+            Command::Schema(command) => command.run(),
             Command::Subgraph(command) => command.run(
                 self.get_client_config()?,
                 self.get_git_context()?,
@@ -214,6 +364,11 @@ impl Rover {
             Command::Explain(command) => command.run(),
             Command::PersistedQueries(command) => command.run(self.get_client_config()?),
             Command::License(command) => command.run(self.get_client_config()?),
+            Command::Telemetry(command) => command.run(
+                self.get_rover_config()?,
+                self.is_telemetry_enabled()?,
+                self.endpoint()?,
+            ),
         }
     }
 
@@ -222,11 +377,22 @@ impl Rover {
             .get_env_var(RoverEnvKey::ConfigHome)?
             .map(|p| Utf8PathBuf::from(&p));
         let override_api_key = self.get_env_var(RoverEnvKey::Key)?;
-        Ok(Config::new(override_home.as_ref(), override_api_key)?)
+        let credential_passphrase = self.get_env_var(RoverEnvKey::CredentialsPassphrase)?;
+        for secret in override_api_key.iter().chain(&credential_passphrase) {
+            crash_bundle::mask_secret(secret);
+        }
+        Ok(Config::new(override_home.as_ref(), override_api_key)?
+            .with_credential_passphrase(credential_passphrase))
     }
 
     pub(crate) fn get_client_config(&self) -> RoverResult<StudioClientConfig> {
-        let override_endpoint = self.get_env_var(RoverEnvKey::RegistryUrl)?;
+        let override_endpoint = match self.get_env_var(RoverEnvKey::RegistryUrl)? {
+            Some(registry_url) => Some(registry_url),
+            None => self
+                .project_config
+                .as_ref()
+                .and_then(|project| project.registry_url.clone()),
+        };
         let is_sudo = if let Some(fire_flower) = self.get_env_var(RoverEnvKey::FireFlower)? {
             let fire_flower = fire_flower.to_lowercase();
             fire_flower == "true" || fire_flower == "1"
@@ -234,13 +400,79 @@ impl Rover {
             false
         };
         let config = self.get_rover_config()?;
+        let retry_env = RetryEnv {
+            retry_timeout: self.get_env_var(RoverEnvKey::RetryTimeoutSeconds)?,
+            max_retries: self.get_env_var(RoverEnvKey::MaxRetries)?,
+            retry_statuses: self.get_env_var(RoverEnvKey::RetryStatuses)?,
+        };
+        let retry_policy = self.retry_opts.policy(
+            retry_env,
+            &config.settings()?.retry,
+            self.get_client_timeout()?
+                .unwrap_or_default()
+                .get_duration(),
+        )?;
         Ok(StudioClientConfig::new(
             override_endpoint,
             config,
             is_sudo,
-            self.get_reqwest_client_builder(),
-            Some(self.client_timeout.get_duration()),
-        ))
+            self.get_reqwest_client_builder()?,
+            retry_policy,
+        )
+        .with_fixtures(self.get_fixture_mode())
+        .with_project_config(self.project_config.clone()))
+    }
+
+    /// The `--client-timeout` flag, or else the `client_timeout` in `.rover.toml`, or else
+    /// the profile's `client_timeout` setting
+    fn get_client_timeout(&self) -> RoverResult<Option<ClientTimeout>> {
+        let project_timeout = self
+            .project_config
+            .as_ref()
+            .and_then(|project| project.client_timeout);
+        Ok(match (self.client_timeout, project_timeout) {
+            (Some(client_timeout), _) => Some(client_timeout),
+            (None, Some(seconds)) => Some(ClientTimeout::new(seconds)),
+            (None, None) => self
+                .get_profile_settings()?
+                .client_timeout
+                .map(ClientTimeout::new),
+        })
+    }
+
+    /// The settings of the profile the command runs with, which only fill in
+    /// options that aren't set any other way
+    fn get_profile_settings(&self) -> RoverResult<ProfileSettings> {
+        match &self.profile_name {
+            Some(profile_name) => {
+                let profile_opt = ProfileOpt {
+                    profile_name: profile_name.clone(),
+                };
+                Ok(profile_opt.settings(&self.get_rover_config()?)?)
+            }
+            None => Ok(ProfileSettings::default()),
+        }
+    }
+
+    /// The settings passed as flags and environment variables, for reporting where each
+    /// setting comes from
+    pub(crate) fn get_setting_overrides(&self) -> RoverResult<SettingOverrides> {
+        Ok(SettingOverrides {
+            client_timeout: self
+                .client_timeout
+                .map(|client_timeout| client_timeout.get_duration().as_secs()),
+            proxy_flag: self.network_opts.proxy().map(str::to_string),
+            proxy_env: self.get_env_var(RoverEnvKey::Proxy)?,
+            registry_url_env: self.get_env_var(RoverEnvKey::RegistryUrl)?,
+        })
+    }
+
+    fn get_fixture_mode(&self) -> Option<FixtureMode> {
+        match (&self.record, &self.replay) {
+            (Some(dir), _) => Some(FixtureMode::Record(dir.clone())),
+            (None, Some(dir)) => Some(FixtureMode::Replay(dir.clone())),
+            (None, None) => None,
+        }
     }
 
     pub(crate) fn get_install_override_path(&self) -> RoverResult<Option<Utf8PathBuf>> {
@@ -263,31 +495,56 @@ impl Rover {
         Ok(git_context)
     }
 
-    pub(crate) fn get_reqwest_client(&self) -> reqwest::Result<Client> {
+    pub(crate) fn get_reqwest_client(&self) -> RoverResult<Client> {
         if let Some(client) = self.client.borrow() {
             Ok(client.clone())
         } else {
             self.client
-                .fill(self.get_reqwest_client_builder().build()?)
+                .fill(self.get_reqwest_client_builder()?.build()?)
                 .ok();
             self.get_reqwest_client()
         }
     }
 
-    pub(crate) fn get_reqwest_client_builder(&self) -> ClientBuilder {
+    pub(crate) fn get_reqwest_client_builder(&self) -> RoverResult<ClientBuilder> {
         // return a copy of the underlying client builder if it's already been populated
         if let Some(client_builder) = self.client_builder.borrow() {
-            *client_builder
+            Ok(client_builder.clone())
         } else {
             // if a request hasn't been made yet, this cell won't be populated yet
-            self.client_builder
-                .fill(
-                    ClientBuilder::new()
-                        .accept_invalid_certs(self.accept_invalid_certs)
-                        .accept_invalid_hostnames(self.accept_invalid_hostnames)
-                        .with_timeout(self.client_timeout.get_duration()),
-                )
-                .ok();
+            let network_env = NetworkEnv {
+                proxy: self.get_env_var(RoverEnvKey::Proxy)?,
+                no_proxy: self.get_env_var(RoverEnvKey::NoProxy)?,
+                system_no_proxy: std::env::var("NO_PROXY")
+                    .or_else(|_| std::env::var("no_proxy"))
+                    .ok(),
+                ca_certs: self.get_env_var(RoverEnvKey::CaCerts)?,
+                client_cert: self.get_env_var(RoverEnvKey::ClientCert)?,
+                client_key: self.get_env_var(RoverEnvKey::ClientKey)?,
+            };
+            let mut client_builder = ClientBuilder::new()
+                .accept_invalid_certs(self.accept_invalid_certs)
+                .accept_invalid_hostnames(self.accept_invalid_hostnames);
+            if let Some(client_timeout) = self.get_client_timeout()? {
+                client_builder = client_builder.with_timeout(client_timeout.get_duration());
+            }
+            // `.rover.toml` takes precedence over the global `settings.toml`, and a profile's
+            // proxy is only used when no other proxy is configured, including the system
+            // proxy that reqwest reads from the environment
+            let mut network_settings = self.get_rover_config()?.settings()?.network;
+            if let Some(proxy) = self
+                .project_config
+                .as_ref()
+                .and_then(|project| project.proxy.clone())
+            {
+                network_settings.proxy = Some(proxy);
+            } else if network_settings.proxy.is_none() && !system_proxy_is_set() {
+                network_settings.proxy = self.get_profile_settings()?.proxy;
+            }
+            let client_builder =
+                self.network_opts
+                    .client_builder(network_env, &network_settings, client_builder)?;
+            self.client_builder.fill(client_builder).ok();
             self.get_reqwest_client_builder()
         }
     }
@@ -329,14 +586,27 @@ impl Rover {
     }
 }
 
+/// Whether a proxy is set with the `HTTPS_PROXY`, `HTTP_PROXY`, or `ALL_PROXY` environment variables
+fn system_proxy_is_set() -> bool {
+    ["HTTPS_PROXY", "HTTP_PROXY", "ALL_PROXY"]
+        .iter()
+        .any(|key| std::env::var(key).is_ok() || std::env::var(key.to_lowercase()).is_ok())
+}
+
 #[derive(Debug, Serialize, Parser)]
 pub enum Command {
+    /// Run queries and mutations against the GraphOS Platform API
+    Api(command::Api),
+
     /// Configuration profile commands
     Config(command::Config),
 
     /// Contract configuration commands
     Contract(command::Contract),
 
+    /// Commands for debugging Rover
+    Debug(command::Debug),
+
     /// Combine multiple subgraphs into a local supergraph
     ///
     /// This command starts a local router that can query across one or more
@@ -386,12 +656,18 @@ pub enum Command {
     /// Readme commands
     Readme(command::Readme),
 
+    /// Commands for working with schemas locally
+    Schema(command::Schema),
+
     /// Subgraph schema commands
     Subgraph(command::Subgraph),
 
     /// Interact with Rover's documentation
     Docs(command::Docs),
 
+    /// Check Rover's configuration and environment for common problems
+    Doctor(command::Doctor),
+
     /// Commands related to updating rover
     Update(command::Update),
 
@@ -412,6 +688,9 @@ pub enum Command {
 
     /// Commands for fetching offline licenses
     License(command::License),
+
+    /// Inspect the anonymous usage data Rover reports
+    Telemetry(command::Telemetry),
 }
 
 #[derive(Default, ValueEnum, Debug, Serialize, Clone, Copy, Eq, PartialEq)]
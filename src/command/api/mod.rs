@@ -0,0 +1,29 @@
+mod query;
+
+pub use query::Query;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Api {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Run any GraphQL query or mutation against the GraphOS Platform API
+    Query(Query),
+}
+
+impl Api {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Query(command) => command.run(client_config),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use clap::Parser;
+use serde::Serialize;
+use serde_json::Value;
+
+use rover_std::Style;
+
+use crate::options::ProfileOpt;
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::FileDescriptorType;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Query {
+    /// The file containing the GraphQL operation to run. You can pass `-` to use stdin instead of a file.
+    #[serde(skip_serializing)]
+    document: FileDescriptorType,
+
+    /// The operation's variables, as a JSON object
+    #[arg(long, value_name = "JSON", value_parser = parse_variables)]
+    #[serde(skip_serializing)]
+    variables: Option<Value>,
+
+    /// The name of the operation to run, when the file contains more than one
+    #[arg(long, value_name = "NAME")]
+    #[serde(skip_serializing)]
+    operation_name: Option<String>,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+impl Query {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let document = self
+            .document
+            .read_file_descriptor("GraphQL operation", &mut std::io::stdin())?;
+        eprintln!(
+            "Running a GraphOS API operation using credentials from the {} profile.",
+            Style::Command.paint(&self.profile.profile_name)
+        );
+
+        let data = client.execute_raw(
+            &document,
+            self.operation_name.as_deref(),
+            self.variables.clone(),
+        )?;
+        Ok(RoverOutput::ApiQueryResponse { data })
+    }
+}
+
+fn parse_variables(variables: &str) -> Result<Value, String> {
+    match serde_json::from_str(variables) {
+        Ok(Value::Object(variables)) => Ok(Value::Object(variables)),
+        Ok(_) => Err(
+            "must be a JSON object keyed by variable name, such as '{\"graphId\": \"my-graph\"}'"
+                .to_string(),
+        ),
+        Err(e) => Err(format!("could not parse JSON: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_variables_objects() {
+        assert_eq!(
+            parse_variables(r#"{"graphId": "my-graph"}"#).unwrap(),
+            json!({ "graphId": "my-graph" })
+        );
+        assert!(parse_variables(r#"["my-graph"]"#).is_err());
+        assert!(parse_variables("graphId: my-graph").is_err());
+    }
+}
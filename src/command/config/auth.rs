@@ -3,10 +3,17 @@ use clap::Parser;
 use rover_std::Style;
 use serde::Serialize;
 
-use config::Profile;
+use config::{CredentialStore, Profile};
 use houston as config;
 
-use crate::{options::ProfileOpt, RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+use crate::{
+    options::ProfileOpt,
+    utils::{
+        client::StudioClientConfig,
+        device_login::{DeviceLogin, DEFAULT_LOGIN_URL},
+    },
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
+};
 
 #[derive(Debug, Serialize, Parser)]
 /// Authenticate a configuration profile with an API key
@@ -18,17 +25,54 @@ use crate::{options::ProfileOpt, RoverError, RoverErrorSuggestion, RoverOutput,
 /// Running without the --profile flag will set an API key for
 /// a profile named "default".
 ///
+/// Running with --login will log in with a browser instead of prompting
+/// for an API key.
+///
 /// Run `rover docs open api-keys` for more details on Apollo's API keys.
 pub struct Auth {
     #[clap(flatten)]
     profile: ProfileOpt,
+
+    /// Where to keep the API key: `file`, `encrypted-file`, or `helper:<name>`.
+    /// Defaults to where the profile's API key is kept already, or `file` for new profiles.
+    #[arg(long = "credential-store", value_name = "STORE")]
+    #[serde(skip_serializing)]
+    credential_store: Option<CredentialStore>,
+
+    /// Log in with a browser instead of pasting an API key
+    #[arg(long = "login")]
+    #[serde(skip_serializing)]
+    login: bool,
+
+    /// The endpoint to log in with
+    #[arg(
+        long = "login-url",
+        value_name = "URL",
+        env = "APOLLO_LOGIN_URL",
+        default_value = DEFAULT_LOGIN_URL,
+        hide_default_value = true,
+        hide_env_values = true
+    )]
+    #[serde(skip_serializing)]
+    login_url: String,
 }
 
 impl Auth {
-    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
-        let api_key = api_key_prompt()?;
-        Profile::set_api_key(&self.profile.profile_name, &config, &api_key)?;
-        Profile::get_credential(&self.profile.profile_name, &config).map(|_| {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let config = &client_config.config;
+        let api_key = if self.login {
+            let login = DeviceLogin::new(client_config.get_reqwest_client()?, &self.login_url);
+            browser_login(&login)?
+        } else {
+            api_key_prompt()?
+        };
+        match &self.credential_store {
+            Some(store) => {
+                Profile::set_api_key_in(&self.profile.profile_name, config, &api_key, store)?
+            }
+            None => Profile::set_api_key(&self.profile.profile_name, config, &api_key)?,
+        }
+        Profile::get_credential(&self.profile.profile_name, config).map(|_| {
             eprintln!("Successfully saved API key. Consider running `rover config whoami` to verify your API authentication.");
         })?;
         Ok(RoverOutput::EmptySuccess)
@@ -48,7 +92,25 @@ fn api_key_prompt() -> RoverResult<String> {
     validate(api_key)
 }
 
-fn validate(api_key: String) -> RoverResult<String> {
+fn browser_login(login: &DeviceLogin) -> RoverResult<String> {
+    let authorization = login.start()?;
+    let url = authorization
+        .verification_uri_complete
+        .as_deref()
+        .unwrap_or(&authorization.verification_uri);
+    eprintln!(
+        "Go to {} and confirm the code {} to log in.",
+        Style::Link.paint(url),
+        Style::Command.paint(&authorization.user_code)
+    );
+    if let Err(e) = opener::open(url) {
+        tracing::debug!("could not open a browser: {}", e);
+    }
+    eprintln!("Waiting for the login to be approved...");
+    login.wait_for_approval(&authorization)
+}
+
+pub(super) fn validate(api_key: String) -> RoverResult<String> {
     if api_key.is_empty() {
         Err(anyhow!("Received an empty API Key. Please try again.").into())
     } else if api_key.as_bytes() == [22] {
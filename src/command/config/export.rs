@@ -0,0 +1,72 @@
+use anyhow::anyhow;
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+use config::{ExportKeys, Profile, ProfileExport};
+use houston as config;
+
+use crate::{utils::env::RoverEnvKey, RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Export configuration profiles, to import them on another machine
+///
+/// Exports every profile unless --profile <name> arguments are passed. The
+/// export is printed as TOML, and can be written to a file with --output.
+///
+/// API keys are left out unless --keys is passed. Encrypted keys are encrypted
+/// with $APOLLO_CREDENTIALS_PASSPHRASE, which is needed again to import them.
+pub struct Export {
+    /// Name of a configuration profile to export. Can be passed more than once
+    #[arg(long = "profile", value_name = "NAME")]
+    #[serde(skip_serializing)]
+    profile_names: Option<Vec<String>>,
+
+    /// Whether to include API keys, and how
+    #[arg(long = "keys", value_enum, default_value_t = KeysOpt::Exclude)]
+    #[serde(skip_serializing)]
+    keys: KeysOpt,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum KeysOpt {
+    /// Leave API keys out, so profiles have to be authenticated again after they're imported
+    Exclude,
+
+    /// Include API keys in plain text
+    Plaintext,
+
+    /// Include API keys encrypted with $APOLLO_CREDENTIALS_PASSPHRASE
+    Encrypted,
+}
+
+impl From<KeysOpt> for ExportKeys {
+    fn from(keys: KeysOpt) -> Self {
+        match keys {
+            KeysOpt::Exclude => ExportKeys::Exclude,
+            KeysOpt::Plaintext => ExportKeys::Plaintext,
+            KeysOpt::Encrypted => ExportKeys::Encrypted,
+        }
+    }
+}
+
+impl Export {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        if matches!(self.keys, KeysOpt::Encrypted) && config.credential_passphrase.is_none() {
+            let mut err = RoverError::new(anyhow!(
+                "No passphrase was provided to encrypt API keys with."
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Set ${} to the passphrase to encrypt API keys with.",
+                RoverEnvKey::CredentialsPassphrase
+            )));
+            return Err(err);
+        }
+        let profile_names = match &self.profile_names {
+            Some(profile_names) => profile_names.clone(),
+            None => Profile::list(&config)?,
+        };
+        let export = ProfileExport::new(&profile_names, &config, self.keys.into())?;
+        eprintln!("Exported {} profile(s).", profile_names.len());
+        Ok(RoverOutput::ConfigExport(export.to_toml()?))
+    }
+}
@@ -0,0 +1,33 @@
+use clap::{builder::PossibleValuesParser, Parser};
+use serde::Serialize;
+
+use config::{Profile, ProfileSettings};
+use houston as config;
+
+use crate::{options::ProfileOpt, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Print a non-sensitive setting of a configuration profile
+///
+/// Prints nothing if the setting isn't set.
+pub struct Get {
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The setting to print
+    #[arg(value_parser = PossibleValuesParser::new(ProfileSettings::KEYS))]
+    #[serde(skip_serializing)]
+    key: String,
+}
+
+impl Get {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        let profile_name = &self.profile.profile_name;
+        let settings = Profile::settings(profile_name, &config)?;
+        Ok(RoverOutput::ProfileSetting {
+            profile_name: profile_name.clone(),
+            key: self.key.clone(),
+            value: settings.get(&self.key)?,
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use clap::Parser;
+use rover_std::Style;
+use serde::Serialize;
+
+use config::{CredentialStore, ProfileExport};
+use houston as config;
+
+use crate::{utils::parsers::FileDescriptorType, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Import configuration profiles from a `rover config export`
+///
+/// Encrypted API keys are decrypted with $APOLLO_CREDENTIALS_PASSPHRASE.
+/// Profiles exported without their API keys need to be authenticated with
+/// `rover config auth` after they're imported.
+pub struct Import {
+    /// The file to import profiles from. You can pass `-` to use stdin instead of a file.
+    #[serde(skip_serializing)]
+    file: FileDescriptorType,
+
+    /// Replace profiles that already exist with the imported ones
+    #[arg(long = "overwrite")]
+    #[serde(skip_serializing)]
+    overwrite: bool,
+
+    /// Where to keep imported API keys: `file`, `encrypted-file`, or `helper:<name>`
+    #[arg(long = "credential-store", value_name = "STORE", default_value_t)]
+    #[serde(skip_serializing)]
+    credential_store: CredentialStore,
+}
+
+impl Import {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        let contents = self
+            .file
+            .read_file_descriptor("profile export", &mut std::io::stdin())?;
+        let export = ProfileExport::from_toml(&contents)?;
+        export.import(&config, &self.credential_store, self.overwrite)?;
+        for (profile_name, has_api_key) in export.profiles() {
+            if has_api_key {
+                eprintln!("Imported profile {}.", Style::Command.paint(profile_name));
+            } else {
+                eprintln!(
+                    "Imported profile {} without an API key. Run `rover config auth --profile {}` to add one.",
+                    Style::Command.paint(profile_name),
+                    profile_name
+                );
+            }
+        }
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
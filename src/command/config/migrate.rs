@@ -0,0 +1,53 @@
+use clap::Parser;
+use rover_std::Style;
+use serde::Serialize;
+
+use config::{CredentialStore, Profile};
+use houston as config;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Move the API keys of configuration profiles to another credential store
+///
+/// Moves every profile's API key unless a --profile <name> argument is passed.
+/// Keys are read from wherever they're kept now, written to the new store,
+/// and erased from the credential helper they were in, if any.
+pub struct Migrate {
+    /// The credential store to move API keys to: `file`, `encrypted-file`, or `helper:<name>`
+    #[arg(long = "to", value_name = "STORE")]
+    #[serde(skip_serializing)]
+    to: CredentialStore,
+
+    /// Name of the only configuration profile to migrate
+    #[arg(long = "profile")]
+    #[serde(skip_serializing)]
+    profile_name: Option<String>,
+}
+
+impl Migrate {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        let profiles = match &self.profile_name {
+            Some(profile_name) => vec![profile_name.clone()],
+            None => Profile::list(&config)?,
+        };
+        for profile_name in profiles {
+            let previous_store = Profile::migrate_api_key(&profile_name, &config, &self.to)?;
+            if previous_store == self.to {
+                eprintln!(
+                    "The API key for profile {} is already in {}.",
+                    Style::Command.paint(&profile_name),
+                    self.to
+                );
+            } else {
+                eprintln!(
+                    "Moved the API key for profile {} from {} to {}.",
+                    Style::Command.paint(&profile_name),
+                    previous_store,
+                    self.to
+                );
+            }
+        }
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
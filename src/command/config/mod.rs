@@ -1,13 +1,21 @@
 mod auth;
 mod clear;
 mod delete;
+mod export;
+mod get;
+mod import;
 mod list;
+mod migrate;
+mod rotate;
+mod set;
+mod show;
+mod unset;
 mod whoami;
 
 use clap::Parser;
 use serde::Serialize;
 
-use crate::utils::client::StudioClientConfig;
+use crate::utils::{client::StudioClientConfig, project_config::SettingOverrides};
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -27,20 +35,58 @@ pub enum Command {
     /// Delete a configuration profile
     Delete(delete::Delete),
 
+    /// Export configuration profiles, to import them on another machine
+    Export(export::Export),
+
+    /// Print a non-sensitive setting of a configuration profile
+    Get(get::Get),
+
+    /// Import configuration profiles from a `rover config export`
+    Import(import::Import),
+
     /// List all configuration profiles
     List(list::List),
 
+    /// Move API keys to another credential store
+    Migrate(migrate::Migrate),
+
+    /// Replace the API key of a configuration profile with a new one
+    Rotate(rotate::Rotate),
+
+    /// Set a non-sensitive setting of a configuration profile
+    Set(set::Set),
+
+    /// Show the settings commands run with, and where they come from
+    Show(show::Show),
+
+    /// Unset a non-sensitive setting of a configuration profile
+    Unset(unset::Unset),
+
     /// View the identity of a user/api key
     Whoami(whoami::WhoAmI),
 }
 
 impl Config {
-    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        overrides: SettingOverrides,
+    ) -> RoverResult<RoverOutput> {
         match &self.command {
-            Command::Auth(command) => command.run(client_config.config),
+            Command::Auth(command) => command.run(client_config),
             Command::List(command) => command.run(client_config.config),
+            Command::Migrate(command) => command.run(client_config.config),
             Command::Delete(command) => command.run(client_config.config),
             Command::Clear(command) => command.run(client_config.config),
+            Command::Export(command) => command.run(client_config.config),
+            Command::Get(command) => command.run(client_config.config),
+            Command::Import(command) => command.run(client_config.config),
+            Command::Rotate(command) => command.run(client_config),
+            Command::Set(command) => command.run(client_config.config),
+            Command::Show(command) => {
+                command.run(client_config.config, client_config.project, overrides)
+            }
+            Command::Unset(command) => command.run(client_config.config),
             Command::Whoami(command) => command.run(client_config),
         }
     }
@@ -0,0 +1,126 @@
+use anyhow::anyhow;
+use clap::Parser;
+use rover_client::operations::config::who_am_i::{
+    self, Actor, ConfigWhoAmIInput, RegistryIdentity,
+};
+use rover_std::Style;
+use serde::Serialize;
+
+use config::{Credential, CredentialOrigin, Profile};
+use houston as config;
+
+use crate::{
+    options::ProfileOpt, utils::client::StudioClientConfig, RoverError, RoverErrorSuggestion,
+    RoverOutput, RoverResult,
+};
+
+use super::auth;
+
+#[derive(Debug, Serialize, Parser)]
+/// Replace the API key of a configuration profile with a new one
+///
+/// The new key is only saved once GraphOS confirms that it belongs to the
+/// same graph, or the same user, as the profile's current key. Delete the
+/// old key in GraphOS Studio once the new one is saved.
+pub struct Rotate {
+    /// Name of the configuration profile to rotate the API key of
+    #[serde(skip_serializing)]
+    name: String,
+}
+
+impl Rotate {
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let config = &client_config.config;
+        let profile = ProfileOpt {
+            profile_name: self.name.clone(),
+        };
+        let old_api_key = Profile::get_api_key(&self.name, config)?;
+        let new_api_key = new_api_key_prompt()?;
+        if new_api_key == old_api_key {
+            return Err(anyhow!(
+                "The new API key is the same as the one profile \"{}\" has already.",
+                self.name
+            )
+            .into());
+        }
+
+        eprintln!("Checking the identity of both API keys against the registry.");
+        let identity = |api_key: String| -> RoverResult<RegistryIdentity> {
+            let credential = Credential {
+                api_key,
+                origin: CredentialOrigin::ConfigFile(self.name.clone()),
+            };
+            let client = client_config.get_client_with_credential(credential, &profile)?;
+            Ok(who_am_i::run(ConfigWhoAmIInput {}, &client)?)
+        };
+        let old_identity = identity(old_api_key).map_err(|mut err| {
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "If the current API key has been deleted already, use `rover config auth --profile {}` to replace it without this check.",
+                self.name
+            )));
+            err
+        })?;
+        let new_identity = identity(new_api_key.clone())?;
+        check_same_identity(&old_identity, &new_identity)?;
+
+        Profile::set_api_key(&self.name, config, &new_api_key)?;
+        eprintln!(
+            "Rotated the API key for profile {}. You can delete the old key in GraphOS Studio now.",
+            Style::Command.paint(&self.name)
+        );
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
+
+fn new_api_key_prompt() -> RoverResult<String> {
+    let term = console::Term::stderr();
+    eprintln!("Paste the new API key into the prompt below.");
+    term.write_str("> ")?;
+    let api_key = term.read_secure_line()?;
+    auth::validate(api_key)
+}
+
+/// Checks that a new API key is for the same graph or user as the key it replaces, so
+/// rotating a key can't change what a profile has access to
+fn check_same_identity(old: &RegistryIdentity, new: &RegistryIdentity) -> RoverResult<()> {
+    let describe = |identity: &RegistryIdentity| match identity.key_actor_type {
+        Actor::GRAPH => format!("graph \"{}\"", identity.id),
+        Actor::USER => format!("user \"{}\"", identity.id),
+        Actor::OTHER => format!("\"{}\"", identity.id),
+    };
+    if old.key_actor_type == new.key_actor_type && old.id == new.id {
+        Ok(())
+    } else {
+        let mut err = RoverError::new(anyhow!(
+            "The new API key is for {}, but the current one is for {}.",
+            describe(new),
+            describe(old)
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Create the new key for the same graph or user as the current one. To switch a profile to a different key, use `rover config auth`.".to_string(),
+        ));
+        Err(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(key_actor_type: Actor, id: &str) -> RegistryIdentity {
+        RegistryIdentity {
+            id: id.to_string(),
+            graph_title: None,
+            key_actor_type,
+            credential_origin: CredentialOrigin::ConfigFile("default".to_string()),
+        }
+    }
+
+    #[test]
+    fn it_only_accepts_keys_for_the_same_identity() {
+        let old = identity(Actor::GRAPH, "my-graph");
+        assert!(check_same_identity(&old, &identity(Actor::GRAPH, "my-graph")).is_ok());
+        assert!(check_same_identity(&old, &identity(Actor::GRAPH, "other-graph")).is_err());
+        assert!(check_same_identity(&old, &identity(Actor::USER, "my-graph")).is_err());
+    }
+}
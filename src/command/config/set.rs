@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use apollo_federation_types::config::FederationVersion;
+use clap::{builder::PossibleValuesParser, Parser};
+use reqwest::Proxy;
+use rover_client::shared::GraphRef;
+use rover_std::Style;
+use serde::Serialize;
+use url::Url;
+
+use config::{HoustonProblem, Profile, ProfileSettings};
+use houston as config;
+
+use crate::{options::ProfileOpt, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Set a non-sensitive setting of a configuration profile
+///
+/// Commands run with the profile use its settings where the corresponding
+/// argument, flag, or environment variable isn't passed:
+///
+/// graph_ref: the <NAME>@<VARIANT> of the graph to use
+///
+/// registry_url: the GraphOS API endpoint to send requests to
+///
+/// client_timeout: how many seconds to wait for HTTP requests to finish
+///
+/// proxy: the URL of the proxy to send requests through
+///
+/// federation_version: the version of Apollo Federation to compose with
+pub struct Set {
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The setting to change
+    #[arg(value_parser = PossibleValuesParser::new(ProfileSettings::KEYS))]
+    #[serde(skip_serializing)]
+    key: String,
+
+    /// The value to give the setting
+    #[serde(skip_serializing)]
+    value: String,
+}
+
+impl Set {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        validate(&self.key, &self.value)?;
+        let profile_name = &self.profile.profile_name;
+        let mut settings = Profile::settings(profile_name, &config)?;
+        settings.set(&self.key, &self.value)?;
+        Profile::save_settings(profile_name, &config, &settings)?;
+        eprintln!(
+            "Set {} for profile {}.",
+            Style::Command.paint(&self.key),
+            Style::Command.paint(profile_name)
+        );
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
+
+/// Checks that `value` is usable for the setting named `key`, so a bad value is
+/// reported when it's set rather than by every command that uses it
+fn validate(key: &str, value: &str) -> Result<(), HoustonProblem> {
+    let reason = match key {
+        "graph_ref" => GraphRef::from_str(value).err().map(|e| e.to_string()),
+        "registry_url" => Url::parse(value).err().map(|e| e.to_string()),
+        "proxy" => Proxy::all(value).err().map(|e| e.to_string()),
+        "federation_version" => FederationVersion::from_str(value).err().map(|_| {
+            "it must be 'latest-2' or an exact version preceded by '=', such as '=2.5.0'"
+                .to_string()
+        }),
+        _ => None,
+    };
+    match reason {
+        // proxy URLs aren't included in errors since they may contain credentials
+        Some(reason) if key == "proxy" => Err(HoustonProblem::InvalidProfileSetting {
+            key: key.to_string(),
+            reason: format!("the URL is not valid: {reason}"),
+        }),
+        Some(reason) => Err(HoustonProblem::InvalidProfileSetting {
+            key: key.to_string(),
+            reason: format!("'{value}' is not valid: {reason}"),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_validates_setting_values() {
+        assert!(validate("graph_ref", "my-graph@prod").is_ok());
+        assert!(validate("graph_ref", "my graph").is_err());
+        assert!(validate("registry_url", "https://api.example.com/graphql").is_ok());
+        assert!(validate("registry_url", "api.example.com").is_err());
+        assert!(validate("federation_version", "=2.5.0").is_ok());
+        assert!(validate("federation_version", "two").is_err());
+        assert!(validate("client_timeout", "60").is_ok());
+    }
+}
@@ -0,0 +1,148 @@
+use clap::Parser;
+use serde::Serialize;
+
+use houston as config;
+
+use crate::{
+    options::ProfileOpt,
+    utils::{
+        client::STUDIO_PROD_API_ENDPOINT,
+        env::RoverEnvKey,
+        project_config::{
+            collect_settings, redact_proxy, ProjectConfig, SettingCandidates, SettingOverrides,
+            SettingSource,
+        },
+    },
+    RoverOutput, RoverResult,
+};
+
+#[derive(Debug, Serialize, Parser)]
+/// Show the settings commands run with, and where they come from
+///
+/// Settings are taken from flags, then environment variables, then the
+/// closest `.rover.toml`, then `settings.toml` in the config home, then the
+/// profile's settings, then defaults.
+///
+/// Without --resolved, every value each setting is given is shown, highest
+/// precedence first.
+pub struct Show {
+    /// Name of the configuration profile to show settings for.
+    /// Defaults to the `profile` in `.rover.toml`, or `default`
+    #[arg(long = "profile")]
+    #[serde(skip_serializing)]
+    profile_name: Option<String>,
+
+    /// Only show the value each setting takes effect with
+    #[arg(long = "resolved")]
+    #[serde(skip_serializing)]
+    resolved: bool,
+}
+
+impl Show {
+    pub fn run(
+        &self,
+        config: config::Config,
+        project: Option<ProjectConfig>,
+        overrides: SettingOverrides,
+    ) -> RoverResult<RoverOutput> {
+        let project = project.as_ref();
+        let from_project = |value: fn(&ProjectConfig) -> Option<String>| match project {
+            Some(project) => (
+                value(project),
+                SettingSource::ProjectConfig(project.path.clone()),
+            ),
+            None => (None, SettingSource::ProjectConfig(Default::default())),
+        };
+
+        let profile_name = self
+            .profile_name
+            .clone()
+            .or_else(|| project.and_then(|project| project.profile.clone()))
+            .unwrap_or_else(|| "default".to_string());
+        let profile = ProfileOpt {
+            profile_name: profile_name.clone(),
+        }
+        .settings(&config)?;
+        let profile_source = SettingSource::Profile(profile_name);
+        let global_proxy = config.settings()?.network.proxy;
+
+        let candidates: Vec<SettingCandidates> = vec![
+            (
+                "profile",
+                vec![
+                    (self.profile_name.clone(), SettingSource::Flag("--profile")),
+                    from_project(|project| project.profile.clone()),
+                    (Some("default".to_string()), SettingSource::Default),
+                ],
+            ),
+            (
+                "graph_ref",
+                vec![
+                    from_project(|project| project.graph_ref.clone()),
+                    (profile.graph_ref, profile_source.clone()),
+                ],
+            ),
+            (
+                "registry_url",
+                vec![
+                    (
+                        overrides.registry_url_env,
+                        SettingSource::Env(RoverEnvKey::RegistryUrl.to_string()),
+                    ),
+                    from_project(|project| project.registry_url.clone()),
+                    (profile.registry_url, profile_source.clone()),
+                    (
+                        Some(STUDIO_PROD_API_ENDPOINT.to_string()),
+                        SettingSource::Default,
+                    ),
+                ],
+            ),
+            (
+                "client_timeout",
+                vec![
+                    (
+                        overrides.client_timeout.map(|seconds| seconds.to_string()),
+                        SettingSource::Flag("--client-timeout"),
+                    ),
+                    from_project(|project| {
+                        project.client_timeout.map(|seconds| seconds.to_string())
+                    }),
+                    (
+                        profile.client_timeout.map(|seconds| seconds.to_string()),
+                        profile_source.clone(),
+                    ),
+                    (Some("30".to_string()), SettingSource::Default),
+                ],
+            ),
+            (
+                "proxy",
+                vec![
+                    (overrides.proxy_flag, SettingSource::Flag("--proxy")),
+                    (
+                        overrides.proxy_env,
+                        SettingSource::Env(RoverEnvKey::Proxy.to_string()),
+                    ),
+                    from_project(|project| project.proxy.clone()),
+                    (global_proxy, SettingSource::GlobalSettings),
+                    (profile.proxy, profile_source.clone()),
+                ],
+            ),
+            (
+                "federation_version",
+                vec![
+                    from_project(|project| project.federation_version.clone()),
+                    (profile.federation_version, profile_source),
+                    (Some("latest-2".to_string()), SettingSource::Default),
+                ],
+            ),
+        ];
+
+        let mut settings = collect_settings(candidates, self.resolved);
+        for setting in &mut settings {
+            if setting.key == "proxy" {
+                setting.value = setting.value.as_deref().map(redact_proxy);
+            }
+        }
+        Ok(RoverOutput::ConfigSettings(settings))
+    }
+}
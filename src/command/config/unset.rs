@@ -0,0 +1,37 @@
+use clap::{builder::PossibleValuesParser, Parser};
+use rover_std::Style;
+use serde::Serialize;
+
+use config::{Profile, ProfileSettings};
+use houston as config;
+
+use crate::{options::ProfileOpt, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Unset a non-sensitive setting of a configuration profile
+///
+/// Commands run with the profile go back to their usual default for the setting.
+pub struct Unset {
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    /// The setting to unset
+    #[arg(value_parser = PossibleValuesParser::new(ProfileSettings::KEYS))]
+    #[serde(skip_serializing)]
+    key: String,
+}
+
+impl Unset {
+    pub fn run(&self, config: config::Config) -> RoverResult<RoverOutput> {
+        let profile_name = &self.profile.profile_name;
+        let mut settings = Profile::settings(profile_name, &config)?;
+        settings.unset(&self.key)?;
+        Profile::save_settings(profile_name, &config, &settings)?;
+        eprintln!(
+            "Unset {} for profile {}.",
+            Style::Command.paint(&self.key),
+            Style::Command.paint(profile_name)
+        );
+        Ok(RoverOutput::EmptySuccess)
+    }
+}
@@ -20,15 +20,16 @@ pub struct Describe {
 impl Describe {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         eprintln!(
             "Fetching description for configuration of {} using credentials from the {} profile.\n",
-            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
         let describe_response = describe::run(
             ContractDescribeInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
             },
             &client,
         )?;
@@ -71,9 +71,10 @@ pub struct Publish {
 impl Publish {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         eprintln!(
             "Publishing configuration to {} using credentials from the {} profile.\n",
-            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
@@ -93,7 +94,7 @@ impl Publish {
 
         let publish_response = publish::run(
             ContractPublishInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 source_variant: self.source_variant.clone(),
                 include_tags,
                 exclude_tags,
@@ -0,0 +1,60 @@
+use anyhow::anyhow;
+use camino::Utf8PathBuf;
+use clap::Parser;
+use robot_panic::bundle::write_bundle;
+use serde::Serialize;
+
+use crate::utils::crash_bundle::{self, BundleSnapshot};
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Write a bundle of debugging information to share with Apollo
+///
+/// The bundle is a .tar.gz archive with the same context that's saved when
+/// Rover crashes: this command, Rover's environment variables, the plugins
+/// that are installed, and the most recent info, warning and error logs.
+/// To include the logs of a command that failed, re-run it with
+/// `--log-file <FILE>` and pass the same file to `--logs`.
+/// API keys, passphrases, headers and client secrets Rover has used are
+/// masked, but check the bundle before sharing it.
+pub struct Bundle {
+    /// The path to write the bundle to
+    #[arg(default_value = "rover-debug-bundle.tar.gz")]
+    #[serde(skip_serializing)]
+    path: Utf8PathBuf,
+
+    /// A log file written with `--log-file` to include instead of this command's own logs.
+    /// Only its last 1000 lines are included.
+    #[arg(long = "logs", value_name = "FILE")]
+    #[serde(skip_serializing)]
+    logs: Option<Utf8PathBuf>,
+}
+
+impl Bundle {
+    pub(crate) fn run(&self, snapshot: BundleSnapshot) -> RoverResult<RoverOutput> {
+        let mut context = snapshot.context();
+        if let Some(logs) = &self.logs {
+            context.log_lines = crash_bundle::read_log_file(logs).map_err(|e| {
+                let mut err =
+                    RoverError::new(anyhow!("Could not read the log file {}: {}", logs, e));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                    "Pass the path given to `--log-file` when the command that failed was run."
+                        .to_string(),
+                ));
+                err
+            })?;
+        }
+        write_bundle(&self.path, None, &context).map_err(|e| {
+            let mut err = RoverError::new(anyhow!(
+                "Could not write a debug bundle to {}: {}",
+                &self.path,
+                e
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Check that the directory exists and can be written to.".to_string(),
+            ));
+            err
+        })?;
+        Ok(RoverOutput::DebugBundle(self.path.clone()))
+    }
+}
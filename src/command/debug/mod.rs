@@ -0,0 +1,27 @@
+mod bundle;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::utils::crash_bundle::BundleSnapshot;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Debug {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Write a bundle of debugging information to share with Apollo
+    Bundle(bundle::Bundle),
+}
+
+impl Debug {
+    pub(crate) fn run(&self, snapshot: BundleSnapshot) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Bundle(command) => command.run(snapshot),
+        }
+    }
+}
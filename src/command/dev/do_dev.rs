@@ -35,15 +35,19 @@ impl Dev {
         let leader_channel = LeaderChannel::new();
         let follower_channel = FollowerChannel::new();
 
+        let federation_version = match &self.opts.supergraph_opts.federation_version {
+            Some(federation_version) => federation_version.clone(),
+            None => self
+                .opts
+                .plugin_opts
+                .profile
+                .federation_version(&client_config)?
+                .unwrap_or(FederationVersion::LatestFedTwo),
+        };
         let supergraph_config = get_supergraph_config(
             &self.opts.supergraph_opts.graph_ref,
             &self.opts.supergraph_opts.supergraph_config_path,
-            &self
-                .opts
-                .supergraph_opts
-                .federation_version
-                .clone()
-                .unwrap_or(FederationVersion::LatestFedTwo),
+            &federation_version,
             client_config.clone(),
             &self.opts.plugin_opts.profile,
         )?;
@@ -123,9 +127,10 @@ impl Dev {
                 })?;
 
             subgraph_watchers.into_iter().for_each(|mut watcher| {
+                let retry_policy = client_config.retry_policy.clone();
                 std::thread::spawn(move || {
                     let _ = watcher
-                        .watch_subgraph_for_changes(client_config.retry_period)
+                        .watch_subgraph_for_changes(&retry_policy)
                         .map_err(log_err_and_continue);
                 });
             });
@@ -166,7 +171,7 @@ impl Dev {
 
             // watch for subgraph changes on the main thread
             // it will take care of updating the main `rover dev` session
-            subgraph_refresher.watch_subgraph_for_changes(client_config.retry_period)?;
+            subgraph_refresher.watch_subgraph_for_changes(&client_config.retry_policy)?;
         }
 
         unreachable!("watch_subgraph_for_changes never returns")
@@ -3,19 +3,28 @@ use std::time::Duration;
 use anyhow::anyhow;
 use reqwest::blocking::Client;
 
+use rover_client::blocking::{GraphQLTransport, RetryPolicy};
 use rover_std::Style;
 
 use crate::command::dev::protocol::{SubgraphSdl, SubgraphUrl};
 use crate::command::graph::Introspect as GraphIntrospect;
 use crate::command::subgraph::Introspect as SubgraphIntrospect;
-use crate::options::IntrospectOpts;
+use crate::options::{
+    IntrospectOpts, IntrospectionAuthOpts, IntrospectionOutputFormat, IntrospectionQueryOpts,
+};
+use crate::utils::auth_provider::AuthProvider;
 use crate::{RoverError, RoverErrorSuggestion, RoverResult};
 
+/// How long `rover dev` waits for a running subgraph to answer introspection
+pub(crate) const INTROSPECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone, Debug)]
 pub struct UnknownIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
     headers: Option<Vec<(String, String)>>,
+    transport: GraphQLTransport,
+    auth: Option<AuthProvider>,
 }
 
 impl UnknownIntrospectRunner {
@@ -23,30 +32,38 @@ impl UnknownIntrospectRunner {
         endpoint: SubgraphUrl,
         client: Client,
         headers: Option<Vec<(String, String)>>,
+        transport: GraphQLTransport,
+        auth: Option<AuthProvider>,
     ) -> Self {
         Self {
             endpoint,
             client,
             headers,
+            transport,
+            auth,
         }
     }
 
     pub fn run(
         &self,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
     ) -> RoverResult<(SubgraphSdl, IntrospectRunnerKind)> {
         let subgraph_runner = SubgraphIntrospectRunner {
             endpoint: self.endpoint.clone(),
             client: self.client.clone(),
             headers: self.headers.clone(),
-            retry_period,
+            transport: self.transport,
+            auth: self.auth.clone(),
+            retry_policy: retry_policy.clone(),
         };
 
         let graph_runner = GraphIntrospectRunner {
             endpoint: self.endpoint.clone(),
             client: self.client.clone(),
             headers: self.headers.clone(),
-            retry_period,
+            transport: self.transport,
+            auth: self.auth.clone(),
+            retry_policy: retry_policy.clone(),
         };
 
         // we _could_ run these in parallel
@@ -109,7 +126,9 @@ pub struct SubgraphIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
     headers: Option<Vec<(String, String)>>,
-    retry_period: Option<Duration>,
+    transport: GraphQLTransport,
+    auth: Option<AuthProvider>,
+    retry_policy: RetryPolicy,
 }
 
 impl SubgraphIntrospectRunner {
@@ -123,9 +142,20 @@ impl SubgraphIntrospectRunner {
                 endpoint: self.endpoint.clone(),
                 headers: self.headers.clone(),
                 watch: false,
+                watch_interval: 1,
+                diff: false,
+                on_change: None,
+                transport: self.transport,
+                auth: IntrospectionAuthOpts::default(),
             },
         }
-        .exec(&self.client, true, self.retry_period)
+        .exec(
+            &self.client,
+            true,
+            &self.retry_policy,
+            Some(INTROSPECTION_TIMEOUT),
+            self.auth.as_ref(),
+        )
     }
 }
 
@@ -134,7 +164,9 @@ pub struct GraphIntrospectRunner {
     endpoint: SubgraphUrl,
     client: Client,
     headers: Option<Vec<(String, String)>>,
-    retry_period: Option<Duration>,
+    transport: GraphQLTransport,
+    auth: Option<AuthProvider>,
+    retry_policy: RetryPolicy,
 }
 
 impl GraphIntrospectRunner {
@@ -148,8 +180,21 @@ impl GraphIntrospectRunner {
                 endpoint: self.endpoint.clone(),
                 headers: self.headers.clone(),
                 watch: false,
+                watch_interval: 1,
+                diff: false,
+                on_change: None,
+                transport: self.transport,
+                auth: IntrospectionAuthOpts::default(),
             },
+            query: IntrospectionQueryOpts::default(),
+            output_format: IntrospectionOutputFormat::Sdl,
         }
-        .exec(&self.client, true, self.retry_period)
+        .exec(
+            &self.client,
+            true,
+            &self.retry_policy,
+            Some(INTROSPECTION_TIMEOUT),
+            self.auth.as_ref(),
+        )
     }
 }
@@ -1,4 +1,4 @@
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use apollo_federation_types::config::FederationVersion;
 use camino::Utf8PathBuf;
@@ -106,6 +106,24 @@ pub struct SupergraphOpts {
     federation_version: Option<FederationVersion>,
 }
 
+/// The address the router listens on when no address or port is configured
+pub(crate) const DEFAULT_ROUTER_SOCKET_ADDR: SocketAddr =
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 4000);
+
+/// The name of the interprocess socket that `rover dev` sessions for the router
+/// at `router_address` communicate over
+pub(crate) fn raw_socket_name(router_address: SocketAddr) -> String {
+    let socket_name = format!("supergraph-{}.sock", router_address);
+    #[cfg(windows)]
+    {
+        format!("\\\\.\\pipe\\{}", socket_name)
+    }
+    #[cfg(unix)]
+    {
+        format!("/tmp/{}", socket_name)
+    }
+}
+
 lazy_static::lazy_static! {
     pub(crate) static ref OVERRIDE_DEV_ROUTER_VERSION: Option<String> =
       std::env::var("APOLLO_ROVER_DEV_ROUTER_VERSION").ok();
@@ -1,5 +1,5 @@
 use std::{
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, SocketAddr},
     sync::{Arc, Mutex},
 };
 
@@ -12,13 +12,12 @@ use rover_std::{Emoji, Fs};
 
 use crate::utils::expansion::expand;
 use crate::{
-    command::dev::{do_dev::log_err_and_continue, SupergraphOpts},
+    command::dev::{
+        do_dev::log_err_and_continue, raw_socket_name, SupergraphOpts, DEFAULT_ROUTER_SOCKET_ADDR,
+    },
     RoverError, RoverResult,
 };
 
-const DEFAULT_ROUTER_SOCKET_ADDR: SocketAddr =
-    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 4000);
-
 /// [`RouterConfigHandler`] is reponsible for orchestrating the YAML configuration file
 /// passed to the router plugin, optionally watching a user's router configuration file for changes
 #[derive(Debug, Clone)]
@@ -124,15 +123,7 @@ impl RouterConfigHandler {
 
     /// Get the name of the interprocess socket address to communicate with other rover dev sessions
     pub fn get_raw_socket_name(&self) -> String {
-        let socket_name = format!("supergraph-{}.sock", self.get_router_address());
-        #[cfg(windows)]
-        {
-            format!("\\\\.\\pipe\\{}", socket_name)
-        }
-        #[cfg(unix)]
-        {
-            format!("/tmp/{}", socket_name)
-        }
+        raw_socket_name(self.get_router_address())
     }
 
     /// The path to the composed supergraph schema
@@ -254,6 +254,7 @@ impl Drop for RouterRunner {
 #[cfg(test)]
 mod tests {
     use httpmock::MockServer;
+    use rover_client::blocking::RetryPolicy;
     use rstest::*;
     use speculoos::prelude::*;
 
@@ -295,7 +296,7 @@ mod tests {
                 houston::Config::new(None::<&Utf8PathBuf>, None).unwrap(),
                 false,
                 ClientBuilder::new(),
-                Some(Duration::from_secs(3)),
+                RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
             ),
         );
 
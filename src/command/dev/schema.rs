@@ -1,19 +1,22 @@
-use std::{net::SocketAddr, time::Duration};
+use std::net::SocketAddr;
 
 use anyhow::anyhow;
 use apollo_federation_types::config::{SchemaSource, SupergraphConfig};
 use reqwest::Url;
 
-use rover_client::blocking::StudioClient;
+use rover_client::blocking::{GraphQLTransport, StudioClient};
 
 use crate::options::ProfileOpt;
 use crate::{
     command::dev::{
-        netstat::normalize_loopback_urls, protocol::FollowerMessenger,
-        watcher::SubgraphSchemaWatcher, SupergraphOpts,
+        introspect::{UnknownIntrospectRunner, INTROSPECTION_TIMEOUT},
+        netstat::normalize_loopback_urls,
+        protocol::FollowerMessenger,
+        watcher::SubgraphSchemaWatcher,
+        SupergraphOpts,
     },
     options::OptionalSubgraphOpts,
-    utils::client::StudioClientConfig,
+    utils::{client::StudioClientConfig, crash_bundle},
     RoverError, RoverErrorSuggestion, RoverResult,
 };
 
@@ -81,18 +84,18 @@ impl OptionalSubgraphOpts {
                 self.subgraph_retries,
             )
         } else {
+            client_config.check_transport(self.subgraph_transport)?;
             let client = client_config
                 .get_builder()
-                .with_timeout(Duration::from_secs(5))
+                .with_timeout(INTROSPECTION_TIMEOUT)
                 .build()?;
+            let auth = self.subgraph_auth.provider(&client);
             SubgraphSchemaWatcher::new_from_url(
                 (name, url.clone()),
-                client,
+                UnknownIntrospectRunner::new(url, client, None, self.subgraph_transport, auth),
                 follower_messenger,
                 self.subgraph_polling_interval,
-                None,
                 self.subgraph_retries,
-                url,
             )
         }
     }
@@ -117,7 +120,7 @@ impl SupergraphOpts {
 
         let client = client_config
             .get_builder()
-            .with_timeout(Duration::from_secs(5))
+            .with_timeout(INTROSPECTION_TIMEOUT)
             .build()?;
         let mut studio_client: Option<StudioClient> = None;
         supergraph_config
@@ -145,14 +148,31 @@ impl SupergraphOpts {
                         introspection_headers,
                     } => {
                         let url = routing_url.unwrap_or(subgraph_url.clone());
+                        let headers = introspection_headers.map(|header_map| {
+                            header_map
+                                .into_iter()
+                                .inspect(|(name, value)| {
+                                    if crash_bundle::is_credential_header(name) {
+                                        crash_bundle::mask_secret(value)
+                                    }
+                                })
+                                .collect()
+                        });
+                        // `introspection_transport` and `introspection_auth` were removed when the
+                        // supergraph config was resolved, so these subgraphs are introspected with
+                        // POST requests and without minted tokens
                         SubgraphSchemaWatcher::new_from_url(
                             (yaml_subgraph_name, url),
-                            client.clone(),
+                            UnknownIntrospectRunner::new(
+                                subgraph_url,
+                                client.clone(),
+                                headers,
+                                GraphQLTransport::default(),
+                                None,
+                            ),
                             follower_messenger.clone(),
                             polling_interval,
-                            introspection_headers,
                             subgraph_retries,
-                            subgraph_url,
                         )
                     }
                     SchemaSource::Sdl { sdl } => {
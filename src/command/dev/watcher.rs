@@ -1,14 +1,12 @@
 use std::str::FromStr;
-use std::{collections::HashMap, time::Duration};
 
 use anyhow::{anyhow, Context};
 use apollo_federation_types::build::SubgraphDefinition;
 use camino::{Utf8Path, Utf8PathBuf};
 use crossbeam_channel::unbounded;
-use reqwest::blocking::Client;
 use url::Url;
 
-use rover_client::blocking::StudioClient;
+use rover_client::blocking::{RetryPolicy, StudioClient};
 use rover_client::operations::subgraph::fetch;
 use rover_client::operations::subgraph::fetch::SubgraphFetchInput;
 use rover_client::shared::GraphRef;
@@ -52,22 +50,14 @@ impl SubgraphSchemaWatcher {
 
     pub fn new_from_url(
         subgraph_key: SubgraphKey,
-        client: Client,
+        introspect_runner: UnknownIntrospectRunner,
         message_sender: FollowerMessenger,
         polling_interval: u64,
-        headers: Option<HashMap<String, String>>,
         subgraph_retries: u64,
-        subgraph_url: Url,
     ) -> RoverResult<Self> {
-        let headers = headers.map(|header_map| header_map.into_iter().collect());
-        let introspect_runner = IntrospectRunnerKind::Unknown(UnknownIntrospectRunner::new(
-            subgraph_url,
-            client,
-            headers,
-        ));
         Self::new_from_introspect_runner(
             subgraph_key,
-            introspect_runner,
+            IntrospectRunnerKind::Unknown(introspect_runner),
             message_sender,
             polling_interval,
             subgraph_retries,
@@ -149,7 +139,7 @@ impl SubgraphSchemaWatcher {
     ) -> RoverResult<Self> {
         Ok(Self {
             schema_watcher_kind: SubgraphSchemaWatcherKind::Introspect(
-                introspect_runner,
+                Box::new(introspect_runner),
                 polling_interval,
             ),
             subgraph_key,
@@ -161,12 +151,12 @@ impl SubgraphSchemaWatcher {
 
     pub fn get_subgraph_definition_and_maybe_new_runner(
         &self,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
     ) -> RoverResult<(SubgraphDefinition, Option<SubgraphSchemaWatcherKind>)> {
         let (name, url) = self.subgraph_key.clone();
         let (sdl, refresher) = match &self.schema_watcher_kind {
             SubgraphSchemaWatcherKind::Introspect(introspect_runner_kind, polling_interval) => {
-                match introspect_runner_kind {
+                match introspect_runner_kind.as_ref() {
                     IntrospectRunnerKind::Graph(graph_runner) => {
                         let sdl = graph_runner.run()?;
                         (sdl, None)
@@ -176,11 +166,11 @@ impl SubgraphSchemaWatcher {
                         (sdl, None)
                     }
                     IntrospectRunnerKind::Unknown(unknown_runner) => {
-                        let (sdl, specific_runner) = unknown_runner.run(retry_period)?;
+                        let (sdl, specific_runner) = unknown_runner.run(retry_policy)?;
                         (
                             sdl,
                             Some(SubgraphSchemaWatcherKind::Introspect(
-                                specific_runner,
+                                Box::new(specific_runner),
                                 *polling_interval,
                             )),
                         )
@@ -202,10 +192,10 @@ impl SubgraphSchemaWatcher {
     fn update_subgraph(
         &mut self,
         last_message: Option<&String>,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
     ) -> RoverResult<Option<String>> {
         let maybe_update_message = match self
-            .get_subgraph_definition_and_maybe_new_runner(retry_period)
+            .get_subgraph_definition_and_maybe_new_runner(retry_policy)
         {
             Ok((subgraph_definition, maybe_new_refresher)) => {
                 if let Some(new_refresher) = maybe_new_refresher {
@@ -267,10 +257,7 @@ impl SubgraphSchemaWatcher {
     ///
     /// This function will block forever for `SubgraphSchemaWatcherKind` that poll for changes—so it
     /// should be started in a separate thread.
-    pub fn watch_subgraph_for_changes(
-        &mut self,
-        retry_period: Option<Duration>,
-    ) -> RoverResult<()> {
+    pub fn watch_subgraph_for_changes(&mut self, retry_policy: &RetryPolicy) -> RoverResult<()> {
         let mut last_message = None;
         match self.schema_watcher_kind.clone() {
             SubgraphSchemaWatcherKind::Introspect(introspect_runner_kind, polling_interval) => {
@@ -286,13 +273,13 @@ impl SubgraphSchemaWatcher {
                     }
                 );
                 loop {
-                    last_message = self.update_subgraph(last_message.as_ref(), retry_period)?;
+                    last_message = self.update_subgraph(last_message.as_ref(), retry_policy)?;
                     std::thread::sleep(std::time::Duration::from_secs(polling_interval));
                 }
             }
             SubgraphSchemaWatcherKind::File(path) => {
                 // populate the schema for the first time (last_message is always None to start)
-                last_message = self.update_subgraph(last_message.as_ref(), retry_period)?;
+                last_message = self.update_subgraph(last_message.as_ref(), retry_policy)?;
 
                 let (tx, rx) = unbounded();
 
@@ -306,11 +293,11 @@ impl SubgraphSchemaWatcher {
                         Ok(Err(err)) => return Err(anyhow::Error::from(err).into()),
                         Err(err) => return Err(anyhow::Error::from(err).into()),
                     }
-                    last_message = self.update_subgraph(last_message.as_ref(), retry_period)?;
+                    last_message = self.update_subgraph(last_message.as_ref(), retry_policy)?;
                 }
             }
             SubgraphSchemaWatcherKind::Once(_) => {
-                self.update_subgraph(None, retry_period)?;
+                self.update_subgraph(None, retry_policy)?;
             }
         }
         Ok(())
@@ -328,7 +315,7 @@ impl SubgraphSchemaWatcher {
 #[derive(Debug, Clone)]
 pub enum SubgraphSchemaWatcherKind {
     /// Poll an endpoint via introspection
-    Introspect(IntrospectRunnerKind, u64),
+    Introspect(Box<IntrospectRunnerKind>, u64),
     /// Watch a file on disk
     File(Utf8PathBuf),
     /// Don't ever update, schema is only pulled once
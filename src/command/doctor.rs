@@ -0,0 +1,402 @@
+use std::fmt;
+use std::net::TcpListener;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use apollo_federation_types::config::{FederationVersion, RouterVersion};
+use clap::Parser;
+use interprocess::local_socket::traits::Stream as _;
+use interprocess::local_socket::{GenericFilePath, Stream, ToFsName};
+use reqwest::StatusCode;
+use rover_client::operations::config::who_am_i::{self, Actor, ConfigWhoAmIInput};
+use rover_client::RoverClientError;
+use serde::Serialize;
+use serde_json::Value;
+
+use houston::{Credential, CredentialOrigin, Profile};
+
+use crate::command::dev::{raw_socket_name, DEFAULT_ROUTER_SOCKET_ADDR};
+use crate::command::install::Plugin;
+use crate::options::ProfileOpt;
+use crate::utils::client::StudioClientConfig;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+// an operation every GraphOS endpoint can answer, with or without a valid API key
+const REACHABILITY_QUERY: &str = "query RoverDoctor { __typename }";
+
+#[derive(Debug, Serialize, Parser)]
+/// Check Rover's configuration and environment for common problems
+///
+/// Checks the config directory, the profile's API key, whether GraphOS is
+/// reachable, whether plugins are published for this machine, ELv2 license
+/// acceptance, and whether `rover dev` can start with its default address.
+pub struct Doctor {
+    #[clap(flatten)]
+    profile: ProfileOpt,
+}
+
+/// Returned by `rover doctor` when any of its checks fail, so it exits with an error
+/// after reporting every check
+#[derive(Debug, Clone)]
+pub struct DoctorFailures(pub Vec<DoctorCheck>);
+
+impl fmt::Display for DoctorFailures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed = self
+            .0
+            .iter()
+            .filter(|check| check.status == DoctorStatus::Fail)
+            .count();
+        write!(f, "{} of {} checks failed.", failed, self.0.len())
+    }
+}
+
+impl std::error::Error for DoctorFailures {}
+
+/// Whether one of `rover doctor`'s checks passed. Warnings are reported with their
+/// suggestions, but don't make `rover doctor` fail.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for DoctorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DoctorStatus::Pass => write!(f, "pass"),
+            DoctorStatus::Warn => write!(f, "warn"),
+            DoctorStatus::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// The result of one of `rover doctor`'s checks
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: DoctorStatus,
+    pub details: String,
+    pub suggestions: Vec<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: DoctorStatus::Pass,
+            details: details.into(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    fn with_problem(name: &'static str, status: DoctorStatus, error: RoverError) -> Self {
+        Self {
+            name,
+            status,
+            details: error.message(),
+            suggestions: error
+                .suggestions()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    fn from_result(name: &'static str, result: RoverResult<String>) -> Self {
+        match result {
+            Ok(details) => Self::pass(name, details),
+            Err(error) => Self::with_problem(name, DoctorStatus::Fail, error),
+        }
+    }
+
+    /// Like `from_result`, for checks of things that only matter for some commands
+    fn warn_from_result(name: &'static str, result: RoverResult<String>) -> Self {
+        match result {
+            Ok(details) => Self::pass(name, details),
+            Err(error) => Self::with_problem(name, DoctorStatus::Warn, error),
+        }
+    }
+}
+
+impl Doctor {
+    pub fn run(&self, client_config: RoverResult<StudioClientConfig>) -> RoverResult<RoverOutput> {
+        eprintln!("Checking Rover's configuration and environment.");
+        let mut checks = Vec::new();
+        match client_config {
+            Ok(client_config) => {
+                checks.push(DoctorCheck::from_result(
+                    "Config directory",
+                    check_config_home(&client_config),
+                ));
+                checks.push(DoctorCheck::from_result(
+                    "Profile",
+                    self.check_profile(&client_config),
+                ));
+                checks.push(DoctorCheck::from_result(
+                    "GraphOS",
+                    self.check_studio(&client_config),
+                ));
+                checks.push(DoctorCheck::warn_from_result(
+                    "ELv2 license",
+                    check_elv2_license(&client_config),
+                ));
+            }
+            // none of the checks that read the config can run without it
+            Err(error) => checks.push(DoctorCheck::with_problem(
+                "Config directory",
+                DoctorStatus::Fail,
+                error,
+            )),
+        }
+        checks.push(DoctorCheck::from_result("Plugins", check_plugins()));
+        // another `rover dev` session or router only gets in the way of starting a new one
+        checks.push(DoctorCheck::warn_from_result(
+            "rover dev socket",
+            check_dev_socket(),
+        ));
+        checks.push(DoctorCheck::warn_from_result(
+            "Router port",
+            check_router_port(),
+        ));
+        if checks
+            .iter()
+            .all(|check| check.status != DoctorStatus::Fail)
+        {
+            Ok(RoverOutput::DoctorReport(checks))
+        } else {
+            Err(RoverError::new(DoctorFailures(checks)))
+        }
+    }
+
+    fn check_profile(&self, client_config: &StudioClientConfig) -> RoverResult<String> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let identity = who_am_i::run(ConfigWhoAmIInput {}, &client)?;
+        let key_owner = match identity.key_actor_type {
+            Actor::GRAPH => format!("graph '{}'", identity.id),
+            Actor::USER => format!("user '{}'", identity.id),
+            _ => {
+                let mut err = RoverError::new(anyhow!(
+                    "The API key for profile '{}' is a {} key, which Rover doesn't accept.",
+                    &self.profile.profile_name,
+                    identity.key_actor_type
+                ));
+                err.set_suggestion(RoverErrorSuggestion::ProperKey);
+                return Err(err);
+            }
+        };
+        Ok(format!(
+            "Profile '{}' has a valid API key for {}",
+            &self.profile.profile_name, key_owner
+        ))
+    }
+
+    fn check_studio(&self, client_config: &StudioClientConfig) -> RoverResult<String> {
+        // the profile's key is used when there is one, but GraphOS answers without one too
+        let credential = Profile::get_credential(&self.profile.profile_name, &client_config.config)
+            .unwrap_or_else(|_| Credential {
+                api_key: String::new(),
+                origin: CredentialOrigin::EnvVar,
+            });
+        // retrying would only delay reporting that GraphOS can't be reached
+        let mut probe_config = client_config.clone();
+        probe_config.retry_policy = probe_config.retry_policy.clone().with_max_retries(0);
+        let client = probe_config.get_client_with_credential(credential, &self.profile)?;
+        let started = Instant::now();
+        let result = client.execute_raw(REACHABILITY_QUERY, Some("RoverDoctor"), None);
+        let elapsed = started.elapsed().as_millis();
+        match result {
+            result if is_graphos_response(&result) => {
+                Ok(format!("GraphOS responded in {}ms", elapsed))
+            }
+            Err(error) => Err(error.into()),
+            Ok(_) => {
+                let mut err = RoverError::new(anyhow!(
+                    "The GraphOS endpoint responded without a `__typename`, so it may not be a GraphOS API."
+                ));
+                err.set_suggestion(RoverErrorSuggestion::Adhoc("Check the `registry_url` setting and the `APOLLO_REGISTRY_URL` environment variable, which change where Rover sends GraphOS requests.".to_string()));
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Whether GraphOS answered the reachability query, either with a `__typename` or by
+/// rejecting the API key it was sent with
+fn is_graphos_response(result: &Result<Value, RoverClientError>) -> bool {
+    match result {
+        Ok(data) => data.get("__typename").is_some_and(Value::is_string),
+        Err(RoverClientError::MalformedKey | RoverClientError::InvalidKey) => true,
+        Err(RoverClientError::ClientError { msg }) => {
+            [StatusCode::UNAUTHORIZED, StatusCode::FORBIDDEN]
+                .iter()
+                .any(|status| *msg == status.to_string())
+        }
+        Err(RoverClientError::GraphQl { errors, .. }) => errors.iter().any(|error| {
+            let code = error
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .and_then(Value::as_str);
+            matches!(code, Some("UNAUTHENTICATED" | "FORBIDDEN"))
+        }),
+        Err(_) => false,
+    }
+}
+
+fn check_config_home(client_config: &StudioClientConfig) -> RoverResult<String> {
+    let home = &client_config.config.home;
+    if !home.is_dir() {
+        let mut err = RoverError::new(anyhow!("The config directory {} doesn't exist", home));
+        err.set_suggestion(RoverErrorSuggestion::SetConfigHome);
+        return Err(err);
+    }
+    // written with `std::fs` rather than `Fs`, which would create the directory if it were removed
+    let probe = home.join(".rover-doctor");
+    std::fs::write(&probe, "").map_err(|e| {
+        let mut err = RoverError::new(anyhow!("Could not write to {}: {}", home, e));
+        err.set_suggestion(RoverErrorSuggestion::SetConfigHome);
+        err
+    })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(format!("{} can be written to", home))
+}
+
+fn check_elv2_license(client_config: &StudioClientConfig) -> RoverResult<String> {
+    let accepted_by_env = std::env::var("APOLLO_ELV2_LICENSE")
+        .map(|value| value.eq_ignore_ascii_case("accept"))
+        .unwrap_or(false);
+    if accepted_by_env {
+        Ok("Accepted with APOLLO_ELV2_LICENSE".to_string())
+    } else if client_config.config.did_accept_elv2_license() {
+        Ok("Accepted on this machine".to_string())
+    } else {
+        let mut err = RoverError::new(anyhow!(
+            "The ELv2 license hasn't been accepted, so `rover dev` and composition with Federation 2 will prompt for it."
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc("Set `APOLLO_ELV2_LICENSE=accept` as an environment variable, or pass `--elv2-license=accept` to a command that needs it.".to_string()));
+        Err(err)
+    }
+}
+
+fn check_plugins() -> RoverResult<String> {
+    let plugins = [
+        Plugin::Supergraph(FederationVersion::LatestFedTwo),
+        Plugin::Router(RouterVersion::Latest),
+    ];
+    let target_archs = plugins
+        .iter()
+        .map(|plugin| {
+            Ok(format!(
+                "{} ({})",
+                plugin.get_name(),
+                plugin.get_target_arch()?
+            ))
+        })
+        .collect::<RoverResult<Vec<_>>>()?;
+    Ok(format!(
+        "Published for this machine: {}",
+        target_archs.join(", ")
+    ))
+}
+
+fn check_dev_socket() -> RoverResult<String> {
+    let raw_socket_name = raw_socket_name(DEFAULT_ROUTER_SOCKET_ADDR);
+    let in_use = raw_socket_name
+        .as_str()
+        .to_fs_name::<GenericFilePath>()
+        .and_then(Stream::connect)
+        .is_ok();
+    if in_use {
+        let mut err = RoverError::new(anyhow!(
+            "A `rover dev` session is already listening on {}.",
+            raw_socket_name
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc("Running `rover dev` again adds a subgraph to that session. To start a separate supergraph, pass a different `--supergraph-port`.".to_string()));
+        Err(err)
+    } else {
+        Ok(format!("{} is available", raw_socket_name))
+    }
+}
+
+fn check_router_port() -> RoverResult<String> {
+    match TcpListener::bind(DEFAULT_ROUTER_SOCKET_ADDR) {
+        Ok(_) => Ok(format!("{} is available", DEFAULT_ROUTER_SOCKET_ADDR)),
+        Err(e) => {
+            let mut err = RoverError::new(anyhow!(
+                "The router can't listen on {}: {}",
+                DEFAULT_ROUTER_SOCKET_ADDR,
+                e
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Pass a different `--supergraph-port` to `rover dev`, or shut down the process bound to '{}'.",
+                DEFAULT_ROUTER_SOCKET_ADDR
+            )));
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+    use serde_json::json;
+
+    use rover_client::blocking::RetryPolicy;
+
+    use crate::utils::client::ClientBuilder;
+
+    use super::*;
+
+    #[test]
+    fn it_only_counts_graphos_responses_as_reachable() {
+        assert!(is_graphos_response(&Ok(json!({ "__typename": "Query" }))));
+        assert!(is_graphos_response(&Err(RoverClientError::InvalidKey)));
+        assert!(is_graphos_response(&Err(RoverClientError::ClientError {
+            msg: StatusCode::UNAUTHORIZED.to_string(),
+        })));
+        assert!(!is_graphos_response(&Ok(json!({}))));
+        assert!(!is_graphos_response(&Err(RoverClientError::ClientError {
+            msg: StatusCode::BAD_GATEWAY.to_string(),
+        })));
+        assert!(!is_graphos_response(&Err(
+            RoverClientError::MalformedResponse {
+                null_field: "data".to_string(),
+            }
+        )));
+    }
+
+    #[test]
+    fn it_does_not_create_a_missing_config_home() {
+        let home = tempfile::tempdir().unwrap();
+        let home = Utf8PathBuf::try_from(home.path().to_path_buf()).unwrap();
+        let config = houston::Config::new(Some(&home), None).unwrap();
+        let client_config = StudioClientConfig::new(
+            None,
+            config,
+            false,
+            ClientBuilder::new(),
+            RetryPolicy::default(),
+        );
+        assert!(check_config_home(&client_config).is_ok());
+
+        std::fs::remove_dir_all(&home).unwrap();
+        assert!(check_config_home(&client_config).is_err());
+        assert!(!home.exists());
+    }
+
+    #[test]
+    fn it_reports_the_router_port_in_use() {
+        let Ok(_listener) = TcpListener::bind(DEFAULT_ROUTER_SOCKET_ADDR) else {
+            // something else has the port already, which is also what this checks for
+            assert!(check_router_port().is_err());
+            return;
+        };
+        let check = DoctorCheck::warn_from_result("Router port", check_router_port());
+        assert_eq!(check.status, DoctorStatus::Warn);
+        assert!(check.details.contains("127.0.0.1:4000"));
+        assert_eq!(check.suggestions.len(), 1);
+    }
+}
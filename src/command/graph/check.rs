@@ -36,17 +36,18 @@ impl Check {
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         let proposed_schema = self
             .schema
             .read_file_descriptor("SDL", &mut std::io::stdin())?;
 
         eprintln!(
             "Checking the proposed schema against {}",
-            Style::Link.paint(self.graph.graph_ref.to_string())
+            Style::Link.paint(graph_ref.to_string())
         );
         let workflow_res = check::run(
             CheckSchemaAsyncInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 proposed_schema,
                 git_context,
                 config: CheckConfig {
@@ -62,7 +63,7 @@ impl Check {
         } else {
             let check_res = check_workflow::run(
                 CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
+                    graph_ref: graph_ref.clone(),
                     workflow_id: workflow_res.workflow_id,
                     checks_timeout_seconds,
                 },
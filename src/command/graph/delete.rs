@@ -25,11 +25,11 @@ pub struct Delete {
 impl Delete {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
-        let graph_ref = self.graph.graph_ref.to_string();
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
 
         eprintln!(
             "Deleting {} using credentials from the {} profile.",
-            Style::Link.paint(&graph_ref),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
@@ -40,12 +40,15 @@ impl Delete {
 
         delete::run(
             GraphDeleteInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
             },
             &client,
         )?;
 
-        eprintln!("Successfully deleted {}.", Style::Link.paint(&graph_ref));
+        eprintln!(
+            "Successfully deleted {}.",
+            Style::Link.paint(graph_ref.to_string())
+        );
         Ok(RoverOutput::EmptySuccess)
     }
 }
@@ -20,16 +20,16 @@ pub struct Fetch {
 impl Fetch {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
-        let graph_ref = self.graph.graph_ref.to_string();
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         eprintln!(
             "Fetching SDL from {} using credentials from the {} profile.",
-            Style::Link.paint(graph_ref),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
         let fetch_response = fetch::run(
             GraphFetchInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
             },
             &client,
         )?;
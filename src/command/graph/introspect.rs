@@ -1,22 +1,32 @@
+use anyhow::anyhow;
 use clap::Parser;
 use reqwest::blocking::Client;
 use serde::Serialize;
-use std::{collections::HashMap, time::Duration};
+use std::time::Duration;
 
 use rover_client::{
-    blocking::GraphQLClient,
+    blocking::{GraphQLClient, RetryPolicy},
     operations::graph::introspect::{self, GraphIntrospectInput},
 };
 
 use crate::{
-    options::{IntrospectOpts, OutputOpts},
-    RoverOutput, RoverResult,
+    cli::RoverOutputFormatKind,
+    options::{IntrospectOpts, IntrospectionOutputFormat, IntrospectionQueryOpts, OutputOpts},
+    utils::auth_provider::{send_with_auth, AuthProvider},
+    RoverError, RoverErrorSuggestion, RoverOutput, RoverResult,
 };
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Introspect {
     #[clap(flatten)]
     pub opts: IntrospectOpts,
+
+    #[clap(flatten)]
+    pub query: IntrospectionQueryOpts,
+
+    /// Print the schema as SDL, or as the raw `__schema` JSON the endpoint returned
+    #[arg(long, value_enum, default_value_t, conflicts_with = "diff")]
+    pub output_format: IntrospectionOutputFormat,
 }
 
 impl Introspect {
@@ -24,12 +34,28 @@ impl Introspect {
         &self,
         client: Client,
         output_opts: &OutputOpts,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
     ) -> RoverResult<RoverOutput> {
+        // the raw introspection result is already JSON, so wrapping it in Rover's
+        // JSON output would only encode it a second time as a string
+        if self.output_format == IntrospectionOutputFormat::IntrospectionJson
+            && output_opts.format_kind() == RoverOutputFormatKind::Json
+        {
+            let mut err = RoverError::new(anyhow!(
+                "`--output-format introspection-json` can't be combined with `--format json`."
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Drop `--format json` to print the introspection result as JSON.".to_string(),
+            ));
+            return Err(err);
+        }
+
+        let auth = self.opts.auth.provider(&client);
         if self.opts.watch {
-            self.exec_and_watch(&client, output_opts, retry_period)
+            self.exec_and_watch(&client, output_opts, retry_policy, timeout, auth.as_ref())
         } else {
-            let sdl = self.exec(&client, true, retry_period)?;
+            let sdl = self.exec(&client, true, retry_policy, timeout, auth.as_ref())?;
             Ok(RoverOutput::Introspection(sdl))
         }
     }
@@ -38,28 +64,45 @@ impl Introspect {
         &self,
         client: &Client,
         should_retry: bool,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
+        auth: Option<&AuthProvider>,
     ) -> RoverResult<String> {
-        let client = GraphQLClient::new(self.opts.endpoint.as_ref(), client.clone(), retry_period);
+        let client = GraphQLClient::new(
+            self.opts.endpoint.as_ref(),
+            client.clone(),
+            retry_policy.clone(),
+        )
+        .with_transport(self.opts.transport)
+        .with_timeout(timeout);
 
-        // add the flag headers to a hashmap to pass along to rover-client
-        let mut headers = HashMap::new();
-        if let Some(arg_headers) = &self.opts.headers {
-            for (header_key, header_value) in arg_headers {
-                headers.insert(header_key.to_string(), header_value.to_string());
+        send_with_auth(auth, self.opts.header_map(), |headers| {
+            let input = GraphIntrospectInput {
+                headers,
+                options: (&self.query).into(),
+            };
+            match self.output_format {
+                IntrospectionOutputFormat::Sdl => introspect::run(input, &client, should_retry)
+                    .map(|response| response.schema_sdl),
+                IntrospectionOutputFormat::IntrospectionJson => {
+                    let introspection = introspect::run_json(input, &client, should_retry)?;
+                    Ok(serde_json::to_string_pretty(&introspection)?)
+                }
             }
-        };
-
-        Ok(introspect::run(GraphIntrospectInput { headers }, &client, should_retry)?.schema_sdl)
+        })
     }
 
     pub fn exec_and_watch(
         &self,
         client: &Client,
         output_opts: &OutputOpts,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
+        auth: Option<&AuthProvider>,
     ) -> ! {
-        self.opts
-            .exec_and_watch(|| self.exec(client, false, retry_period), output_opts)
+        self.opts.exec_and_watch(
+            || self.exec(client, false, retry_policy, timeout, auth),
+            output_opts,
+        )
     }
 }
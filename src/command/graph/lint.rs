@@ -1,16 +1,23 @@
 use clap::Parser;
 use rover_client::operations::graph::lint::{self, LintGraphInput};
+use rover_client::shared::{GraphRef, LintResponse};
+use rover_client::RoverClientError;
 use serde::Serialize;
 
-use crate::options::{GraphRefOpt, LintOpts, ProfileOpt, SchemaOpt};
+use crate::options::{LintOpts, ProfileOpt, SchemaOpt};
 
 use crate::utils::client::StudioClientConfig;
+use crate::utils::lint::{self as local_lint, LintConfig};
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
 pub struct Lint {
-    #[clap(flatten)]
-    graph: GraphRefOpt,
+    /// <NAME>@<VARIANT> of graph in Apollo Studio.
+    /// @<VARIANT> may be left off, defaulting to @current.
+    /// Not required when linting with `--offline`.
+    #[arg(value_name = "GRAPH_REF", required_unless_present = "offline")]
+    #[serde(skip_serializing)]
+    graph_ref: Option<GraphRef>,
 
     #[clap(flatten)]
     profile: ProfileOpt,
@@ -25,22 +32,61 @@ pub struct Lint {
 
 impl Lint {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
-        let client = client_config.get_authenticated_client(&self.profile)?;
-
         let file_with_metadata = self
             .schema
             .read_file_descriptor_with_metadata("SDL", &mut std::io::stdin())?;
 
-        let lint_result: rover_client::shared::LintResponse = lint::run(
-            LintGraphInput {
-                graph_ref: self.graph.graph_ref.clone(),
-                file_name: file_with_metadata.file_path,
-                proposed_schema: file_with_metadata.schema,
-                ignore_existing: self.lint.ignore_existing_lint_violations,
-            },
-            &client,
+        let mut lint_response = self.lint_schema(
+            &client_config,
+            file_with_metadata.file_path,
+            file_with_metadata.schema,
         )?;
 
-        Ok(RoverOutput::LintResponse(lint_result))
+        if self.lint.fix {
+            let lint_fix = local_lint::fix(&lint_response)?;
+            if self.lint.dry_run {
+                return Ok(RoverOutput::LintFix(lint_fix));
+            }
+            if !lint_fix.is_empty() {
+                lint_fix.write(self.schema.file_path())?;
+                // report whatever violations are left after fixing
+                lint_response =
+                    self.lint_schema(&client_config, lint_fix.file_name, lint_fix.fixed_schema)?;
+            }
+        }
+
+        Ok(RoverOutput::LintResponse(self.lint.finish(lint_response)?))
+    }
+
+    fn lint_schema(
+        &self,
+        client_config: &StudioClientConfig,
+        file_name: String,
+        proposed_schema: String,
+    ) -> RoverResult<LintResponse> {
+        match &self.graph_ref {
+            Some(graph_ref) if !self.lint.offline => {
+                let client = client_config.get_authenticated_client(&self.profile)?;
+                match lint::run(
+                    LintGraphInput {
+                        graph_ref: graph_ref.clone(),
+                        file_name,
+                        proposed_schema,
+                        ignore_existing: self.lint.ignore_existing_lint_violations,
+                    },
+                    &client,
+                ) {
+                    Ok(lint_response) => Ok(lint_response),
+                    // the baseline may still excuse the violations GraphOS failed on
+                    Err(RoverClientError::LintFailures { lint_response }) => Ok(lint_response),
+                    Err(e) => Err(e.into()),
+                }
+            }
+            _ => local_lint::lint(
+                &LintConfig::load(self.lint.lint_config.as_deref())?,
+                file_name,
+                proposed_schema,
+            ),
+        }
     }
 }
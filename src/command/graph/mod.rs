@@ -59,11 +59,15 @@ impl Graph {
             Command::Fetch(command) => command.run(client_config),
             Command::Lint(command) => command.run(client_config),
             Command::Publish(command) => command.run(client_config, git_context),
-            Command::Introspect(command) => command.run(
-                client_config.get_reqwest_client()?,
-                output_opts,
-                client_config.retry_period,
-            ),
+            Command::Introspect(command) => {
+                client_config.check_transport(command.opts.transport)?;
+                command.run(
+                    client_config.get_reqwest_client()?,
+                    output_opts,
+                    &client_config.retry_policy,
+                    client_config.client_timeout(),
+                )
+            }
         }
     }
 }
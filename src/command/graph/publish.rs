@@ -29,10 +29,10 @@ impl Publish {
         git_context: GitContext,
     ) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
-        let graph_ref = self.graph.graph_ref.to_string();
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         eprintln!(
             "Publishing SDL to {} using credentials from the {} profile.",
-            Style::Link.paint(graph_ref),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
@@ -44,7 +44,7 @@ impl Publish {
 
         let publish_response = publish::run(
             GraphPublishInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 proposed_schema,
                 git_context,
             },
@@ -52,7 +52,7 @@ impl Publish {
         )?;
 
         Ok(RoverOutput::GraphPublishResponse {
-            graph_ref: self.graph.graph_ref.clone(),
+            graph_ref: graph_ref.clone(),
             publish_response,
         })
     }
@@ -349,9 +349,12 @@ impl PluginInstaller {
     }
 
     fn install_latest_major(&self, plugin: &Plugin) -> RoverResult<Option<Utf8PathBuf>> {
-        let latest_version = self
-            .rover_installer
-            .get_plugin_version(&plugin.get_tarball_url()?, true)?;
+        let latest_version = self.rover_installer.get_plugin_version(
+            &plugin.get_tarball_url()?,
+            true,
+            &self.client_config.get_no_redirect_reqwest_client()?,
+            &|request| self.client_config.retry_policy.send(request),
+        )?;
 
         if let Ok(Some(exe)) = self.find_existing_exact(plugin, &latest_version) {
             if !self.force {
@@ -387,6 +390,13 @@ impl PluginInstaller {
 
     fn do_install(&self, plugin: &Plugin, is_latest: bool) -> RoverResult<Option<Utf8PathBuf>> {
         let plugin_name = plugin.get_name();
+        let _span = tracing::info_span!(
+            "plugin_install",
+            otel.name = %format!("install {}", plugin_name),
+            rover.plugin = %plugin_name,
+            rover.plugin.version = %plugin.get_tarball_version(),
+        )
+        .entered();
         let plugin_tarball_url = plugin.get_tarball_url()?;
         // only print the download message if the username and password have been stripped from the URL
         if let Some(sanitized_url) = sanitize_url(&plugin_tarball_url) {
@@ -398,7 +408,9 @@ impl PluginInstaller {
             &plugin_name,
             &plugin_tarball_url,
             &self.client_config.get_reqwest_client()?,
+            &self.client_config.get_no_redirect_reqwest_client()?,
             is_latest,
+            &|request| self.client_config.retry_policy.send(request),
         )?)
     }
 }
@@ -1,7 +1,10 @@
+mod api;
 mod config;
 mod contract;
+mod debug;
 mod dev;
 mod docs;
+mod doctor;
 mod explain;
 mod fed2;
 mod graph;
@@ -11,15 +14,20 @@ mod license;
 pub(crate) mod output;
 mod persisted_queries;
 mod readme;
+mod schema;
 pub(crate) mod subgraph;
 mod supergraph;
+mod telemetry;
 pub(crate) mod template;
 mod update;
 
+pub use api::Api;
 pub use config::Config;
 pub use contract::Contract;
+pub use debug::Debug;
 pub use dev::Dev;
 pub use docs::Docs;
+pub use doctor::{Doctor, DoctorFailures};
 pub use explain::Explain;
 pub use fed2::Fed2;
 pub use graph::Graph;
@@ -29,7 +37,9 @@ pub use license::License;
 pub use output::RoverOutput;
 pub use persisted_queries::PersistedQueries;
 pub use readme::Readme;
+pub use schema::Schema;
 pub use subgraph::Subgraph;
 pub use supergraph::Supergraph;
+pub use telemetry::Telemetry;
 pub use template::Template;
 pub use update::Update;
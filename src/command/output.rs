@@ -17,16 +17,20 @@ use rover_client::operations::subgraph::delete::SubgraphDeleteResponse;
 use rover_client::operations::subgraph::list::SubgraphListResponse;
 use rover_client::operations::subgraph::publish::SubgraphPublishResponse;
 use rover_client::shared::{
-    CheckRequestSuccessResult, CheckWorkflowResponse, FetchResponse, GraphRef, LintResponse,
-    SdlType,
+    BatchCheckWorkflowResponse, CheckRequestSuccessResult, CheckWorkflowResponse, FetchResponse,
+    GraphRef, LintResponse, SdlType,
 };
 use rover_client::RoverClientError;
 use rover_std::Style;
 
+use crate::command::doctor::{DoctorCheck, DoctorStatus};
 use crate::command::supergraph::compose::CompositionOutput;
 use crate::command::template::queries::list_templates_for_language::ListTemplatesForLanguageTemplates;
 use crate::options::JsonVersion;
 use crate::options::ProjectLanguage;
+use crate::utils::lint::LintFix;
+use crate::utils::project_config::Setting;
+use crate::utils::schema_diff::SchemaDiff;
 use crate::utils::table::{self, row};
 use crate::RoverError;
 
@@ -56,8 +60,10 @@ pub enum RoverOutput {
     CompositionResult(CompositionOutput),
     SubgraphList(SubgraphListResponse),
     CheckWorkflowResponse(CheckWorkflowResponse),
+    BatchCheckWorkflowResponse(BatchCheckWorkflowResponse),
     AsyncCheckResponse(CheckRequestSuccessResult),
     LintResponse(LintResponse),
+    LintFix(LintFix),
     GraphPublishResponse {
         graph_ref: GraphRef,
         publish_response: GraphPublishResponse,
@@ -79,7 +85,15 @@ pub enum RoverOutput {
         path: Utf8PathBuf,
     },
     Profiles(Vec<String>),
+    ConfigExport(String),
+    ConfigSettings(Vec<Setting>),
+    ProfileSetting {
+        profile_name: String,
+        key: String,
+        value: Option<String>,
+    },
     Introspection(String),
+    IntrospectionDiff(SchemaDiff),
     ErrorExplanation(String),
     ReadmeFetchResponse {
         graph_ref: GraphRef,
@@ -96,6 +110,18 @@ pub enum RoverOutput {
         graph_id: String,
         jwt: String,
     },
+    ApiQueryResponse {
+        data: Value,
+    },
+    TelemetryStatus {
+        enabled: bool,
+        endpoint: String,
+        spool_path: Utf8PathBuf,
+    },
+    TelemetryReports(Vec<Value>),
+    TelemetryFields(&'static [(&'static str, &'static str)]),
+    DebugBundle(Utf8PathBuf),
+    DoctorReport(Vec<DoctorCheck>),
     EmptySuccess,
 }
 
@@ -351,20 +377,54 @@ impl RoverOutput {
                 forum_call_to_action))
             }
             RoverOutput::CheckWorkflowResponse(check_response) => Some(check_response.get_output()),
+            RoverOutput::BatchCheckWorkflowResponse(batch_response) => {
+                Some(batch_response.get_output())
+            }
             RoverOutput::AsyncCheckResponse(check_response) => Some(format!(
                 "Check successfully started with workflow ID: {}\nView full details at {}",
                 check_response.workflow_id, check_response.target_url
             )),
             RoverOutput::LintResponse(lint_response) => Some(lint_response.get_ariadne()?),
+            RoverOutput::LintFix(lint_fix) => Some(lint_fix.get_stdout()),
             RoverOutput::Profiles(profiles) => {
                 if profiles.is_empty() {
                     stderrln!("No profiles found.")?;
                 }
                 Some(profiles.join("\n"))
             }
+            RoverOutput::ConfigExport(export) => Some(export.clone()),
+            RoverOutput::ConfigSettings(settings) => {
+                let mut table = table::get_table();
+
+                // bc => sets top row to be bold and center
+                table.add_row(row![bc => "Setting", "Value", "Source"]);
+                for setting in settings {
+                    table.add_row(row![
+                        setting.key,
+                        setting.value.as_deref().unwrap_or("(not set)"),
+                        setting
+                            .source
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default()
+                    ]);
+                }
+                Some(format!("{}", table))
+            }
+            RoverOutput::ProfileSetting {
+                profile_name,
+                key,
+                value,
+            } => {
+                if value.is_none() {
+                    stderrln!("{} is not set for profile \"{}\".", key, profile_name)?;
+                }
+                value.clone()
+            }
             RoverOutput::Introspection(introspection_response) => {
                 Some((introspection_response).to_string())
             }
+            RoverOutput::IntrospectionDiff(diff) => Some(diff.get_stdout()),
             RoverOutput::ErrorExplanation(explanation) => {
                 // underline bolded md
                 let mut skin = MadSkin::default();
@@ -442,6 +502,84 @@ impl RoverOutput {
                 stderrln!("Success!")?;
                 Some(jwt.to_string())
             }
+            RoverOutput::ApiQueryResponse { data } => Some(serde_json::to_string_pretty(data)?),
+            RoverOutput::TelemetryStatus {
+                enabled,
+                endpoint,
+                spool_path,
+            } => {
+                if *enabled {
+                    stderrln!("Anonymous usage data is sent to {}.", endpoint)?;
+                } else {
+                    stderrln!("Anonymous usage data is not reported.")?;
+                }
+                stderrln!(
+                    "A copy of the usage data for recent commands is kept in {}. Run {} to see it.",
+                    spool_path,
+                    Style::Command.paint("`rover telemetry show`")
+                )?;
+                None
+            }
+            RoverOutput::TelemetryReports(reports) => {
+                if reports.is_empty() {
+                    stderrln!("No usage data has been kept yet.")?;
+                    None
+                } else {
+                    let reports = reports
+                        .iter()
+                        .map(serde_json::to_string_pretty)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Some(reports.join("\n"))
+                }
+            }
+            RoverOutput::TelemetryFields(fields) => {
+                let mut table = table::get_table();
+
+                // bc => sets top row to be bold and center
+                table.add_row(row![bc => "Field", "Description"]);
+                for (field, description) in fields.iter() {
+                    table.add_row(row![field, description]);
+                }
+                Some(format!("{}", table))
+            }
+            RoverOutput::DoctorReport(checks) => {
+                let mut table = table::get_table();
+
+                // bc => sets top row to be bold and center
+                table.add_row(row![bc => "Check", "Result", "Details"]);
+                for check in checks {
+                    table.add_row(row![check.name, check.status, check.details]);
+                }
+                let mut output = format!("{}", table);
+
+                let count = |status| checks.iter().filter(|check| check.status == status).count();
+                for check in checks
+                    .iter()
+                    .filter(|check| check.status != DoctorStatus::Pass)
+                {
+                    for suggestion in &check.suggestions {
+                        output.push_str(&format!(
+                            "\n{}: {}",
+                            Style::Heading.paint(check.name),
+                            suggestion
+                        ));
+                    }
+                }
+                // failures are reported by the error `rover doctor` returns
+                match (count(DoctorStatus::Fail), count(DoctorStatus::Warn)) {
+                    (0, 0) => stderrln!("All checks passed.")?,
+                    (0, warned) => stderrln!("No checks failed, but {} had warnings.", warned)?,
+                    _ => (),
+                }
+                Some(output)
+            }
+            RoverOutput::DebugBundle(path) => {
+                stderrln!(
+                    "Wrote a debug bundle to {}. It has the command that was run, Rover's environment variables, installed plugins, and recent logs. Secrets Rover has used are masked, but check it before sharing it.",
+                    Style::Path.paint(path.as_str())
+                )?;
+                None
+            }
             RoverOutput::EmptySuccess => None,
         })
     }
@@ -515,12 +653,18 @@ impl RoverOutput {
                 json!({ "template_id": template_id, "path": path })
             }
             RoverOutput::CheckWorkflowResponse(check_response) => check_response.get_json(),
+            RoverOutput::BatchCheckWorkflowResponse(batch_response) => batch_response.get_json(),
             RoverOutput::AsyncCheckResponse(check_response) => check_response.get_json(),
             RoverOutput::LintResponse(lint_response) => lint_response.get_json(),
+            RoverOutput::LintFix(lint_fix) => lint_fix.get_json(),
             RoverOutput::Profiles(profiles) => json!({ "profiles": profiles }),
+            RoverOutput::ConfigExport(export) => json!({ "export": export }),
+            RoverOutput::ConfigSettings(settings) => json!({ "settings": settings }),
+            RoverOutput::ProfileSetting { key, value, .. } => json!({ "key": key, "value": value }),
             RoverOutput::Introspection(introspection_response) => {
                 json!({ "introspection_response": introspection_response })
             }
+            RoverOutput::IntrospectionDiff(diff) => diff.get_json(),
             RoverOutput::ErrorExplanation(explanation_markdown) => {
                 json!({ "explanation_markdown": explanation_markdown })
             }
@@ -561,6 +705,23 @@ impl RoverOutput {
             RoverOutput::LicenseResponse { jwt, .. } => {
                 json!({"jwt": jwt })
             }
+            RoverOutput::ApiQueryResponse { data } => json!({ "data": data }),
+            RoverOutput::TelemetryStatus {
+                enabled,
+                endpoint,
+                spool_path,
+            } => {
+                json!({ "enabled": enabled, "endpoint": endpoint, "spool_path": spool_path })
+            }
+            RoverOutput::TelemetryReports(reports) => json!({ "reports": reports }),
+            RoverOutput::TelemetryFields(fields) => json!({
+                "fields": fields
+                    .iter()
+                    .map(|(field, description)| json!({ "field": field, "description": description }))
+                    .collect::<Vec<_>>()
+            }),
+            RoverOutput::DebugBundle(path) => json!({ "path": path }),
+            RoverOutput::DoctorReport(checks) => json!({ "checks": checks }),
         }
     }
 
@@ -604,7 +765,9 @@ impl RoverOutput {
 
     pub(crate) fn get_json_version(&self) -> JsonVersion {
         match &self {
-            Self::CheckWorkflowResponse(_) => JsonVersion::Two,
+            Self::CheckWorkflowResponse(_) | Self::BatchCheckWorkflowResponse(_) => {
+                JsonVersion::Two
+            }
             _ => JsonVersion::default(),
         }
     }
@@ -640,6 +803,7 @@ impl RoverOutput {
             RoverOutput::AsyncCheckResponse(_) => Some("Check Started"),
             RoverOutput::Profiles(_) => Some("Profiles"),
             RoverOutput::Introspection(_) => Some("Introspection Response"),
+            RoverOutput::IntrospectionDiff(_) => Some("Introspection Diff"),
             RoverOutput::ReadmeFetchResponse { .. } => Some("Readme"),
             RoverOutput::GraphPublishResponse { .. } => Some("Schema Hash"),
             _ => None,
@@ -871,6 +1035,8 @@ mod tests {
             "error": {
                 "message": "Encountered 2 build errors while trying to build subgraph \"subgraph\" into supergraph \"name@current\".",
                 "code": "E029",
+                "graph_ref": "name@current",
+                "subgraph": "subgraph",
                 "details": {
                     "build_errors": [
                         {
@@ -942,7 +1108,8 @@ mod tests {
                         }
                     ]
                 },
-                "code": "E027"
+                "code": "E027",
+                "graph_ref": "name@current",
             }
         });
         assert_json_eq!(actual_json, expected_json);
@@ -1205,6 +1372,7 @@ mod tests {
             "error": {
                 "message": "The changes in the schema you proposed caused operation, linter and proposal checks to fail.",
                 "code": "E043",
+                "graph_ref": "name@current",
             }
         });
         assert_json_eq!(expected_json, actual_json);
@@ -1346,6 +1514,8 @@ mod tests {
             "error": {
                 "message": "Encountered 2 build errors while trying to build subgraph \"subgraph\" into supergraph \"name@current\".",
                 "code": "E029",
+                "graph_ref": "name@current",
+                "subgraph": "subgraph",
                 "details": {
                     "build_errors": [
                         {
@@ -1510,12 +1680,42 @@ mod tests {
             },
             "error": {
                 "message": "Could not find subgraph \"invalid_subgraph\".",
-                "code": "E009"
+                "code": "E009",
+                "subgraph": "invalid_subgraph",
+                "valid_subgraphs": [],
             }
         });
         assert_json_eq!(expected_json, actual_json)
     }
 
+    #[test]
+    fn graphql_error_message_json() {
+        let errors = serde_json::from_value(json!([
+            {
+                "message": "Not allowed",
+                "path": ["graph"],
+                "extensions": { "code": "FORBIDDEN" }
+            }
+        ]))
+        .unwrap();
+        let actual_json: JsonOutput = RoverError::new(RoverClientError::GraphQl {
+            msg: "Not allowed".to_string(),
+            errors,
+        })
+        .into();
+        let actual_json = serde_json::to_value(actual_json).unwrap();
+        assert_eq!(actual_json["error"]["code"], "E045");
+        assert_eq!(actual_json["error"]["message"], "Not allowed");
+        assert_eq!(
+            actual_json["error"]["graphql_errors"][0]["extensions"]["code"],
+            "FORBIDDEN"
+        );
+        assert_eq!(
+            actual_json["error"]["graphql_errors"][0]["path"],
+            json!(["graph"])
+        );
+    }
+
     #[test]
     fn composition_error_message_json() {
         let source = BuildErrors::from(vec![
@@ -0,0 +1,28 @@
+use anyhow::Context;
+use clap::Parser;
+use rover_client::operations::graph::introspect;
+use serde::Serialize;
+
+use crate::utils::parsers::FileDescriptorType;
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct FromIntrospection {
+    /// The introspection result to convert, either an entire GraphQL response or just
+    /// its `data`. You can pass `-` to use stdin instead of a file.
+    #[arg(value_name = "FILE")]
+    #[serde(skip_serializing)]
+    introspection: FileDescriptorType,
+}
+
+impl FromIntrospection {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        let contents = self
+            .introspection
+            .read_file_descriptor("introspection result", &mut std::io::stdin())?;
+        let introspection = serde_json::from_str(&contents)
+            .context("The introspection result is not valid JSON")?;
+        let sdl = introspect::from_json(introspection)?.schema_sdl;
+        Ok(RoverOutput::Introspection(sdl))
+    }
+}
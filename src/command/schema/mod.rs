@@ -0,0 +1,28 @@
+mod from_introspection;
+
+pub use from_introspection::FromIntrospection;
+
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Schema {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Convert a saved introspection result to SDL
+    FromIntrospection(FromIntrospection),
+}
+
+impl Schema {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::FromIntrospection(command) => command.run(),
+        }
+    }
+}
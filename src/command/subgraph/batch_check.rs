@@ -0,0 +1,358 @@
+use std::collections::{HashMap, HashSet};
+use std::thread;
+
+use anyhow::anyhow;
+use clap::{Args, Parser};
+use rover_client::blocking::StudioClient;
+use rover_client::operations::subgraph::check::{self, SubgraphCheckAsyncInput};
+use rover_client::operations::subgraph::check_workflow::{self, CheckWorkflowInput};
+use rover_client::operations::subgraph::fetch_all::{self, SubgraphFetchAllInput};
+use rover_client::shared::{
+    BatchCheckWorkflowResponse, CheckConfig, GitContext, GraphRef, SubgraphCheckOutcome,
+    SubgraphCheckResult,
+};
+use rover_client::RoverClientError;
+use rover_std::Style;
+use serde::Serialize;
+
+use crate::options::{CheckConfigOpts, GraphRefOpt, ProfileOpt};
+use crate::utils::client::StudioClientConfig;
+use crate::utils::parsers::{parse_subgraph_schema, FileDescriptorType};
+#[cfg(feature = "composition-js")]
+use crate::utils::supergraph_config::resolve_supergraph_yaml;
+use crate::{RoverError, RoverErrorSuggestion, RoverOutput, RoverResult};
+
+/// Subgraph names paired with their proposed SDL
+type ProposedSchemas = Vec<(String, String)>;
+
+#[derive(Debug, Serialize, Parser)]
+pub struct BatchCheck {
+    #[clap(flatten)]
+    graph: GraphRefOpt,
+
+    #[clap(flatten)]
+    profile: ProfileOpt,
+
+    #[clap(flatten)]
+    #[serde(skip_serializing)]
+    schemas: BatchCheckSchemaSource,
+
+    /// Check every subgraph, including subgraphs whose proposed schema is identical
+    /// to the schema currently published to the graph registry
+    #[arg(long = "all")]
+    check_all: bool,
+
+    #[clap(flatten)]
+    config: CheckConfigOpts,
+}
+
+#[derive(Args, Debug, Serialize)]
+#[group(required = true)]
+pub struct BatchCheckSchemaSource {
+    /// The relative path to a supergraph configuration file containing the subgraphs to check.
+    /// You can pass `-` to use stdin instead of a file.
+    #[serde(skip_serializing)]
+    #[arg(long = "config", conflicts_with = "subgraph_schemas")]
+    supergraph_yaml: Option<FileDescriptorType>,
+
+    /// A subgraph to check, as a name=path pair (e.g. products=./products.graphql).
+    /// Can be passed multiple times.
+    #[serde(skip_serializing)]
+    #[arg(
+        long = "schema",
+        value_name = "NAME=PATH",
+        value_parser = parse_subgraph_schema,
+        conflicts_with = "supergraph_yaml"
+    )]
+    subgraph_schemas: Option<Vec<(String, FileDescriptorType)>>,
+}
+
+impl BatchCheck {
+    pub fn run(
+        &self,
+        client_config: StudioClientConfig,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> RoverResult<RoverOutput> {
+        let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
+
+        let proposed_schemas = self.read_proposed_schemas(client_config)?;
+        let (proposed_schemas, skipped_subgraphs) = if self.check_all {
+            (proposed_schemas, Vec::new())
+        } else {
+            self.filter_unchanged(proposed_schemas, &graph_ref, &client)?
+        };
+
+        eprintln!(
+            "Checking the proposed schemas for {} subgraph(s) against {}",
+            proposed_schemas.len(),
+            Style::Link.paint(graph_ref.to_string())
+        );
+
+        // each check is submitted and polled on its own thread so the total
+        // time spent waiting is that of the slowest check rather than the sum
+        let mut results: Vec<SubgraphCheckResult> = thread::scope(|scope| {
+            let handles: Vec<_> = proposed_schemas
+                .into_iter()
+                .map(|(subgraph, proposed_schema)| {
+                    let client = &client;
+                    let graph_ref = &graph_ref;
+                    let git_context = git_context.clone();
+                    let name = subgraph.clone();
+                    let handle = scope.spawn(move || {
+                        self.check_subgraph(
+                            client,
+                            graph_ref,
+                            &name,
+                            proposed_schema,
+                            git_context,
+                            checks_timeout_seconds,
+                        )
+                    });
+                    (subgraph, handle)
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|(subgraph, handle)| {
+                    // a panic in one check shouldn't discard the results of the others
+                    let outcome = handle
+                        .join()
+                        .unwrap_or_else(|_| SubgraphCheckOutcome::Errored {
+                            message: "the check for this subgraph stopped unexpectedly".to_string(),
+                        });
+                    SubgraphCheckResult { subgraph, outcome }
+                })
+                .collect()
+        });
+        results.sort_by(|a, b| a.subgraph.cmp(&b.subgraph));
+
+        let batch_response = BatchCheckWorkflowResponse {
+            results,
+            skipped_subgraphs,
+        };
+
+        if batch_response.has_failures() {
+            Err(RoverClientError::BatchCheckWorkflowFailure {
+                graph_ref,
+                batch_response: Box::new(batch_response),
+            }
+            .into())
+        } else {
+            Ok(RoverOutput::BatchCheckWorkflowResponse(batch_response))
+        }
+    }
+
+    /// Reads the proposed SDL for every subgraph, keyed by subgraph name
+    fn read_proposed_schemas(
+        &self,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<ProposedSchemas> {
+        if let Some(supergraph_yaml) = &self.schemas.supergraph_yaml {
+            self.read_supergraph_yaml(supergraph_yaml, client_config)
+        } else {
+            let subgraph_schemas = self.schemas.subgraph_schemas.as_deref().unwrap_or_default();
+            validate_subgraph_schemas(subgraph_schemas)?;
+            let mut proposed_schemas = Vec::new();
+            for (subgraph, schema) in subgraph_schemas {
+                let sdl = schema.read_file_descriptor(
+                    &format!("SDL for subgraph '{}'", subgraph),
+                    &mut std::io::stdin(),
+                )?;
+                proposed_schemas.push((subgraph.clone(), sdl));
+            }
+            Ok(proposed_schemas)
+        }
+    }
+
+    #[cfg(feature = "composition-js")]
+    fn read_supergraph_yaml(
+        &self,
+        supergraph_yaml: &FileDescriptorType,
+        client_config: StudioClientConfig,
+    ) -> RoverResult<ProposedSchemas> {
+        let supergraph_config =
+            resolve_supergraph_yaml(supergraph_yaml, client_config, &self.profile)?;
+        Ok(supergraph_config
+            .get_subgraph_definitions()?
+            .into_iter()
+            .map(|definition| (definition.name, definition.sdl))
+            .collect())
+    }
+
+    #[cfg(not(feature = "composition-js"))]
+    fn read_supergraph_yaml(
+        &self,
+        _supergraph_yaml: &FileDescriptorType,
+        _client_config: StudioClientConfig,
+    ) -> RoverResult<ProposedSchemas> {
+        let mut err = RoverError::new(anyhow!(
+            "This version of Rover does not support reading subgraphs from a supergraph config."
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Pass each subgraph with `--schema <NAME=PATH>` instead.".to_string(),
+        ));
+        Err(err)
+    }
+
+    /// Splits the proposed schemas into those that differ from the schemas published
+    /// to the graph registry, and the names of those that don't
+    fn filter_unchanged(
+        &self,
+        proposed_schemas: ProposedSchemas,
+        graph_ref: &GraphRef,
+        client: &StudioClient,
+    ) -> RoverResult<(ProposedSchemas, Vec<String>)> {
+        let published_schemas: HashMap<String, String> = fetch_all::run(
+            SubgraphFetchAllInput {
+                graph_ref: graph_ref.clone(),
+            },
+            client,
+        )?
+        .into_iter()
+        .map(|subgraph| (subgraph.name().clone(), subgraph.sdl().clone()))
+        .collect();
+
+        let is_unchanged = |(subgraph, proposed_schema): &(String, String)| {
+            published_schemas
+                .get(subgraph)
+                .is_some_and(|published_schema| published_schema.trim() == proposed_schema.trim())
+        };
+        let (unchanged, changed): (Vec<_>, Vec<_>) =
+            proposed_schemas.into_iter().partition(is_unchanged);
+
+        let mut skipped_subgraphs: Vec<String> = unchanged
+            .into_iter()
+            .map(|(subgraph, _)| subgraph)
+            .collect();
+        skipped_subgraphs.sort();
+        Ok((changed, skipped_subgraphs))
+    }
+
+    fn check_subgraph(
+        &self,
+        client: &StudioClient,
+        graph_ref: &GraphRef,
+        subgraph: &str,
+        proposed_schema: String,
+        git_context: GitContext,
+        checks_timeout_seconds: u64,
+    ) -> SubgraphCheckOutcome {
+        let workflow_res = match check::run(
+            SubgraphCheckAsyncInput {
+                graph_ref: graph_ref.clone(),
+                subgraph: subgraph.to_string(),
+                git_context,
+                proposed_schema,
+                config: CheckConfig {
+                    query_count_threshold: self.config.query_count_threshold,
+                    query_count_threshold_percentage: self.config.query_percentage_threshold,
+                    validation_period: self.config.validation_period.clone(),
+                },
+            },
+            client,
+        ) {
+            Ok(workflow_res) => workflow_res,
+            Err(e) => {
+                return SubgraphCheckOutcome::Errored {
+                    message: e.to_string(),
+                }
+            }
+        };
+
+        if self.config.background {
+            return SubgraphCheckOutcome::Started(workflow_res);
+        }
+
+        match check_workflow::run(
+            CheckWorkflowInput {
+                graph_ref: graph_ref.clone(),
+                workflow_id: workflow_res.workflow_id,
+                checks_timeout_seconds,
+            },
+            subgraph.to_string(),
+            client,
+        ) {
+            Ok(check_response) => SubgraphCheckOutcome::Passed(Box::new(check_response)),
+            Err(RoverClientError::CheckWorkflowFailure { check_response, .. }) => {
+                SubgraphCheckOutcome::Failed(check_response)
+            }
+            Err(e) => SubgraphCheckOutcome::Errored {
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+/// Rejects `--schema` values that name the same subgraph twice or read stdin more than once
+fn validate_subgraph_schemas(subgraph_schemas: &[(String, FileDescriptorType)]) -> RoverResult<()> {
+    let mut names = HashSet::new();
+    for (subgraph, _) in subgraph_schemas {
+        if !names.insert(subgraph.as_str()) {
+            let mut err = RoverError::new(anyhow!(
+                "The subgraph '{}' was passed to `--schema` more than once.",
+                subgraph
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Pass each subgraph to `--schema` only once.".to_string(),
+            ));
+            return Err(err);
+        }
+    }
+
+    let stdin_count = subgraph_schemas
+        .iter()
+        .filter(|(_, schema)| matches!(schema, FileDescriptorType::Stdin))
+        .count();
+    if stdin_count > 1 {
+        let mut err = RoverError::new(anyhow!(
+            "Only one `--schema` value can read its SDL from stdin, but {} did.",
+            stdin_count
+        ));
+        err.set_suggestion(RoverErrorSuggestion::Adhoc(
+            "Pass `-` as the path for at most one subgraph, and write the others to files."
+                .to_string(),
+        ));
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8PathBuf;
+
+    use super::*;
+
+    fn schema(name: &str, path: &str) -> (String, FileDescriptorType) {
+        let file = if path == "-" {
+            FileDescriptorType::Stdin
+        } else {
+            FileDescriptorType::File(Utf8PathBuf::from(path))
+        };
+        (name.to_string(), file)
+    }
+
+    #[test]
+    fn it_accepts_distinct_subgraphs() {
+        let schemas = vec![schema("products", "-"), schema("users", "users.graphql")];
+        assert!(validate_subgraph_schemas(&schemas).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_duplicate_subgraph_names() {
+        let schemas = vec![
+            schema("products", "a.graphql"),
+            schema("products", "b.graphql"),
+        ];
+        assert!(validate_subgraph_schemas(&schemas).is_err());
+    }
+
+    #[test]
+    fn it_rejects_reading_stdin_twice() {
+        let schemas = vec![schema("products", "-"), schema("users", "-")];
+        assert!(validate_subgraph_schemas(&schemas).is_err());
+    }
+}
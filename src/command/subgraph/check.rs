@@ -37,6 +37,7 @@ impl Check {
         checks_timeout_seconds: u64,
     ) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
 
         let proposed_schema = self
             .schema
@@ -45,12 +46,12 @@ impl Check {
         eprintln!(
             "Checking the proposed schema for subgraph {} against {}",
             &self.subgraph.subgraph_name,
-            Style::Link.paint(self.graph.graph_ref.to_string())
+            Style::Link.paint(graph_ref.to_string())
         );
 
         let workflow_res = check::run(
             SubgraphCheckAsyncInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 subgraph: self.subgraph.subgraph_name.clone(),
                 git_context,
                 proposed_schema,
@@ -67,7 +68,7 @@ impl Check {
         } else {
             let check_res = check_workflow::run(
                 CheckWorkflowInput {
-                    graph_ref: self.graph.graph_ref.clone(),
+                    graph_ref: graph_ref.clone(),
                     workflow_id: workflow_res.workflow_id,
                     checks_timeout_seconds,
                 },
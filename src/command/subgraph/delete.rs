@@ -29,10 +29,11 @@ pub struct Delete {
 impl Delete {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
         eprintln!(
             "Checking for build errors resulting from deleting subgraph {} from {} using credentials from the {} profile.",
             Style::Link.paint(&self.subgraph.subgraph_name),
-            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Command.paint(&self.profile.profile_name)
         );
 
@@ -43,7 +44,7 @@ impl Delete {
             // run delete with dryRun, so we can preview build errors
             let delete_dry_run_response = delete::run(
                 SubgraphDeleteInput {
-                    graph_ref: self.graph.graph_ref.clone(),
+                    graph_ref: graph_ref.clone(),
                     subgraph: self.subgraph.subgraph_name.clone(),
                     dry_run,
                 },
@@ -51,7 +52,7 @@ impl Delete {
             )?;
 
             RoverOutput::SubgraphDeleteResponse {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 subgraph: self.subgraph.subgraph_name.clone(),
                 dry_run,
                 delete_response: delete_dry_run_response,
@@ -69,7 +70,7 @@ impl Delete {
 
         let delete_response = delete::run(
             SubgraphDeleteInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 subgraph: self.subgraph.subgraph_name.clone(),
                 dry_run,
             },
@@ -77,7 +78,7 @@ impl Delete {
         )?;
 
         Ok(RoverOutput::SubgraphDeleteResponse {
-            graph_ref: self.graph.graph_ref.clone(),
+            graph_ref: graph_ref.clone(),
             subgraph: self.subgraph.subgraph_name.clone(),
             dry_run,
             delete_response,
@@ -1,14 +1,15 @@
 use clap::Parser;
 use reqwest::blocking::Client;
 use serde::Serialize;
-use std::{collections::HashMap, time::Duration};
+use std::time::Duration;
 
 use rover_client::{
-    blocking::GraphQLClient,
+    blocking::{GraphQLClient, RetryPolicy},
     operations::subgraph::introspect::{self, SubgraphIntrospectInput},
 };
 
 use crate::options::{IntrospectOpts, OutputOpts};
+use crate::utils::auth_provider::{send_with_auth, AuthProvider};
 use crate::{RoverOutput, RoverResult};
 
 #[derive(Debug, Serialize, Parser)]
@@ -22,12 +23,14 @@ impl Introspect {
         &self,
         client: Client,
         output_opts: &OutputOpts,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
     ) -> RoverResult<RoverOutput> {
+        let auth = self.opts.auth.provider(&client);
         if self.opts.watch {
-            self.exec_and_watch(&client, output_opts, retry_period)
+            self.exec_and_watch(&client, output_opts, retry_policy, timeout, auth.as_ref())
         } else {
-            let sdl = self.exec(&client, true, retry_period)?;
+            let sdl = self.exec(&client, true, retry_policy, timeout, auth.as_ref())?;
             Ok(RoverOutput::Introspection(sdl))
         }
     }
@@ -36,28 +39,35 @@ impl Introspect {
         &self,
         client: &Client,
         should_retry: bool,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
+        auth: Option<&AuthProvider>,
     ) -> RoverResult<String> {
-        let client = GraphQLClient::new(self.opts.endpoint.as_ref(), client.clone(), retry_period);
+        let client = GraphQLClient::new(
+            self.opts.endpoint.as_ref(),
+            client.clone(),
+            retry_policy.clone(),
+        )
+        .with_transport(self.opts.transport)
+        .with_timeout(timeout);
 
-        // add the flag headers to a hashmap to pass along to rover-client
-        let mut headers = HashMap::new();
-        if let Some(arg_headers) = &self.opts.headers {
-            for (header_key, header_value) in arg_headers {
-                headers.insert(header_key.to_string(), header_value.to_string());
-            }
-        };
-
-        Ok(introspect::run(SubgraphIntrospectInput { headers }, &client, should_retry)?.result)
+        send_with_auth(auth, self.opts.header_map(), |headers| {
+            introspect::run(SubgraphIntrospectInput { headers }, &client, should_retry)
+                .map(|response| response.result)
+        })
     }
 
     pub fn exec_and_watch(
         &self,
         client: &Client,
         output_opts: &OutputOpts,
-        retry_period: Option<Duration>,
+        retry_policy: &RetryPolicy,
+        timeout: Option<Duration>,
+        auth: Option<&AuthProvider>,
     ) -> ! {
-        self.opts
-            .exec_and_watch(|| self.exec(client, false, retry_period), output_opts)
+        self.opts.exec_and_watch(
+            || self.exec(client, false, retry_policy, timeout, auth),
+            output_opts,
+        )
     }
 }
@@ -1,3 +1,4 @@
+mod batch_check;
 mod check;
 mod delete;
 mod fetch;
@@ -25,6 +26,11 @@ pub struct Subgraph {
 
 #[derive(Debug, Serialize, Parser)]
 pub enum Command {
+    /// Check the schemas of multiple subgraphs against the federated graph
+    /// in the Apollo graph registry, running the checks concurrently and
+    /// reporting their results together
+    BatchCheck(batch_check::BatchCheck),
+
     /// Check for build errors and breaking changes caused by an updated subgraph schema
     /// against the federated graph in the Apollo graph registry
     Check(check::Check),
@@ -57,15 +63,22 @@ impl Subgraph {
         output_opts: &OutputOpts,
     ) -> RoverResult<RoverOutput> {
         match &self.command {
+            Command::BatchCheck(command) => {
+                command.run(client_config, git_context, checks_timeout_seconds)
+            }
             Command::Check(command) => {
                 command.run(client_config, git_context, checks_timeout_seconds)
             }
             Command::Delete(command) => command.run(client_config),
-            Command::Introspect(command) => command.run(
-                client_config.get_reqwest_client()?,
-                output_opts,
-                client_config.retry_period,
-            ),
+            Command::Introspect(command) => {
+                client_config.check_transport(command.opts.transport)?;
+                command.run(
+                    client_config.get_reqwest_client()?,
+                    output_opts,
+                    &client_config.retry_policy,
+                    client_config.client_timeout(),
+                )
+            }
             Command::Fetch(command) => command.run(client_config),
             Command::Lint(command) => command.run(client_config),
             Command::List(command) => command.run(client_config),
@@ -59,6 +59,7 @@ impl Publish {
         git_context: GitContext,
     ) -> RoverResult<RoverOutput> {
         let client = client_config.get_authenticated_client(&self.profile)?;
+        let graph_ref = self.graph.resolve(&self.profile, &client_config)?;
 
         let url = Self::determine_routing_url(
             self.no_url,
@@ -67,7 +68,7 @@ impl Publish {
             || {
                 Ok(routing_url::run(
                     SubgraphRoutingUrlInput {
-                        graph_ref: self.graph.graph_ref.clone(),
+                        graph_ref: graph_ref.clone(),
                         subgraph_name: self.subgraph.subgraph_name.clone(),
                     },
                     &client,
@@ -80,7 +81,7 @@ impl Publish {
 
         eprintln!(
             "Publishing SDL to {} (subgraph: {}) using credentials from the {} profile.",
-            Style::Link.paint(self.graph.graph_ref.to_string()),
+            Style::Link.paint(graph_ref.to_string()),
             Style::Link.paint(&self.subgraph.subgraph_name),
             Style::Command.paint(&self.profile.profile_name)
         );
@@ -93,7 +94,7 @@ impl Publish {
 
         let publish_response = publish::run(
             SubgraphPublishInput {
-                graph_ref: self.graph.graph_ref.clone(),
+                graph_ref: graph_ref.clone(),
                 subgraph: self.subgraph.subgraph_name.clone(),
                 url,
                 schema,
@@ -104,7 +105,7 @@ impl Publish {
         )?;
 
         Ok(RoverOutput::SubgraphPublishResponse {
-            graph_ref: self.graph.graph_ref.clone(),
+            graph_ref: graph_ref.clone(),
             subgraph: self.subgraph.subgraph_name.clone(),
             publish_response,
         })
@@ -112,10 +112,19 @@ impl Compose {
         override_install_path: Option<Utf8PathBuf>,
         client_config: StudioClientConfig,
     ) -> RoverResult<RoverOutput> {
+        let federation_version = match &self.opts.federation_version {
+            Some(federation_version) => federation_version.clone(),
+            None => self
+                .opts
+                .plugin_opts
+                .profile
+                .federation_version(&client_config)?
+                .unwrap_or(LatestFedTwo),
+        };
         let mut supergraph_config = get_supergraph_config(
             &self.opts.supergraph_config_source.graph_ref,
             &self.opts.supergraph_config_source.supergraph_yaml.clone(),
-            &self.opts.federation_version.clone().unwrap_or(LatestFedTwo),
+            &federation_version,
             client_config.clone(),
             &self.opts.plugin_opts.profile,
         )?
@@ -178,10 +187,18 @@ impl Compose {
             &federation_version
         );
 
-        let output = Command::new(&exe)
-            .args(["compose", yaml_path.as_ref()])
-            .output()
-            .context("Failed to execute command")?;
+        let span = tracing::info_span!(
+            "composition",
+            otel.name = "compose supergraph",
+            rover.federation_version = %federation_version,
+            rover.subgraphs = num_subgraphs,
+        );
+        let output = span.in_scope(|| {
+            Command::new(&exe)
+                .args(["compose", yaml_path.as_ref()])
+                .output()
+                .context("Failed to execute command")
+        })?;
         let stdout = str::from_utf8(&output.stdout)
             .with_context(|| format!("Could not parse output of `{} compose`", &exe))?;
 
@@ -0,0 +1,14 @@
+use clap::Parser;
+use serde::Serialize;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Describe each field in the usage data
+pub struct Fields {}
+
+impl Fields {
+    pub fn run(&self) -> RoverResult<RoverOutput> {
+        Ok(RoverOutput::TelemetryFields(sputnik::FIELDS))
+    }
+}
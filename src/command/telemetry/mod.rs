@@ -0,0 +1,39 @@
+mod fields;
+mod show;
+mod status;
+
+use clap::Parser;
+use serde::Serialize;
+use url::Url;
+
+use houston::Config;
+
+use crate::{RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+pub struct Telemetry {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Serialize, Parser)]
+pub enum Command {
+    /// Show whether usage data is reported, and where it's sent
+    Status(status::Status),
+
+    /// Show the usage data reported for the most recent commands
+    Show(show::Show),
+
+    /// Describe each field in the usage data
+    Fields(fields::Fields),
+}
+
+impl Telemetry {
+    pub fn run(&self, config: Config, enabled: bool, endpoint: Url) -> RoverResult<RoverOutput> {
+        match &self.command {
+            Command::Status(command) => command.run(config, enabled, endpoint),
+            Command::Show(command) => command.run(config),
+            Command::Fields(command) => command.run(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use clap::Parser;
+use serde::Serialize;
+
+use houston::Config;
+use sputnik::read_spooled_reports;
+
+use crate::{utils::telemetry::telemetry_spool_path, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Show the usage data reported for the most recent commands
+///
+/// A copy of the usage data for each command is kept, even when reporting
+/// is disabled, so it can be inspected before it's sent anywhere.
+pub struct Show {
+    /// How many of the most recent commands to show. The usage data for the
+    /// last 50 commands is kept
+    #[arg(long = "last", short = 'n', default_value_t = 10)]
+    #[serde(skip_serializing)]
+    last: usize,
+}
+
+impl Show {
+    pub fn run(&self, config: Config) -> RoverResult<RoverOutput> {
+        let reports = read_spooled_reports(&telemetry_spool_path(&config), self.last)?;
+        Ok(RoverOutput::TelemetryReports(reports))
+    }
+}
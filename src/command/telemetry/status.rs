@@ -0,0 +1,25 @@
+use clap::Parser;
+use serde::Serialize;
+use url::Url;
+
+use houston::Config;
+
+use crate::{utils::telemetry::telemetry_spool_path, RoverOutput, RoverResult};
+
+#[derive(Debug, Serialize, Parser)]
+/// Show whether usage data is reported, and where it's sent
+///
+/// Usage data is sent to $APOLLO_TELEMETRY_URL, which can be a `file://` URL
+/// to write it to a file instead. Set $APOLLO_TELEMETRY_DISABLED to stop
+/// reporting it.
+pub struct Status {}
+
+impl Status {
+    pub fn run(&self, config: Config, enabled: bool, endpoint: Url) -> RoverResult<RoverOutput> {
+        Ok(RoverOutput::TelemetryStatus {
+            enabled,
+            endpoint: endpoint.to_string(),
+            spool_path: telemetry_spool_path(&config),
+        })
+    }
+}
@@ -2,6 +2,7 @@ use clap::Parser;
 use serde::Serialize;
 
 use crate::options::TemplateOpt;
+use crate::utils::client::StudioClientConfig;
 use crate::{RoverOutput, RoverResult};
 
 use super::templates::list_templates;
@@ -13,8 +14,11 @@ pub struct List {
 }
 
 impl List {
-    pub fn run(&self) -> RoverResult<RoverOutput> {
-        let templates = list_templates(self.options.language.clone())?;
+    pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let templates = list_templates(
+            &client_config.get_reqwest_client()?,
+            self.options.language.clone(),
+        )?;
         Ok(RoverOutput::TemplateList(templates))
     }
 }
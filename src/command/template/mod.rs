@@ -32,7 +32,7 @@ impl Template {
     pub(crate) fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
         match &self.command {
             Command::Use(use_template) => use_template.run(client_config),
-            Command::List(list) => list.run(),
+            Command::List(list) => list.run(client_config),
         }
     }
 }
@@ -35,10 +35,12 @@ pub struct Use {
 
 impl Use {
     pub fn run(&self, client_config: StudioClientConfig) -> RoverResult<RoverOutput> {
+        let client = client_config.get_reqwest_client()?;
+
         // find the template to extract
         let (template_id, download_url) = if let Some(template_id) = &self.template {
             // if they specify an ID, get it
-            let result = get_template(template_id)?;
+            let result = get_template(&client, template_id)?;
             if let Some(result) = result {
                 (template_id.clone(), result.download_url)
             } else {
@@ -51,7 +53,7 @@ impl Use {
         } else {
             // otherwise, ask them what language they want to use
             let project_language = self.options.get_or_prompt_language()?;
-            let templates = get_templates_for_language(project_language)?;
+            let templates = get_templates_for_language(&client, project_language)?;
             let template = selection_prompt(templates)?;
             (template.id, template.download_url)
         };
@@ -60,7 +62,7 @@ impl Use {
         let path = self.get_or_prompt_path()?;
 
         // download and extract a tarball from github
-        extract_tarball(download_url, &path, &client_config.get_reqwest_client()?)?;
+        extract_tarball(download_url, &path, &client)?;
 
         Ok(RoverOutput::TemplateUseSuccess { template_id, path })
     }
@@ -50,6 +50,25 @@ pub enum RoverErrorCode {
     E042,
     E043,
     E044,
+    E045,
+    E046,
+    E047,
+    E048,
+    E049,
+    E050,
+    E051,
+    E052,
+    E053,
+    E054,
+    E055,
+    E056,
+    E057,
+    E058,
+    E059,
+    E060,
+    E061,
+    E062,
+    E063,
 }
 
 impl Display for RoverErrorCode {
@@ -239,6 +258,82 @@ impl RoverErrorCode {
                 RoverErrorCode::E044,
                 include_str!("./codes/E044.md").to_string(),
             ),
+            (
+                RoverErrorCode::E045,
+                include_str!("./codes/E045.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E046,
+                include_str!("./codes/E046.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E047,
+                include_str!("./codes/E047.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E048,
+                include_str!("./codes/E048.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E049,
+                include_str!("./codes/E049.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E050,
+                include_str!("./codes/E050.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E051,
+                include_str!("./codes/E051.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E052,
+                include_str!("./codes/E052.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E053,
+                include_str!("./codes/E053.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E054,
+                include_str!("./codes/E054.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E055,
+                include_str!("./codes/E055.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E056,
+                include_str!("./codes/E056.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E057,
+                include_str!("./codes/E057.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E058,
+                include_str!("./codes/E058.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E059,
+                include_str!("./codes/E059.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E060,
+                include_str!("./codes/E060.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E061,
+                include_str!("./codes/E061.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E062,
+                include_str!("./codes/E062.md").to_string(),
+            ),
+            (
+                RoverErrorCode::E063,
+                include_str!("./codes/E063.md").to_string(),
+            ),
         ];
         contents.into_iter().collect()
     }
@@ -167,6 +167,13 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     Some(RoverErrorSuggestion::FixCheckFailures),
                     Some(RoverErrorCode::E043),
                 ),
+                RoverClientError::BatchCheckWorkflowFailure {
+                    graph_ref: _,
+                    batch_response: _,
+                } => (
+                    Some(RoverErrorSuggestion::FixCheckFailures),
+                    Some(RoverErrorCode::E043),
+                ),
                 RoverClientError::LintFailures { lint_response: _ } => (
                     Some(RoverErrorSuggestion::FixLintFailure),
                     Some(RoverErrorCode::E042),
@@ -221,7 +228,7 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     Some(RoverErrorSuggestion::CheckGraphNameAndAuth),
                     Some(RoverErrorCode::E010),
                 ),
-                RoverClientError::GraphQl { .. } => (None, None),
+                RoverClientError::GraphQl { .. } => (None, Some(RoverErrorCode::E045)),
                 RoverClientError::IntrospectionError { .. } => (None, Some(RoverErrorCode::E011)),
                 RoverClientError::ClientError { .. } => (None, Some(RoverErrorCode::E012)),
                 RoverClientError::InvalidKey => {
@@ -240,18 +247,21 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     Some(RoverErrorSuggestion::SubmitIssue),
                     Some(RoverErrorCode::E015),
                 ),
-                RoverClientError::BadReleaseUrl => (Some(RoverErrorSuggestion::SubmitIssue), None),
+                RoverClientError::BadReleaseUrl => (
+                    Some(RoverErrorSuggestion::SubmitIssue),
+                    Some(RoverErrorCode::E054),
+                ),
                 RoverClientError::NoSupergraphBuilds { .. } => (
                     Some(RoverErrorSuggestion::RunComposition),
                     Some(RoverErrorCode::E027),
                 ),
-                RoverClientError::AdhocError { .. } => (None, None),
-                RoverClientError::InvalidGraphRef { .. } => {
-                    unreachable!("Graph ref parse errors should be caught via clap")
-                }
+                RoverClientError::AdhocError { .. } => (None, Some(RoverErrorCode::E046)),
+                // graph refs and validation periods are usually parsed by clap,
+                // but they're coded in case they're parsed from elsewhere
+                RoverClientError::InvalidGraphRef => (None, Some(RoverErrorCode::E055)),
                 RoverClientError::InvalidValidationPeriodDuration(_)
                 | RoverClientError::ValidationPeriodTooGranular => {
-                    unreachable!("Validation period parse errors should be caught via clap")
+                    (None, Some(RoverErrorCode::E056))
                 }
                 RoverClientError::InvalidInputError { graph_ref } => (
                     Some(RoverErrorSuggestion::FixChecksInput {
@@ -267,16 +277,24 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     Some(RoverErrorSuggestion::UpgradePlan),
                     Some(RoverErrorCode::E034),
                 ),
-                RoverClientError::RateLimitExceeded => {
-                    (Some(RoverErrorSuggestion::TryAgainLater), None)
-                }
+                RoverClientError::NoRecordedResponse { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Re-run this command with `--record` instead of `--replay` to record a response to this request.".to_string(),
+                    )),
+                    Some(RoverErrorCode::E057),
+                ),
+                RoverClientError::RateLimitExceeded => (
+                    Some(RoverErrorSuggestion::TryAgainLater),
+                    Some(RoverErrorCode::E047),
+                ),
                 RoverClientError::ChecksTimeoutError { url } => (
                     Some(RoverErrorSuggestion::IncreaseChecksTimeout { url: url.clone() }),
-                    None,
+                    Some(RoverErrorCode::E048),
+                ),
+                RoverClientError::UnknownCheckWorkflowStatus => (
+                    Some(RoverErrorSuggestion::SubmitIssue),
+                    Some(RoverErrorCode::E049),
                 ),
-                RoverClientError::UnknownCheckWorkflowStatus => {
-                    (Some(RoverErrorSuggestion::SubmitIssue), None)
-                }
                 RoverClientError::MissingRoutingUrlError {
                     subgraph_name,
                     graph_ref,
@@ -295,7 +313,7 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                         graph_ref: graph_ref.clone(),
                         frontend_url_root: frontend_url_root.clone(),
                     }),
-                    None,
+                    Some(RoverErrorCode::E050),
                 ),
                 RoverClientError::PersistedQueryListIdNotFound {
                     graph_id,
@@ -306,16 +324,19 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                         graph_id: graph_id.clone(),
                         frontend_url_root: frontend_url_root.clone(),
                     }),
-                    None,
+                    Some(RoverErrorCode::E051),
                 ),
-                RoverClientError::RelayOperationParseFailures { .. } => (None, None),
+                RoverClientError::RelayOperationParseFailures { .. } => {
+                    (None, Some(RoverErrorCode::E052))
+                }
                 RoverClientError::OfflineLicenseNotEnabled { .. } => (
                     Some(RoverErrorSuggestion::ContactApolloAccountManager),
                     Some(RoverErrorCode::E044),
                 ),
-                RoverClientError::OrganizationNotFound { .. } => {
-                    (Some(RoverErrorSuggestion::CheckGraphNameAndAuth), None)
-                }
+                RoverClientError::OrganizationNotFound { .. } => (
+                    Some(RoverErrorSuggestion::CheckGraphNameAndAuth),
+                    Some(RoverErrorCode::E053),
+                ),
             };
             return RoverErrorMetadata {
                 json_version: JsonVersion::default(),
@@ -366,6 +387,47 @@ impl From<&mut anyhow::Error> for RoverErrorMetadata {
                     )),
                     Some(RoverErrorCode::E035),
                 ),
+                HoustonProblem::CredentialPassphraseRequired(_) => (
+                    Some(RoverErrorSuggestion::Adhoc(format!(
+                        "Set ${} to the passphrase the API key was encrypted with.",
+                        RoverEnvKey::CredentialsPassphrase
+                    ))),
+                    Some(RoverErrorCode::E058),
+                ),
+                HoustonProblem::CredentialDecryptionFailed(profile_name) => (
+                    Some(RoverErrorSuggestion::Adhoc(format!(
+                        "Check that ${} is the passphrase the API key was encrypted with, or run `rover config auth --profile {}` to store the key again.",
+                        RoverEnvKey::CredentialsPassphrase,
+                        profile_name
+                    ))),
+                    Some(RoverErrorCode::E059),
+                ),
+                HoustonProblem::CredentialHelperFailed { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Check that the credential helper is installed and works when run on its own, or store the API key somewhere else with `rover config auth --credential-store file`.".to_string(),
+                    )),
+                    Some(RoverErrorCode::E060),
+                ),
+                HoustonProblem::InvalidProfileSetting { .. } => (
+                    Some(RoverErrorSuggestion::Adhoc(format!(
+                        "Profiles have these settings: {}.",
+                        houston::ProfileSettings::KEYS.join(", ")
+                    ))),
+                    Some(RoverErrorCode::E061),
+                ),
+                HoustonProblem::ProfileAlreadyExists(profile_name) => (
+                    Some(RoverErrorSuggestion::Adhoc(format!(
+                        "Pass `--overwrite` to replace it, or delete it first with `rover config delete {}`.",
+                        profile_name
+                    ))),
+                    Some(RoverErrorCode::E062),
+                ),
+                HoustonProblem::InvalidProfileName(_) => (
+                    Some(RoverErrorSuggestion::Adhoc(
+                        "Rename the profile in the export file. Profile names can't be empty, be `.` or `..`, or contain `/`, `\\`, `=` or control characters such as line breaks.".to_string(),
+                    )),
+                    Some(RoverErrorCode::E063),
+                ),
                 HoustonProblem::PathNotUtf8(_) => (
                     Some(RoverErrorSuggestion::SubmitIssue),
                     Some(RoverErrorCode::E023),
@@ -7,7 +7,7 @@ pub type RoverResult<T> = std::result::Result<T, RoverError>;
 use calm_io::{stderr, stdoutln};
 use rover_client::RoverClientError;
 use rover_std::Style;
-use serde::ser::SerializeStruct;
+use serde::ser::SerializeMap;
 use serde::{Serialize, Serializer};
 use serde_json::{json, Value};
 
@@ -17,6 +17,7 @@ use std::fmt::{self, Debug, Display};
 
 use apollo_federation_types::build::BuildErrors;
 
+use crate::command::{DoctorFailures, RoverOutput};
 use crate::options::JsonVersion;
 
 /// A specialized `Error` type for Rover that wraps `anyhow`
@@ -35,23 +36,21 @@ fn serialize_anyhow<S>(error: &anyhow::Error, serializer: S) -> std::result::Res
 where
     S: Serializer,
 {
-    let top_level_struct = "error";
     let message_field_name = "message";
     let details_struct = "details";
 
+    let mut data = serializer.serialize_map(None)?;
+    data.serialize_entry(message_field_name, &error.to_string())?;
     if let Some(rover_client_error) = error.downcast_ref::<RoverClientError>() {
         if let Some(rover_client_error_source) = rover_client_error.source() {
             if let Some(build_errors) = rover_client_error_source.downcast_ref::<BuildErrors>() {
-                let mut top_level_data = serializer.serialize_struct(top_level_struct, 2)?;
-                top_level_data.serialize_field(message_field_name, &error.to_string())?;
-                top_level_data.serialize_field(details_struct, &build_errors)?;
-                return top_level_data.end();
+                data.serialize_entry(details_struct, &build_errors)?;
             }
         }
+        for (key, value) in rover_client_error.fields() {
+            data.serialize_entry(&key, &value)?;
+        }
     }
-
-    let mut data = serializer.serialize_struct(top_level_struct, 1)?;
-    data.serialize_field(message_field_name, &error.to_string())?;
     data.end()
 }
 
@@ -93,11 +92,20 @@ impl RoverError {
                 graph_ref: _,
                 check_response,
             }) => stdoutln!("{}", check_response.get_output())?,
+            Some(RoverClientError::BatchCheckWorkflowFailure {
+                graph_ref: _,
+                batch_response,
+            }) => stdoutln!("{}", batch_response.get_output())?,
             Some(RoverClientError::LintFailures { lint_response }) => {
                 stdoutln!("{}", lint_response.get_ariadne()?)?
             }
             _ => (),
         }
+        if let Some(DoctorFailures(checks)) = self.error.downcast_ref::<DoctorFailures>() {
+            if let Some(report) = RoverOutput::DoctorReport(checks.clone()).get_stdout()? {
+                stdoutln!("{}", report)?;
+            }
+        }
 
         stderr!("{}", self)?;
         Ok(())
@@ -109,8 +117,15 @@ impl RoverError {
                 graph_ref: _,
                 check_response,
             }) => check_response.get_json(),
+            Some(RoverClientError::BatchCheckWorkflowFailure {
+                graph_ref: _,
+                batch_response,
+            }) => batch_response.get_json(),
             Some(RoverClientError::LintFailures { lint_response }) => lint_response.get_json(),
-            _ => Value::Null,
+            _ => match self.error.downcast_ref::<DoctorFailures>() {
+                Some(DoctorFailures(checks)) => json!({ "checks": checks }),
+                None => Value::Null,
+            },
         };
     }
 
@@ -123,6 +138,10 @@ impl RoverError {
             Some(RoverClientError::CheckWorkflowFailure {
                 graph_ref: _,
                 check_response: _,
+            })
+            | Some(RoverClientError::BatchCheckWorkflowFailure {
+                graph_ref: _,
+                batch_response: _,
             }) => JsonVersion::Two,
             _ => self.metadata.json_version.clone(),
         }
@@ -1,14 +1,73 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
 use clap::Parser;
+use houston::Config;
 use rover_client::shared::GraphRef;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    options::ProfileOpt,
+    utils::{client::StudioClientConfig, project_config::ProjectConfig},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// The graph ref passed to a command. When none is passed, [`GraphRefOpt::resolve`] falls
+/// back to the `graph_ref` in `.rover.toml`, and then to the `graph_ref` setting of its profile.
 #[derive(Debug, Serialize, Deserialize, Parser)]
 pub struct GraphRefOpt {
     /// <NAME>@<VARIANT> of graph in Apollo Studio.
-    /// @<VARIANT> may be left off, defaulting to @current
+    /// @<VARIANT> may be left off, defaulting to @current.
+    /// Defaults to the `graph_ref` in `.rover.toml`, or the profile's `graph_ref` setting
     #[arg(value_name = "GRAPH_REF")]
     #[serde(skip_serializing)]
-    pub graph_ref: GraphRef,
+    graph_ref: Option<GraphRef>,
+}
+
+impl GraphRefOpt {
+    /// The graph ref to run a command against, which was either passed or is the default
+    /// for the project or for `profile`
+    pub fn resolve(
+        &self,
+        profile: &ProfileOpt,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<GraphRef> {
+        if let Some(graph_ref) = &self.graph_ref {
+            return Ok(graph_ref.clone());
+        }
+        if let Some(ProjectConfig {
+            graph_ref: Some(graph_ref),
+            path,
+            ..
+        }) = &client_config.project
+        {
+            return GraphRef::from_str(graph_ref)
+                .map_err(|e| RoverError::new(anyhow!("Invalid graph_ref in {}: {}", path, e)));
+        }
+        GraphRefOpt::from_profile(profile, &client_config.config)
+    }
+
+    fn from_profile(profile: &ProfileOpt, config: &Config) -> RoverResult<GraphRef> {
+        let profile_name = &profile.profile_name;
+        let Some(graph_ref) = profile.settings(config)?.graph_ref else {
+            let mut err = RoverError::new(anyhow!(
+                "No graph ref was passed, and profile \"{}\" doesn't have a `graph_ref` setting.",
+                profile_name
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Pass a graph ref as `<NAME>@<VARIANT>`, or set one for the profile with `rover config set graph_ref <GRAPH_REF> --profile {}`.",
+                profile_name
+            )));
+            return Err(err);
+        };
+        GraphRef::from_str(&graph_ref).map_err(|e| {
+            RoverError::new(anyhow!(
+                "Invalid graph_ref setting in profile \"{}\": {}",
+                profile_name,
+                e
+            ))
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Parser)]
@@ -19,3 +78,40 @@ pub struct OptionalGraphRefOpt {
     #[serde(skip_serializing)]
     pub graph_ref: Option<GraphRef>,
 }
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8Path;
+    use houston::{Profile, ProfileSettings};
+
+    use super::*;
+
+    #[test]
+    fn it_falls_back_to_the_profile_graph_ref() {
+        let home = tempfile::tempdir().unwrap();
+        let home = Utf8Path::from_path(home.path()).unwrap();
+        let config = Config::new(Some(&home), None).unwrap();
+        Profile::set_api_key("default", &config, "service:my-graph:abc123").unwrap();
+
+        let profile = ProfileOpt {
+            profile_name: "default".to_string(),
+        };
+        assert!(GraphRefOpt::from_profile(&profile, &config).is_err());
+
+        let settings = ProfileSettings {
+            graph_ref: Some("my-graph@prod".to_string()),
+            ..Default::default()
+        };
+        Profile::save_settings("default", &config, &settings).unwrap();
+        assert_eq!(
+            GraphRefOpt::from_profile(&profile, &config)
+                .unwrap()
+                .to_string(),
+            "my-graph@prod"
+        );
+
+        let profile_toml = home.join("profiles").join("default").join("profile.toml");
+        std::fs::write(profile_toml, "graph_ref = [").unwrap();
+        assert!(GraphRefOpt::from_profile(&profile, &config).is_err());
+    }
+}
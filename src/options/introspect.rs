@@ -1,13 +1,34 @@
-use clap::Parser;
-use reqwest::Url;
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Duration;
+
+use backoff::{backoff::Backoff, ExponentialBackoff};
+
+use clap::{Parser, ValueEnum};
+use reqwest::{blocking::Client, Url};
+use rover_client::blocking::GraphQLTransport;
+use rover_client::operations::graph::introspect::{IntrospectionOptions, DEFAULT_TYPE_DEPTH};
+use rover_std::Style;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     options::{OutputOpts, RoverPrinter},
-    utils::parsers::parse_header,
+    utils::{
+        auth_provider::{AuthProvider, AuthProviderConfig},
+        parsers::parse_header,
+        schema_diff::SchemaDiff,
+        shell,
+    },
     RoverOutput, RoverResult,
 };
 
+/// The longest `--watch` waits between polls while the endpoint is failing
+const MAX_WATCH_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How far each `--watch` poll can randomly stray from the interval, so that many
+/// watchers started together don't keep polling the endpoint at the same moment
+const WATCH_JITTER: f64 = 0.1;
+
 #[derive(Debug, Serialize, Deserialize, Parser)]
 pub struct IntrospectOpts {
     /// The endpoint of the subgraph to introspect
@@ -27,45 +48,255 @@ pub struct IntrospectOpts {
     /// poll the endpoint, printing the introspection result if/when its contents change
     #[arg(long)]
     pub watch: bool,
+
+    /// How many seconds to wait between polls with `--watch`, with some jitter. Polls back off while the endpoint is failing.
+    #[arg(long, value_name = "SECONDS", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..), requires = "watch")]
+    pub watch_interval: u64,
+
+    /// With `--watch`, print the types, fields, arguments and enum values that changed instead of the whole schema each time it changes
+    #[arg(long, requires = "watch")]
+    pub diff: bool,
+
+    /// With `--watch`, a shell command to run each time the schema changes, including when it's first fetched.
+    /// The path of a file containing the new schema is in the `ROVER_SCHEMA_PATH` environment variable.
+    #[arg(long, value_name = "COMMAND", requires = "watch")]
+    #[serde(skip_serializing)]
+    pub on_change: Option<String>,
+
+    /// How to send the introspection query: `post` (the default), `get` to send it as query parameters,
+    /// or `websocket` to send it over GraphQL over WebSocket, which can't be combined with proxy or TLS options
+    #[arg(long, default_value_t)]
+    pub transport: GraphQLTransport,
+
+    #[clap(flatten)]
+    pub auth: IntrospectionAuthOpts,
+}
+
+/// Options for minting tokens for introspection requests, for endpoints whose
+/// tokens expire too quickly to pass with `--header`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Parser)]
+pub struct IntrospectionAuthOpts {
+    /// A shell command that prints a token to send as `Authorization: Bearer <token>`.
+    /// The command may instead print a JSON object with `access_token` and `expires_in` fields.
+    /// It's run again when the token expires or the endpoint rejects it.
+    #[arg(long, value_name = "COMMAND", conflicts_with = "auth_token_url")]
+    #[serde(skip_serializing)]
+    pub auth_command: Option<String>,
+
+    /// An OAuth2 token endpoint to request tokens from with the client credentials grant
+    #[arg(long, value_name = "URL", requires_all = ["auth_client_id", "auth_client_secret"])]
+    #[serde(skip_serializing)]
+    pub auth_token_url: Option<Url>,
+
+    /// The client ID to request OAuth2 tokens with
+    #[arg(long, value_name = "CLIENT_ID", requires = "auth_token_url")]
+    #[serde(skip_serializing)]
+    pub auth_client_id: Option<String>,
+
+    /// The client secret to request OAuth2 tokens with
+    #[arg(
+        long,
+        value_name = "CLIENT_SECRET",
+        env = "APOLLO_INTROSPECTION_CLIENT_SECRET",
+        hide_env_values = true
+    )]
+    #[serde(skip_serializing)]
+    pub auth_client_secret: Option<String>,
+
+    /// The scope to request OAuth2 tokens with
+    #[arg(long, value_name = "SCOPE", requires = "auth_token_url")]
+    #[serde(skip_serializing)]
+    pub auth_scope: Option<String>,
+
+    /// The audience to request OAuth2 tokens for, for providers that require one
+    #[arg(long, value_name = "AUDIENCE", requires = "auth_token_url")]
+    #[serde(skip_serializing)]
+    pub auth_audience: Option<String>,
+}
+
+impl IntrospectionAuthOpts {
+    pub fn config(&self) -> Option<AuthProviderConfig> {
+        if let Some(command) = &self.auth_command {
+            Some(AuthProviderConfig::Command {
+                command: command.clone(),
+                expires_in: None,
+            })
+        } else if let (Some(token_url), Some(client_id), Some(client_secret)) = (
+            &self.auth_token_url,
+            &self.auth_client_id,
+            &self.auth_client_secret,
+        ) {
+            Some(AuthProviderConfig::ClientCredentials {
+                token_url: token_url.to_string(),
+                client_id: client_id.clone(),
+                client_secret: client_secret.clone(),
+                scope: self.auth_scope.clone(),
+                audience: self.auth_audience.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn provider(&self, client: &Client) -> Option<AuthProvider> {
+        self.config()
+            .map(|config| AuthProvider::new(config, client.clone()))
+    }
+}
+
+/// Options controlling the introspection query `rover graph introspect` sends
+#[derive(Debug, Clone, Serialize, Deserialize, Parser)]
+pub struct IntrospectionQueryOpts {
+    /// How many levels of `ofType` to request for each type reference.
+    /// Raise this for deeply wrapped types like `[[[String!]!]!]!`, or lower it for servers that limit query depth.
+    #[arg(long, default_value_t = DEFAULT_TYPE_DEPTH as u8, value_parser = clap::value_parser!(u8).range(1..=32))]
+    pub type_depth: u8,
+
+    /// Don't request descriptions, for servers that reject them or to shrink the response
+    #[arg(long)]
+    pub no_descriptions: bool,
+
+    /// Request deprecated arguments and input fields. The server must support `includeDeprecated` on `args` and `inputFields`.
+    #[arg(long)]
+    pub include_deprecated_input_values: bool,
+
+    /// Request whether each directive is repeatable. The server must support `isRepeatable`.
+    #[arg(long)]
+    pub include_is_repeatable: bool,
+
+    /// Request the `@specifiedBy` URL of each scalar. The server must support `specifiedByURL`.
+    #[arg(long)]
+    pub include_specified_by_url: bool,
+}
+
+impl Default for IntrospectionQueryOpts {
+    fn default() -> Self {
+        Self {
+            type_depth: DEFAULT_TYPE_DEPTH as u8,
+            no_descriptions: false,
+            include_deprecated_input_values: false,
+            include_is_repeatable: false,
+            include_specified_by_url: false,
+        }
+    }
+}
+
+impl From<&IntrospectionQueryOpts> for IntrospectionOptions {
+    fn from(opts: &IntrospectionQueryOpts) -> Self {
+        IntrospectionOptions {
+            type_depth: opts.type_depth.into(),
+            include_descriptions: !opts.no_descriptions,
+            include_deprecated_input_values: opts.include_deprecated_input_values,
+            include_is_repeatable: opts.include_is_repeatable,
+            include_specified_by_url: opts.include_specified_by_url,
+        }
+    }
+}
+
+/// How `rover graph introspect` prints the introspected schema
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+pub enum IntrospectionOutputFormat {
+    /// The schema in SDL
+    #[default]
+    Sdl,
+    /// The raw `__schema` JSON returned by the endpoint
+    IntrospectionJson,
 }
 
 impl IntrospectOpts {
+    /// The `--header`s to send with each introspection request
+    pub fn header_map(&self) -> HashMap<String, String> {
+        self.headers
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
     pub fn exec_and_watch<F>(&self, exec_fn: F, output_opts: &OutputOpts) -> !
     where
         F: Fn() -> RoverResult<String>,
     {
-        let mut last_result = None;
+        let interval = Duration::from_secs(self.watch_interval);
+        // a backoff that never grows, used only for its jitter
+        let mut poll_jitter = ExponentialBackoff {
+            current_interval: interval,
+            initial_interval: interval,
+            randomization_factor: WATCH_JITTER,
+            multiplier: 1.0,
+            max_interval: interval,
+            max_elapsed_time: None,
+            ..Default::default()
+        };
+        let mut error_backoff = ExponentialBackoff {
+            current_interval: interval,
+            initial_interval: interval,
+            max_interval: interval.max(MAX_WATCH_BACKOFF),
+            max_elapsed_time: None,
+            ..Default::default()
+        };
+        let mut last_sdl: Option<String> = None;
+        let mut last_error = None;
         loop {
-            match exec_fn() {
+            let wait = match exec_fn() {
                 Ok(sdl) => {
-                    let mut was_updated = true;
-                    if let Some(last) = last_result {
-                        if last == sdl {
-                            was_updated = false
-                        }
-                    }
-
-                    if was_updated {
-                        let output = RoverOutput::Introspection(sdl.to_string());
+                    last_error = None;
+                    if last_sdl.as_ref() != Some(&sdl) {
+                        let output = match (&last_sdl, self.diff) {
+                            (Some(last), true) => {
+                                RoverOutput::IntrospectionDiff(SchemaDiff::between(last, &sdl))
+                            }
+                            _ => RoverOutput::Introspection(sdl.clone()),
+                        };
                         let _ = output.write_or_print(output_opts).map_err(|e| e.print());
+                        if let Some(on_change) = &self.on_change {
+                            run_on_change(on_change, &sdl);
+                        }
+                        last_sdl = Some(sdl);
                     }
-                    last_result = Some(sdl);
+                    error_backoff.reset();
+                    poll_jitter.next_backoff().unwrap_or(interval)
                 }
                 Err(error) => {
-                    let mut was_updated = true;
                     let e = error.to_string();
-                    if let Some(last) = last_result {
-                        if last == e {
-                            was_updated = false;
-                        }
-                    }
-                    if was_updated {
+                    if last_error.as_ref() != Some(&e) {
                         let _ = error.write_or_print(output_opts).map_err(|e| e.print());
                     }
-                    last_result = Some(e);
+                    last_error = Some(e);
+                    error_backoff.next_backoff().unwrap_or(MAX_WATCH_BACKOFF)
                 }
-            }
-            std::thread::sleep(std::time::Duration::from_secs(1))
+            };
+            std::thread::sleep(wait)
         }
     }
 }
+
+/// Runs an `--on-change` hook with the new schema, warning rather than stopping the watch if it fails
+fn run_on_change(command: &str, sdl: &str) {
+    let warn_prefix = Style::WarningPrefix.paint("WARN:");
+    let result = tempfile::Builder::new()
+        .prefix("rover-schema-")
+        .suffix(".graphql")
+        .tempfile()
+        .and_then(|mut schema_file| {
+            schema_file.write_all(sdl.as_bytes())?;
+            shell::command(command)
+                .env("ROVER_SCHEMA_PATH", schema_file.path())
+                .status()
+        });
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "{} {} exited with {}",
+            warn_prefix,
+            Style::Command.paint(command),
+            status
+        ),
+        Err(e) => eprintln!(
+            "{} could not run {}: {}",
+            warn_prefix,
+            Style::Command.paint(command),
+            e
+        ),
+    }
+}
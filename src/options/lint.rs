@@ -1,9 +1,75 @@
+use camino::Utf8PathBuf;
 use clap::Parser;
+use rover_client::shared::LintResponse;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::lint::{fail_on_errors, LintBaseline};
+use crate::RoverResult;
+
 #[derive(Debug, Serialize, Deserialize, Parser)]
 pub struct LintOpts {
     /// Ignore existing lint violations for a published subgraph. If passed, the command will only report lint violations introduced by recent changes.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "offline")]
     pub ignore_existing_lint_violations: bool,
+
+    /// Lint the schema locally with Rover's built-in rules instead of in GraphOS.
+    /// Linting offline does not require a graph ref or an API key.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// The path to a YAML file configuring the rules used when linting with `--offline`.
+    /// Defaults to `.rover-lint.yaml` in the current directory, if it exists.
+    #[arg(long = "lint-config", requires = "offline")]
+    #[serde(skip_serializing)]
+    pub lint_config: Option<Utf8PathBuf>,
+
+    /// The path to a file of known lint violations. Violations recorded in the
+    /// baseline are reported as ignored, so only new violations fail the command.
+    #[arg(long, conflicts_with = "ignore_existing_lint_violations")]
+    #[serde(skip_serializing)]
+    pub baseline: Option<Utf8PathBuf>,
+
+    /// Record every current violation in the file passed to `--baseline` instead
+    /// of failing on them.
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Apply mechanical fixes for violations with an unambiguous remedy, such as
+    /// the casing of names or missing deprecation reasons, and write them back to the schema file.
+    #[arg(long, conflicts_with = "update_baseline")]
+    pub fix: bool,
+
+    /// Print the fixes `--fix` would make as a unified diff instead of writing them.
+    #[arg(long, requires = "fix")]
+    pub dry_run: bool,
+}
+
+impl LintOpts {
+    /// Applies or updates the baseline, if one was passed, and fails if any
+    /// unrecorded error-level violations remain.
+    pub fn finish(&self, mut lint_response: LintResponse) -> RoverResult<LintResponse> {
+        let Some(path) = &self.baseline else {
+            return fail_on_errors(lint_response);
+        };
+
+        if self.update_baseline {
+            let baseline = LintBaseline::from_diagnostics(&lint_response.diagnostics);
+            baseline.save(path)?;
+            eprintln!(
+                "Recorded {} lint violation(s) in the baseline at {}",
+                baseline.len(),
+                path
+            );
+            return Ok(lint_response);
+        }
+
+        let stale = LintBaseline::load(path)?.apply(&mut lint_response);
+        if stale > 0 {
+            eprintln!(
+                "{} lint violation(s) recorded in the baseline at {} no longer occur. Run with `--update-baseline` to remove them.",
+                stale, path
+            );
+        }
+        fail_on_errors(lint_response)
+    }
 }
@@ -4,9 +4,11 @@ mod graph;
 mod introspect;
 mod license;
 mod lint;
+mod network;
 mod output;
 mod persisted_queries;
 mod profile;
+mod retry;
 mod schema;
 mod subgraph;
 mod template;
@@ -17,9 +19,11 @@ pub(crate) use graph::*;
 pub(crate) use introspect::*;
 pub(crate) use license::*;
 pub(crate) use lint::*;
+pub(crate) use network::*;
 pub(crate) use output::*;
 pub(crate) use persisted_queries::*;
 pub(crate) use profile::*;
+pub(crate) use retry::*;
 pub(crate) use schema::*;
 pub(crate) use subgraph::*;
 pub(crate) use template::*;
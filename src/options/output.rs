@@ -98,6 +98,11 @@ impl OutputOpts {
         }
     }
 
+    /// The format Rover's output is printed in
+    pub fn format_kind(&self) -> RoverOutputFormatKind {
+        self.format_kind
+    }
+
     /// Handle output and errors from a Rover command.
     pub fn handle_output<T>(&self, rover_command_output: T) -> RoverResult<()>
     where
@@ -1,17 +1,173 @@
-use clap::Parser;
+use std::str::FromStr;
+
+use anyhow::Context;
+use apollo_federation_types::config::FederationVersion;
+use clap::{ArgMatches, Parser};
+use houston::{HoustonProblem, Profile, ProfileSettings};
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+use crate::{
+    utils::{client::StudioClientConfig, project_config::ProjectConfig},
+    RoverResult,
+};
+
+/// The profile commands run with when none is passed or set in `.rover.toml`
+pub const DEFAULT_PROFILE: &str = "default";
+
 #[derive(Debug, Clone, Serialize, Deserialize, Parser)]
 pub struct ProfileOpt {
-    /// Name of configuration profile to use
-    #[arg(long = "profile", default_value = "default")]
+    /// Name of configuration profile to use.
+    /// Defaults to the `profile` in `.rover.toml`, or `default`
+    #[arg(long = "profile", default_value = DEFAULT_PROFILE)]
     #[serde(skip_serializing)]
     pub profile_name: String,
 }
 
+impl ProfileOpt {
+    /// The `--profile` a command was run with, found in whichever of `matches`' subcommands
+    /// takes one, so settings that apply to every request can come from the profile
+    pub(crate) fn name_from_arg_matches(matches: &ArgMatches) -> Option<String> {
+        match matches.try_get_one::<String>("profile_name") {
+            Ok(Some(profile_name)) => Some(profile_name.clone()),
+            _ => matches
+                .subcommand()
+                .and_then(|(_, matches)| ProfileOpt::name_from_arg_matches(matches)),
+        }
+    }
+
+    /// Makes `profile_name` the default for `--profile` in `cmd` and each of its subcommands
+    /// that take a [`ProfileOpt`], so the `profile` in `.rover.toml` is used when none is passed
+    pub(crate) fn with_default(cmd: clap::Command, profile_name: &str) -> clap::Command {
+        let takes_profile = cmd.get_arguments().any(|arg| {
+            arg.get_id() == "profile_name"
+                && matches!(arg.get_default_values(), [value] if *value == DEFAULT_PROFILE)
+        });
+        let cmd = if takes_profile {
+            cmd.mut_arg("profile_name", |arg| {
+                arg.default_value(profile_name.to_string())
+            })
+        } else {
+            cmd
+        };
+        let subcommands: Vec<String> = cmd
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect();
+        subcommands.into_iter().fold(cmd, |cmd, name| {
+            cmd.mut_subcommand(name, |subcommand| {
+                ProfileOpt::with_default(subcommand, profile_name)
+            })
+        })
+    }
+
+    /// The profile's non-sensitive settings. These are all unset if the profile doesn't
+    /// exist, since commands can still authenticate with `$APOLLO_KEY` without one.
+    pub fn settings(&self, config: &houston::Config) -> Result<ProfileSettings, HoustonProblem> {
+        match Profile::settings(&self.profile_name, config) {
+            Err(HoustonProblem::ProfileNotFound(_) | HoustonProblem::NoConfigProfiles) => {
+                Ok(ProfileSettings::default())
+            }
+            settings => settings,
+        }
+    }
+
+    /// The `federation_version` from `.rover.toml`, or else the profile's setting, which are
+    /// used when `--federation-version` isn't passed
+    pub fn federation_version(
+        &self,
+        client_config: &StudioClientConfig,
+    ) -> RoverResult<Option<FederationVersion>> {
+        let (version, source) = match &client_config.project {
+            Some(ProjectConfig {
+                federation_version: Some(version),
+                path,
+                ..
+            }) => (version.clone(), path.to_string()),
+            _ => match self.settings(&client_config.config)?.federation_version {
+                Some(version) => (version, format!("profile \"{}\"", self.profile_name)),
+                None => return Ok(None),
+            },
+        };
+        let version = FederationVersion::from_str(&version)
+            .with_context(|| format!("Invalid federation_version '{}' in {}", version, source))?;
+        Ok(Some(version))
+    }
+}
+
 impl Display for ProfileOpt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", &self.profile_name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use crate::cli::Rover;
+
+    use super::*;
+
+    fn profile_default(cmd: &clap::Command, path: &[&str]) -> Option<String> {
+        let cmd = path.iter().fold(cmd, |cmd, name| {
+            cmd.find_subcommand(name)
+                .unwrap_or_else(|| panic!("no `{}` subcommand", name))
+        });
+        let arg = cmd
+            .get_arguments()
+            .find(|arg| arg.get_id() == "profile_name")?;
+        arg.get_default_values()
+            .first()
+            .map(|value| value.to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn it_defaults_to_the_project_profile() {
+        let cmd = ProfileOpt::with_default(Rover::command(), "staging");
+        assert_eq!(
+            profile_default(&cmd, &["graph", "fetch"]).as_deref(),
+            Some("staging")
+        );
+        assert_eq!(
+            profile_default(&cmd, &["subgraph", "publish"]).as_deref(),
+            Some("staging")
+        );
+        // `config show` reports where the profile came from, so it has no default
+        assert_eq!(profile_default(&cmd, &["config", "show"]), None);
+
+        let cmd = Rover::command();
+        assert_eq!(
+            profile_default(&cmd, &["graph", "fetch"]).as_deref(),
+            Some(DEFAULT_PROFILE)
+        );
+    }
+
+    #[test]
+    fn it_finds_the_profile_of_the_command_being_run() {
+        let name = |cmd: clap::Command, args: &[&str]| {
+            ProfileOpt::name_from_arg_matches(&cmd.get_matches_from(args))
+        };
+        assert_eq!(
+            name(
+                Rover::command(),
+                &["rover", "subgraph", "list", "my-graph", "--profile", "prod"]
+            )
+            .as_deref(),
+            Some("prod")
+        );
+        assert_eq!(
+            name(Rover::command(), &["rover", "subgraph", "list", "my-graph"]).as_deref(),
+            Some(DEFAULT_PROFILE)
+        );
+        assert_eq!(
+            name(
+                ProfileOpt::with_default(Rover::command(), "staging"),
+                &["rover", "subgraph", "list", "my-graph"]
+            )
+            .as_deref(),
+            Some("staging")
+        );
+        assert_eq!(name(Rover::command(), &["rover", "docs", "list"]), None);
+    }
+}
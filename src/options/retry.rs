@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use clap::Parser;
+use houston::RetrySettings;
+use reqwest::StatusCode;
+use rover_client::blocking::RetryPolicy;
+use serde::Serialize;
+
+use crate::{utils::env::RoverEnvKey, RoverError, RoverErrorSuggestion, RoverResult};
+
+/// Options controlling how failed requests to GraphOS Studio, graph endpoints, and
+/// plugin downloads are retried
+#[derive(Debug, Default, Clone, Serialize, Parser)]
+pub struct RetryOpts {
+    /// How many seconds to keep retrying a failed request for. Defaults to the `--client-timeout`.
+    #[arg(long = "retry-timeout", value_name = "SECONDS", global = true)]
+    retry_timeout: Option<u64>,
+
+    /// The most times to retry a failed request. By default, requests are retried until the `--retry-timeout`.
+    #[arg(long = "max-retries", value_name = "COUNT", global = true)]
+    max_retries: Option<u32>,
+
+    /// The HTTP statuses to retry requests for, separated by commas. Defaults to 429,500,502,503,504.
+    #[arg(
+        long = "retry-statuses",
+        value_name = "STATUS",
+        value_delimiter = ',',
+        value_parser = parse_status,
+        global = true
+    )]
+    #[serde(skip_serializing)]
+    retry_statuses: Option<Vec<StatusCode>>,
+}
+
+/// The retry settings from the environment, which are used where flags aren't set
+#[derive(Debug, Default)]
+pub struct RetryEnv {
+    pub retry_timeout: Option<String>,
+    pub max_retries: Option<String>,
+    pub retry_statuses: Option<String>,
+}
+
+impl RetryOpts {
+    /// The retry policy from these flags, then the environment, then `settings.toml`,
+    /// retrying until `default_max_elapsed_time` if none of them say otherwise
+    pub fn policy(
+        &self,
+        env: RetryEnv,
+        settings: &RetrySettings,
+        default_max_elapsed_time: Duration,
+    ) -> RoverResult<RetryPolicy> {
+        let retry_timeout = match (self.retry_timeout, env.retry_timeout) {
+            (Some(seconds), _) => Some(seconds),
+            (None, Some(seconds)) => Some(parse_env(RoverEnvKey::RetryTimeoutSeconds, &seconds)?),
+            (None, None) => settings.max_elapsed_seconds,
+        };
+        let max_retries = match (self.max_retries, env.max_retries) {
+            (Some(max_retries), _) => Some(max_retries),
+            (None, Some(max_retries)) => Some(parse_env(RoverEnvKey::MaxRetries, &max_retries)?),
+            (None, None) => settings.max_retries,
+        };
+        let retry_statuses = match (&self.retry_statuses, env.retry_statuses) {
+            (Some(statuses), _) => Some(statuses.clone()),
+            (None, Some(statuses)) => Some(
+                statuses
+                    .split(',')
+                    .map(parse_status)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| invalid_env(RoverEnvKey::RetryStatuses, e))?,
+            ),
+            (None, None) => settings
+                .statuses
+                .as_ref()
+                .map(|statuses| {
+                    statuses
+                        .iter()
+                        .map(|status| parse_status(&status.to_string()))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()
+                .map_err(|e| {
+                    RoverError::new(anyhow!("Invalid `statuses` in the `[retry]` settings: {e}"))
+                })?,
+        };
+
+        let mut policy = RetryPolicy::default().with_max_elapsed_time(
+            retry_timeout.map_or(default_max_elapsed_time, Duration::from_secs),
+        );
+        if let Some(max_retries) = max_retries {
+            policy = policy.with_max_retries(max_retries);
+        }
+        if let Some(retry_statuses) = retry_statuses {
+            policy = policy.with_retry_statuses(retry_statuses);
+        }
+        tracing::debug!(?policy);
+        Ok(policy)
+    }
+}
+
+fn parse_status(status: &str) -> Result<StatusCode, String> {
+    status
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .filter(|status| status.is_client_error() || status.is_server_error())
+        .ok_or_else(|| format!("'{status}' is not an HTTP error status between 400 and 599"))
+}
+
+fn parse_env<T: std::str::FromStr>(key: RoverEnvKey, value: &str) -> RoverResult<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e: T::Err| invalid_env(key, e))
+}
+
+fn invalid_env(key: RoverEnvKey, error: impl std::fmt::Display) -> RoverError {
+    let mut err = RoverError::new(anyhow!("Invalid value for ${key}: {error}"));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+        "Unset ${key}, or set it to a valid value."
+    )));
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_prefers_flags_then_env_then_settings() {
+        let settings = RetrySettings {
+            max_elapsed_seconds: Some(10),
+            max_retries: Some(1),
+            statuses: Some(vec![503]),
+        };
+        let default_max_elapsed_time = Duration::from_secs(30);
+
+        let policy = RetryOpts::default()
+            .policy(RetryEnv::default(), &settings, default_max_elapsed_time)
+            .unwrap();
+        assert_eq!(policy.max_elapsed_time(), Duration::from_secs(10));
+        assert_eq!(policy.max_retries(), Some(1));
+        assert_eq!(policy.retry_statuses(), [StatusCode::SERVICE_UNAVAILABLE]);
+
+        let env = RetryEnv {
+            retry_timeout: Some("20".to_string()),
+            max_retries: None,
+            retry_statuses: Some("429, 502".to_string()),
+        };
+        let opts = RetryOpts {
+            max_retries: Some(5),
+            ..Default::default()
+        };
+        let policy = opts
+            .policy(env, &settings, default_max_elapsed_time)
+            .unwrap();
+        assert_eq!(policy.max_elapsed_time(), Duration::from_secs(20));
+        assert_eq!(policy.max_retries(), Some(5));
+        assert_eq!(
+            policy.retry_statuses(),
+            [StatusCode::TOO_MANY_REQUESTS, StatusCode::BAD_GATEWAY]
+        );
+
+        let policy = RetryOpts::default()
+            .policy(
+                RetryEnv::default(),
+                &RetrySettings::default(),
+                default_max_elapsed_time,
+            )
+            .unwrap();
+        assert_eq!(policy, RetryPolicy::default());
+    }
+
+    #[test]
+    fn it_rejects_invalid_statuses() {
+        assert!(parse_status("503").is_ok());
+        assert!(parse_status("200").is_err());
+        assert!(parse_status("teapot").is_err());
+        let env = RetryEnv {
+            retry_statuses: Some("429,ok".to_string()),
+            ..Default::default()
+        };
+        assert!(RetryOpts::default()
+            .policy(env, &RetrySettings::default(), Duration::from_secs(30))
+            .is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use camino::Utf8Path;
 use clap::Parser;
 
 use crate::{utils::parsers::FileDescriptorType, RoverResult};
@@ -17,6 +18,14 @@ pub struct FileWithMetadata {
 }
 
 impl SchemaOpt {
+    /// The path of the schema file, or `None` if the schema is read from stdin
+    pub(crate) fn file_path(&self) -> Option<&Utf8Path> {
+        match &self.schema {
+            FileDescriptorType::Stdin => None,
+            FileDescriptorType::File(file_path) => Some(file_path),
+        }
+    }
+
     pub(crate) fn read_file_descriptor(
         &self,
         file_description: &str,
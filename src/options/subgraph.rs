@@ -12,11 +12,13 @@ use dialoguer::Input;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
+use rover_client::blocking::GraphQLTransport;
 #[cfg(feature = "composition-js")]
 use rover_std::{Emoji, Fs, Style};
 
 #[cfg(feature = "composition-js")]
 use crate::cli::Rover;
+use crate::options::IntrospectionAuthOpts;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Parser)]
 pub struct SubgraphOpt {
@@ -62,6 +64,22 @@ pub struct OptionalSubgraphOpts {
     #[serde(skip_serializing)]
     pub subgraph_polling_interval: u64,
 
+    /// How to send introspection requests to the running subgraph: `post` (the default),
+    /// `get` to send them as query parameters, or `websocket` to send them over GraphQL over WebSocket,
+    /// which can't be combined with proxy or TLS options.
+    /// Only used when the `--schema` argument is not passed.
+    #[arg(
+        long = "subgraph-transport",
+        default_value_t,
+        conflicts_with = "subgraph_schema_path"
+    )]
+    #[serde(skip_serializing)]
+    pub subgraph_transport: GraphQLTransport,
+
+    #[clap(flatten)]
+    #[serde(skip_serializing)]
+    pub subgraph_auth: IntrospectionAuthOpts,
+
     /// The number of times to retry a subgraph if an error is detected from it
     /// The default value is 0.
     #[arg(long = "subgraph-retries", short = 'r', default_value = "0")]
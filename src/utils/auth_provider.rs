@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use rover_client::RoverClientError;
+
+use crate::{
+    utils::{crash_bundle, shell},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+
+/// Tokens are replaced this long before they expire, so they don't expire in flight
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// How to obtain a token for introspection requests, from the `--auth-*` flags
+/// or from `introspection_auth` in `supergraph.yaml`
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthProviderConfig {
+    /// Request a token from an OAuth2 token endpoint with the client credentials grant
+    ClientCredentials {
+        token_url: String,
+        client_id: String,
+        #[serde(skip_serializing)]
+        client_secret: String,
+        #[serde(default)]
+        scope: Option<String>,
+        #[serde(default)]
+        audience: Option<String>,
+    },
+    /// Run a shell command that prints a token, or a JSON object with `access_token`
+    /// and `expires_in` fields
+    Command {
+        command: String,
+        /// How many seconds a token is valid for, when neither the command nor the token says
+        #[serde(default)]
+        expires_in: Option<u64>,
+    },
+}
+
+// written by hand so the client secret doesn't end up in logs
+impl fmt::Debug for AuthProviderConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthProviderConfig::ClientCredentials {
+                token_url,
+                client_id,
+                scope,
+                audience,
+                ..
+            } => f
+                .debug_struct("ClientCredentials")
+                .field("token_url", token_url)
+                .field("client_id", client_id)
+                .field("client_secret", &"[REDACTED]")
+                .field("scope", scope)
+                .field("audience", audience)
+                .finish(),
+            AuthProviderConfig::Command {
+                command,
+                expires_in,
+            } => f
+                .debug_struct("Command")
+                .field("command", command)
+                .field("expires_in", expires_in)
+                .finish(),
+        }
+    }
+}
+
+/// Mints tokens for introspection requests, reusing each token until it's about to
+/// expire or the endpoint rejects it. Clones share the same cached token.
+#[derive(Debug, Clone)]
+pub struct AuthProvider {
+    config: AuthProviderConfig,
+    client: Client,
+    token: Arc<Mutex<Option<Token>>>,
+}
+
+#[derive(Clone)]
+struct Token {
+    access_token: String,
+    expires_at: Option<Instant>,
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Token")
+            .field("access_token", &"[REDACTED]")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// The fields of an OAuth2 token response, which token commands may also print
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl Token {
+    /// A token without a known lifetime is kept until the endpoint rejects it
+    fn new(access_token: String, expires_in: Option<u64>) -> Token {
+        let expires_in = expires_in
+            .map(Duration::from_secs)
+            .or_else(|| jwt_expires_in(&access_token));
+        Token {
+            access_token,
+            expires_at: expires_in.map(|expires_in| Instant::now() + expires_in),
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() + EXPIRY_MARGIN < expires_at,
+            None => true,
+        }
+    }
+}
+
+impl AuthProvider {
+    pub fn new(config: AuthProviderConfig, client: Client) -> AuthProvider {
+        if let AuthProviderConfig::ClientCredentials { client_secret, .. } = &config {
+            crash_bundle::mask_secret(client_secret);
+        }
+        AuthProvider {
+            config,
+            client,
+            token: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The value of the `Authorization` header, minting a new token if needed
+    pub fn authorization(&self) -> RoverResult<String> {
+        let mut token = self.token.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(token) = token.as_ref().filter(|token| token.is_fresh()) {
+            return Ok(format!("Bearer {}", token.access_token));
+        }
+        tracing::debug!("minting a new introspection token");
+        let fresh = self.mint()?;
+        crash_bundle::mask_secret(&fresh.access_token);
+        let authorization = format!("Bearer {}", fresh.access_token);
+        *token = Some(fresh);
+        Ok(authorization)
+    }
+
+    /// Forgets the cached token, so the next request mints a new one
+    pub fn invalidate(&self) {
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    fn mint(&self) -> RoverResult<Token> {
+        match &self.config {
+            AuthProviderConfig::ClientCredentials {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+                audience,
+            } => {
+                let mut form = vec![
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id.as_str()),
+                    ("client_secret", client_secret.as_str()),
+                ];
+                if let Some(scope) = scope {
+                    form.push(("scope", scope.as_str()));
+                }
+                if let Some(audience) = audience {
+                    form.push(("audience", audience.as_str()));
+                }
+                let response = self
+                    .client
+                    .post(token_url)
+                    .form(&form)
+                    .send()
+                    .with_context(|| format!("Could not request a token from {}", token_url))?;
+                let status = response.status();
+                if !status.is_success() {
+                    let body = response.text().unwrap_or_default();
+                    let mut err = RoverError::new(anyhow!(
+                        "The token endpoint {} responded with {}: {}",
+                        token_url,
+                        status,
+                        body.trim()
+                    ));
+                    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                        "Check the client ID, client secret and scope used to request introspection tokens."
+                            .to_string(),
+                    ));
+                    return Err(err);
+                }
+                let response: TokenResponse = response.json().with_context(|| {
+                    format!("Could not read the token returned by {}", token_url)
+                })?;
+                Ok(Token::new(response.access_token, response.expires_in))
+            }
+            AuthProviderConfig::Command {
+                command,
+                expires_in,
+            } => {
+                let output = shell::command(command)
+                    .output()
+                    .with_context(|| format!("Could not run `{}`", command))?;
+                if !output.status.success() {
+                    let mut err = RoverError::new(anyhow!(
+                        "The token command `{}` failed with {}",
+                        command,
+                        output.status
+                    ));
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if !stderr.trim().is_empty() {
+                        err.set_suggestion(RoverErrorSuggestion::Adhoc(stderr.trim().to_string()));
+                    }
+                    return Err(err);
+                }
+                let stdout = String::from_utf8(output.stdout).with_context(|| {
+                    format!("The token command `{}` printed invalid UTF-8", command)
+                })?;
+                let stdout = stdout.trim();
+                if stdout.is_empty() {
+                    return Err(RoverError::new(anyhow!(
+                        "The token command `{}` did not print a token",
+                        command
+                    )));
+                }
+                Ok(match serde_json::from_str::<TokenResponse>(stdout) {
+                    Ok(response) => {
+                        Token::new(response.access_token, response.expires_in.or(*expires_in))
+                    }
+                    Err(_) => Token::new(stdout.to_string(), *expires_in),
+                })
+            }
+        }
+    }
+}
+
+/// Sends `request` with `headers`, plus an `Authorization` header from `auth` if there is one.
+/// If the endpoint rejects the token, a new one is minted and the request is sent once more.
+pub fn send_with_auth<T>(
+    auth: Option<&AuthProvider>,
+    headers: HashMap<String, String>,
+    request: impl Fn(HashMap<String, String>) -> Result<T, RoverClientError>,
+) -> RoverResult<T> {
+    for (name, value) in &headers {
+        if crash_bundle::is_credential_header(name) {
+            crash_bundle::mask_secret(value);
+        }
+    }
+    let Some(auth) = auth else {
+        return Ok(request(headers)?);
+    };
+    let authenticated = || -> RoverResult<HashMap<String, String>> {
+        let mut headers = headers.clone();
+        headers.retain(|key, _| !key.eq_ignore_ascii_case("authorization"));
+        headers.insert("Authorization".to_string(), auth.authorization()?);
+        Ok(headers)
+    };
+    match request(authenticated()?) {
+        Err(e) if is_unauthorized(&e) => {
+            tracing::debug!("the introspection token was rejected, minting a new one");
+            auth.invalidate();
+            Ok(request(authenticated()?)?)
+        }
+        result => Ok(result?),
+    }
+}
+
+fn is_unauthorized(error: &RoverClientError) -> bool {
+    matches!(
+        error,
+        RoverClientError::SendRequest { source, .. } if source.status() == Some(StatusCode::UNAUTHORIZED)
+    )
+}
+
+/// How long until a JWT's `exp` claim, if the token is a JWT that has one
+fn jwt_expires_in(token: &str) -> Option<Duration> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: u64,
+    }
+
+    let segments: Vec<&str> = token.split('.').collect();
+    let [_, payload, _] = segments.as_slice() else {
+        return None;
+    };
+    let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: Claims = serde_json::from_slice(&payload).ok()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(claims.exp.saturating_sub(now)))
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    fn jwt(exp: u64) -> String {
+        let claims = URL_SAFE_NO_PAD.encode(json!({ "exp": exp }).to_string());
+        format!("eyJhbGciOiJIUzI1NiJ9.{claims}.signature")
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn it_reads_jwt_expiry() {
+        let expires_in = jwt_expires_in(&jwt(now() + 3600)).unwrap();
+        assert!(expires_in > Duration::from_secs(3500));
+        assert_eq!(jwt_expires_in(&jwt(now() - 10)), Some(Duration::ZERO));
+        assert_eq!(jwt_expires_in("opaque-token"), None);
+        assert!(!Token::new(jwt(now() + 10), None).is_fresh());
+        assert!(Token::new("opaque-token".to_string(), None).is_fresh());
+    }
+
+    #[test]
+    fn it_keeps_the_client_secret_out_of_logs() {
+        let config = AuthProviderConfig::ClientCredentials {
+            token_url: "https://auth.example.com/oauth/token".to_string(),
+            client_id: "rover".to_string(),
+            client_secret: "hunter2".to_string(),
+            scope: None,
+            audience: None,
+        };
+        assert!(!format!("{:?}", config).contains("hunter2"));
+        assert!(!serde_json::to_string(&config).unwrap().contains("hunter2"));
+    }
+
+    #[test]
+    fn it_requests_client_credentials_tokens() {
+        let server = MockServer::start();
+        let token_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/oauth/token")
+                .body_contains("grant_type=client_credentials")
+                .body_contains("client_id=rover")
+                .body_contains("scope=introspect");
+            then.status(200).json_body(
+                json!({ "access_token": "minted", "token_type": "Bearer", "expires_in": 3600 }),
+            );
+        });
+        let auth = AuthProvider::new(
+            AuthProviderConfig::ClientCredentials {
+                token_url: server.url("/oauth/token"),
+                client_id: "rover".to_string(),
+                client_secret: "secret".to_string(),
+                scope: Some("introspect".to_string()),
+                audience: None,
+            },
+            Client::new(),
+        );
+
+        assert_eq!(auth.authorization().unwrap(), "Bearer minted");
+        assert_eq!(auth.authorization().unwrap(), "Bearer minted");
+        token_mock.assert_hits(1);
+
+        auth.invalidate();
+        auth.authorization().unwrap();
+        token_mock.assert_hits(2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_runs_token_commands() {
+        let auth = AuthProvider::new(
+            AuthProviderConfig::Command {
+                command: "echo ' token-from-command '".to_string(),
+                expires_in: None,
+            },
+            Client::new(),
+        );
+        assert_eq!(auth.authorization().unwrap(), "Bearer token-from-command");
+
+        let auth = AuthProvider::new(
+            AuthProviderConfig::Command {
+                command: r#"echo '{"access_token": "json-token", "expires_in": 5}'"#.to_string(),
+                expires_in: None,
+            },
+            Client::new(),
+        );
+        assert_eq!(auth.authorization().unwrap(), "Bearer json-token");
+        let token = auth.token.lock().unwrap().clone().unwrap();
+        assert!(!token.is_fresh());
+
+        let auth = AuthProvider::new(
+            AuthProviderConfig::Command {
+                command: "echo oops >&2; exit 3".to_string(),
+                expires_in: None,
+            },
+            Client::new(),
+        );
+        assert!(auth.authorization().is_err());
+    }
+}
@@ -1,23 +1,35 @@
 use core::fmt;
 use std::{io, str::FromStr, time::Duration};
 
-use crate::{options::ProfileOpt, PKG_NAME, PKG_VERSION};
-use anyhow::Result;
+use crate::{
+    options::ProfileOpt,
+    utils::{crash_bundle, project_config::ProjectConfig},
+    RoverError, RoverErrorSuggestion, RoverResult,
+};
+use crate::{PKG_NAME, PKG_VERSION};
+use anyhow::{anyhow, Result};
 
 use houston as config;
-use reqwest::blocking::Client;
-use rover_client::blocking::StudioClient;
+use reqwest::{blocking::Client, redirect, Certificate, Identity, Proxy};
+use rover_client::blocking::{FixtureMode, GraphQLTransport, RetryPolicy, StudioClient};
 
 use serde::Serialize;
 
 /// the Apollo graph registry's production API endpoint
-const STUDIO_PROD_API_ENDPOINT: &str = "https://api.apollographql.com/graphql";
+pub(crate) const STUDIO_PROD_API_ENDPOINT: &str = "https://api.apollographql.com/graphql";
 
-#[derive(Debug, Clone, Copy)]
+/// the API key sent when replaying fixtures without one, which never leaves the machine
+const REPLAY_API_KEY: &str = "replay";
+
+#[derive(Debug, Clone)]
 pub struct ClientBuilder {
     accept_invalid_certs: bool,
     accept_invalid_hostnames: bool,
     timeout: Option<std::time::Duration>,
+    proxy: Option<Proxy>,
+    root_certificates: Vec<Certificate>,
+    identity: Option<Identity>,
+    follow_redirects: bool,
 }
 
 impl Default for ClientBuilder {
@@ -32,6 +44,10 @@ impl ClientBuilder {
             accept_invalid_certs: false,
             accept_invalid_hostnames: false,
             timeout: None,
+            proxy: None,
+            root_certificates: Vec::new(),
+            identity: None,
+            follow_redirects: true,
         }
     }
 
@@ -56,17 +72,87 @@ impl ClientBuilder {
         }
     }
 
+    /// Sends requests through `proxy` instead of the system's proxy settings. `proxy`
+    /// needs its own `NoProxy`, since `NO_PROXY` isn't read once a proxy is set.
+    pub fn with_proxy(self, proxy: Proxy) -> Self {
+        Self {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    /// Trusts `certificate` as well as the system's root certificates
+    pub fn with_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Identifies with a client certificate when servers ask for one
+    pub fn with_identity(self, identity: Identity) -> Self {
+        Self {
+            identity: Some(identity),
+            ..self
+        }
+    }
+
+    /// Whether any proxy or TLS settings are set, which WebSocket connections don't use
+    fn has_connection_settings(&self) -> bool {
+        self.proxy.is_some()
+            || !self.root_certificates.is_empty()
+            || self.identity.is_some()
+            || self.accept_invalid_certs
+            || self.accept_invalid_hostnames
+    }
+
+    /// Checks that requests sent with `transport` use the proxy and TLS settings
+    /// that are set, since WebSocket connections are opened without them
+    pub(crate) fn check_transport(&self, transport: GraphQLTransport) -> RoverResult<()> {
+        if transport == GraphQLTransport::WebSocket && self.has_connection_settings() {
+            let mut err = RoverError::new(anyhow!(
+                "The `websocket` transport can't be used with a proxy, custom certificates, a client certificate, or `--insecure-accept-invalid-certs`/`--insecure-accept-invalid-hostnames`."
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Use the `post` or `get` transport, or unset the proxy and TLS options."
+                    .to_string(),
+            ));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Returns redirect responses instead of following them
+    pub fn without_redirects(self) -> Self {
+        Self {
+            follow_redirects: false,
+            ..self
+        }
+    }
+
     pub(crate) fn build(self) -> reqwest::Result<Client> {
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .gzip(true)
             .brotli(true)
             .danger_accept_invalid_certs(self.accept_invalid_certs)
             .danger_accept_invalid_hostnames(self.accept_invalid_hostnames)
-            .timeout(self.timeout)
-            .user_agent(format!("{}/{}", PKG_NAME, PKG_VERSION))
-            .build()?;
+            .timeout(
+                self.timeout
+                    .unwrap_or_else(|| ClientTimeout::default().get_duration()),
+            )
+            .user_agent(format!("{}/{}", PKG_NAME, PKG_VERSION));
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(identity) = self.identity {
+            builder = builder.identity(identity);
+        }
+        if !self.follow_redirects {
+            builder = builder.redirect(redirect::Policy::none());
+        }
 
-        Ok(client)
+        builder.build()
     }
 }
 
@@ -112,11 +198,13 @@ impl fmt::Display for ClientTimeout {
 pub struct StudioClientConfig {
     pub(crate) config: config::Config,
     client_builder: ClientBuilder,
-    uri: String,
+    override_endpoint: Option<String>,
     version: String,
     is_sudo: bool,
     client: Option<Client>,
-    pub(crate) retry_period: Option<Duration>,
+    pub(crate) retry_policy: RetryPolicy,
+    fixtures: Option<FixtureMode>,
+    pub(crate) project: Option<ProjectConfig>,
 }
 
 impl StudioClientConfig {
@@ -125,7 +213,7 @@ impl StudioClientConfig {
         config: config::Config,
         is_sudo: bool,
         client_builder: ClientBuilder,
-        retry_period: Option<Duration>,
+        retry_policy: RetryPolicy,
     ) -> StudioClientConfig {
         let version = if cfg!(debug_assertions) {
             format!("{} (dev)", PKG_VERSION)
@@ -134,39 +222,142 @@ impl StudioClientConfig {
         };
 
         StudioClientConfig {
-            uri: override_endpoint.unwrap_or_else(|| STUDIO_PROD_API_ENDPOINT.to_string()),
+            override_endpoint,
             config,
             version,
             client_builder,
             is_sudo,
             client: None,
-            retry_period,
+            retry_policy,
+            fixtures: None,
+            project: None,
         }
     }
 
+    /// Records responses from GraphOS Studio to fixture files, or replays them from fixture files
+    pub(crate) fn with_fixtures(self, fixtures: Option<FixtureMode>) -> StudioClientConfig {
+        StudioClientConfig { fixtures, ..self }
+    }
+
+    /// The `.rover.toml` that commands fall back to for settings that weren't passed
+    pub(crate) fn with_project_config(self, project: Option<ProjectConfig>) -> StudioClientConfig {
+        StudioClientConfig { project, ..self }
+    }
+
     pub(crate) fn get_reqwest_client(&self) -> reqwest::Result<Client> {
         if let Some(client) = &self.client {
             Ok(client.clone())
         } else {
             // we can use clone here freely since `reqwest` uses an `Arc` under the hood
-            self.client_builder.build()
+            self.client_builder.clone().build()
         }
     }
 
+    /// A client with the same settings that returns redirect responses instead of following them
+    pub(crate) fn get_no_redirect_reqwest_client(&self) -> reqwest::Result<Client> {
+        self.client_builder.clone().without_redirects().build()
+    }
+
+    /// How long requests can take before they're abandoned
+    pub(crate) fn client_timeout(&self) -> Option<Duration> {
+        self.client_builder.timeout
+    }
+
+    /// Checks that introspection requests sent with `transport` use the configured
+    /// proxy and TLS settings
+    pub(crate) fn check_transport(&self, transport: GraphQLTransport) -> RoverResult<()> {
+        self.client_builder.check_transport(transport)
+    }
+
     #[cfg(feature = "composition-js")]
     pub(crate) fn get_builder(&self) -> ClientBuilder {
-        self.client_builder
+        self.client_builder.clone()
     }
 
+    /// A client for GraphOS Studio that authenticates as `profile_opt`. When replaying
+    /// fixtures, a placeholder is used if the profile has no API key, since no requests are sent.
     pub fn get_authenticated_client(&self, profile_opt: &ProfileOpt) -> Result<StudioClient> {
-        let credential = config::Profile::get_credential(&profile_opt.profile_name, &self.config)?;
-        Ok(StudioClient::new(
+        let credential =
+            match config::Profile::get_credential(&profile_opt.profile_name, &self.config) {
+                Err(_) if matches!(self.fixtures, Some(FixtureMode::Replay(_))) => {
+                    config::Credential {
+                        api_key: REPLAY_API_KEY.to_string(),
+                        origin: config::CredentialOrigin::EnvVar,
+                    }
+                }
+                credential => credential?,
+            };
+        self.get_client_with_credential(credential, profile_opt)
+    }
+
+    /// A client for GraphOS Studio that authenticates with `credential` instead of the
+    /// profile's API key. Requests go to the profile's `registry_url` unless
+    /// `$APOLLO_REGISTRY_URL` or `.rover.toml` sets one.
+    pub fn get_client_with_credential(
+        &self,
+        credential: config::Credential,
+        profile_opt: &ProfileOpt,
+    ) -> Result<StudioClient> {
+        crash_bundle::mask_secret(&credential.api_key);
+        let uri = self
+            .override_endpoint
+            .clone()
+            .or(profile_opt.settings(&self.config)?.registry_url)
+            .unwrap_or_else(|| STUDIO_PROD_API_ENDPOINT.to_string());
+        let client = self.get_reqwest_client()?;
+
+        let client = StudioClient::new(
             credential,
-            &self.uri,
+            &uri,
             &self.version,
             self.is_sudo,
-            self.get_reqwest_client()?,
-            self.retry_period,
-        ))
+            client,
+            self.retry_policy.clone(),
+        );
+        Ok(match &self.fixtures {
+            Some(fixtures) => client.with_fixtures(fixtures.clone()),
+            None => client,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_replays_fixtures_without_an_api_key() {
+        let home = tempfile::tempdir().unwrap();
+        let home = camino::Utf8PathBuf::try_from(home.path().to_path_buf()).unwrap();
+        let config = config::Config::new(Some(&home), None).unwrap();
+        let profile_opt = ProfileOpt {
+            profile_name: "default".to_string(),
+        };
+        let client_config = StudioClientConfig::new(
+            None,
+            config,
+            false,
+            ClientBuilder::new(),
+            RetryPolicy::default(),
+        );
+        assert!(client_config
+            .get_authenticated_client(&profile_opt)
+            .is_err());
+
+        let client_config =
+            client_config.with_fixtures(Some(FixtureMode::Replay(home.join("fixtures"))));
+        assert!(client_config.get_authenticated_client(&profile_opt).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_websockets_with_tls_settings() {
+        let builder = ClientBuilder::new();
+        assert!(builder.check_transport(GraphQLTransport::WebSocket).is_ok());
+
+        let builder = builder.accept_invalid_certs(true);
+        assert!(builder.check_transport(GraphQLTransport::Post).is_ok());
+        assert!(builder
+            .check_transport(GraphQLTransport::WebSocket)
+            .is_err());
     }
 }
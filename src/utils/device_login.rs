@@ -0,0 +1,230 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// Where `rover config auth --login` starts a login when no other URL is configured
+pub const DEFAULT_LOGIN_URL: &str = "https://studio.apollographql.com/api/oauth";
+
+/// The client ID Rover identifies itself with to the login endpoints
+const CLIENT_ID: &str = "rover";
+
+/// The grant type for polling the token endpoint, from RFC 8628
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// How long to wait between polls when the endpoint doesn't say
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How much longer to wait between polls each time the endpoint asks Rover to slow down
+const SLOW_DOWN_INCREMENT: Duration = Duration::from_secs(5);
+
+/// Logs in with the OAuth 2.0 device authorization grant (RFC 8628): Rover asks for a
+/// code, the user approves it in a browser, and Rover polls until the approval arrives.
+/// The endpoints are `<login_url>/device_authorization` and `<login_url>/token`.
+#[derive(Debug, Clone)]
+pub struct DeviceLogin {
+    client: Client,
+    login_url: String,
+}
+
+/// A login waiting for the user to approve it
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceAuthorization {
+    device_code: String,
+
+    /// The code the user enters to approve the login
+    pub user_code: String,
+
+    /// Where the user enters the code
+    pub verification_uri: String,
+
+    /// Where the user can approve the login without entering the code, if the endpoint has one
+    pub verification_uri_complete: Option<String>,
+
+    expires_in: u64,
+
+    #[serde(default)]
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+impl DeviceLogin {
+    pub fn new(client: Client, login_url: &str) -> DeviceLogin {
+        DeviceLogin {
+            client,
+            login_url: login_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Asks the login endpoint for a code for the user to approve
+    pub fn start(&self) -> RoverResult<DeviceAuthorization> {
+        let url = format!("{}/device_authorization", self.login_url);
+        let response = self
+            .client
+            .post(&url)
+            .form(&[("client_id", CLIENT_ID)])
+            .send()
+            .with_context(|| format!("Could not start a login with {}", url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            return Err(RoverError::new(anyhow!(
+                "The login endpoint {} responded with {}: {}",
+                url,
+                status,
+                body.trim()
+            )));
+        }
+        Ok(response
+            .json()
+            .with_context(|| format!("Could not read the login code returned by {}", url))?)
+    }
+
+    /// Polls the token endpoint until the user approves or denies the login, or it expires,
+    /// and returns the API key it was approved with
+    pub fn wait_for_approval(&self, authorization: &DeviceAuthorization) -> RoverResult<String> {
+        let url = format!("{}/token", self.login_url);
+        let expires_at = Instant::now() + Duration::from_secs(authorization.expires_in);
+        let mut interval = authorization
+            .interval
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_POLL_INTERVAL);
+        loop {
+            thread::sleep(interval);
+            if Instant::now() >= expires_at {
+                return Err(expired());
+            }
+            let response = self
+                .client
+                .post(&url)
+                .form(&[
+                    ("grant_type", DEVICE_CODE_GRANT_TYPE),
+                    ("device_code", authorization.device_code.as_str()),
+                    ("client_id", CLIENT_ID),
+                ])
+                .send()
+                .with_context(|| format!("Could not check on the login with {}", url))?;
+            if response.status().is_success() {
+                let response: TokenResponse = response
+                    .json()
+                    .with_context(|| format!("Could not read the API key returned by {}", url))?;
+                return Ok(response.access_token);
+            }
+
+            let status = response.status();
+            let body = response.text().unwrap_or_default();
+            let error = serde_json::from_str::<TokenErrorResponse>(&body).map_err(|_| {
+                anyhow!(
+                    "The login endpoint {} responded with {}: {}",
+                    url,
+                    status,
+                    body.trim()
+                )
+            })?;
+            match error.error.as_str() {
+                "authorization_pending" => {}
+                "slow_down" => interval += SLOW_DOWN_INCREMENT,
+                "expired_token" => return Err(expired()),
+                "access_denied" => {
+                    return Err(RoverError::new(anyhow!("The login was denied.")));
+                }
+                other => {
+                    return Err(RoverError::new(anyhow!(
+                        "The login failed with {}{}",
+                        other,
+                        error
+                            .error_description
+                            .map(|description| format!(": {}", description))
+                            .unwrap_or_default()
+                    )))
+                }
+            }
+            tracing::debug!(?interval, "waiting for the login to be approved");
+        }
+    }
+}
+
+fn expired() -> RoverError {
+    let mut err = RoverError::new(anyhow!("The login code expired before it was approved."));
+    err.set_suggestion(RoverErrorSuggestion::Adhoc(
+        "Run the command again to get a new code.".to_string(),
+    ));
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    use super::*;
+
+    fn authorization(server: &MockServer) -> httpmock::Mock<'_> {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/oauth/device_authorization")
+                .body_contains("client_id=rover");
+            then.status(200).json_body(json!({
+                "device_code": "device-123",
+                "user_code": "WDJB-MJHT",
+                "verification_uri": "https://studio.example.com/device",
+                "expires_in": 60,
+                "interval": 0
+            }));
+        })
+    }
+
+    #[test]
+    fn it_logs_in_once_the_code_is_approved() {
+        let server = MockServer::start();
+        let authorization_mock = authorization(&server);
+        let approved = server.mock(|when, then| {
+            when.method(POST)
+                .path("/oauth/token")
+                .body_contains("device_code=device-123");
+            then.status(200)
+                .json_body(json!({ "access_token": "user:gh.foo:abc123", "token_type": "bearer" }));
+        });
+
+        let login = DeviceLogin::new(Client::new(), &server.url("/oauth/"));
+        let authorization = login.start().unwrap();
+        assert_eq!(authorization.user_code, "WDJB-MJHT");
+        assert_eq!(
+            login.wait_for_approval(&authorization).unwrap(),
+            "user:gh.foo:abc123"
+        );
+        authorization_mock.assert();
+        approved.assert();
+    }
+
+    #[test]
+    fn it_stops_when_the_login_is_denied() {
+        let server = MockServer::start();
+        authorization(&server);
+        let denied = server.mock(|when, then| {
+            when.method(POST).path("/oauth/token");
+            then.status(400)
+                .json_body(json!({ "error": "access_denied" }));
+        });
+
+        let login = DeviceLogin::new(Client::new(), &server.url("/oauth"));
+        let authorization = login.start().unwrap();
+        assert!(login.wait_for_approval(&authorization).is_err());
+        denied.assert_hits(1);
+    }
+}
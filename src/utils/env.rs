@@ -64,10 +64,10 @@ impl RoverEnv {
     }
 
     fn get_debug_value(key: RoverEnvKey, value: &str) -> String {
-        let value = if let RoverEnvKey::Key = key {
-            houston::mask_key(value)
-        } else {
-            value.to_string()
+        let value = match key {
+            RoverEnvKey::Key => houston::mask_key(value),
+            RoverEnvKey::CredentialsPassphrase => "[REDACTED]".to_string(),
+            _ => value.to_string(),
         };
 
         format!("${} = {}", key, value)
@@ -106,6 +106,15 @@ pub enum RoverEnvKey {
     VcsAuthor,
     NodeModulesBin,
     ChecksTimeoutSeconds,
+    RetryTimeoutSeconds,
+    MaxRetries,
+    RetryStatuses,
+    Proxy,
+    NoProxy,
+    CaCerts,
+    ClientCert,
+    ClientKey,
+    CredentialsPassphrase,
 }
 
 impl fmt::Display for RoverEnvKey {
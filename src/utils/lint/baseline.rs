@@ -0,0 +1,198 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context};
+use camino::Utf8Path;
+use rover_client::shared::{Diagnostic, LintResponse};
+use rover_std::Fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::LintLevel;
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// A record of known lint violations. Violations present in the baseline are
+/// reported as ignored, so only newly introduced violations fail a lint run.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LintBaseline {
+    version: u32,
+    violations: BTreeSet<BaselineEntry>,
+}
+
+/// A violation is identified by its rule, its schema coordinate and a hash of its
+/// message, so moving an element around in the file doesn't invalidate the baseline.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+struct BaselineEntry {
+    rule: String,
+    coordinate: String,
+    message_hash: String,
+}
+
+impl From<&Diagnostic> for BaselineEntry {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        BaselineEntry {
+            rule: diagnostic.rule.clone(),
+            coordinate: diagnostic.coordinate.clone(),
+            message_hash: format!("{:x}", Sha256::digest(diagnostic.message.as_bytes())),
+        }
+    }
+}
+
+impl LintBaseline {
+    /// Records every violation that isn't already ignored
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> LintBaseline {
+        LintBaseline {
+            version: BASELINE_VERSION,
+            violations: diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.level != LintLevel::Ignore.as_diagnostic_level())
+                .map(BaselineEntry::from)
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &Utf8Path) -> RoverResult<LintBaseline> {
+        if !path.exists() {
+            let mut err = RoverError::new(anyhow!("Could not find a lint baseline at {}", path));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Create the baseline by re-running this command with `--update-baseline`."
+                    .to_string(),
+            ));
+            return Err(err);
+        }
+        let contents = Fs::read_file(path)?;
+        let baseline: LintBaseline = serde_json::from_str(&contents)
+            .with_context(|| format!("Could not parse the lint baseline at {}", path))?;
+        if baseline.version != BASELINE_VERSION {
+            let mut err = RoverError::new(anyhow!(
+                "The lint baseline at {} has unsupported version {}",
+                path,
+                baseline.version
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Regenerate the baseline by re-running this command with `--update-baseline`."
+                    .to_string(),
+            ));
+            return Err(err);
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Utf8Path) -> RoverResult<()> {
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+        Fs::write_file(path, contents)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Reports the violations in `lint_response` that are in the baseline as ignored,
+    /// returning the number of baselined violations that no longer occur.
+    pub fn apply(&self, lint_response: &mut LintResponse) -> usize {
+        let mut seen = BTreeSet::new();
+        for diagnostic in lint_response.diagnostics.iter_mut() {
+            let entry = BaselineEntry::from(&*diagnostic);
+            if self.violations.contains(&entry) {
+                diagnostic.level = LintLevel::Ignore.as_diagnostic_level().to_string();
+                seen.insert(entry);
+            }
+        }
+        self.violations.len() - seen.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(level: &str, rule: &str, coordinate: &str, message: &str) -> Diagnostic {
+        Diagnostic {
+            level: level.to_string(),
+            message: message.to_string(),
+            coordinate: coordinate.to_string(),
+            start_line: 1,
+            start_byte_offset: 0,
+            end_byte_offset: 0,
+            rule: rule.to_string(),
+        }
+    }
+
+    fn lint_response(diagnostics: Vec<Diagnostic>) -> LintResponse {
+        LintResponse {
+            diagnostics,
+            file_name: "schema.graphql".to_string(),
+            proposed_schema: String::new(),
+        }
+    }
+
+    #[test]
+    fn it_ignores_baselined_violations() {
+        let existing = diagnostic(
+            "ERROR",
+            "DEPRECATED_DIRECTIVE_MISSING_REASON",
+            "Query.legacy",
+            "Deprecated field is missing a reason",
+        );
+        let baseline = LintBaseline::from_diagnostics(&[
+            existing.clone(),
+            diagnostic("WARNING", "TYPE_SUFFIX", "ProductType", "removed since"),
+            diagnostic("IGNORED", "TYPE_SUFFIX", "ignored", "not recorded"),
+        ]);
+        assert_eq!(baseline.len(), 2);
+
+        let introduced = diagnostic(
+            "ERROR",
+            "DEPRECATED_DIRECTIVE_MISSING_REASON",
+            "Query.other",
+            "Deprecated field is missing a reason",
+        );
+        let mut response = lint_response(vec![existing, introduced]);
+        let stale = baseline.apply(&mut response);
+
+        assert_eq!(stale, 1);
+        assert_eq!(response.diagnostics[0].level, "IGNORED");
+        assert_eq!(response.diagnostics[1].level, "ERROR");
+    }
+
+    #[test]
+    fn it_does_not_match_changed_messages() {
+        let baseline = LintBaseline::from_diagnostics(&[diagnostic(
+            "WARNING",
+            "TAG_DIRECTIVE_INVALID_NAME",
+            "Product",
+            "Tag name 'a b' is invalid",
+        )]);
+        let mut response = lint_response(vec![diagnostic(
+            "WARNING",
+            "TAG_DIRECTIVE_INVALID_NAME",
+            "Product",
+            "Tag name 'c d' is invalid",
+        )]);
+        baseline.apply(&mut response);
+        assert_eq!(response.diagnostics[0].level, "WARNING");
+    }
+
+    #[test]
+    fn it_round_trips_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = camino::Utf8PathBuf::try_from(dir.path().join("lint-baseline.json")).unwrap();
+        assert!(LintBaseline::load(&path).is_err());
+
+        let baseline = LintBaseline::from_diagnostics(&[diagnostic(
+            "WARNING",
+            "ENUM_SUFFIX",
+            "StatusEnum",
+            "Enum names should not end with Enum",
+        )]);
+        baseline.save(&path).unwrap();
+        assert_eq!(LintBaseline::load(&path).unwrap(), baseline);
+    }
+}
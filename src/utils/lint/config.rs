@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use regex::Regex;
+use serde::Deserialize;
+
+use rover_std::Fs;
+
+use super::rules::LintRule;
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The file `rover graph lint --offline` and `rover subgraph lint --offline` look for
+/// in the current directory when `--lint-config` is not passed.
+pub const DEFAULT_LINT_CONFIG_FILE: &str = ".rover-lint.yaml";
+
+/// The `@tag` name format federation accepts, used when `tag_name_pattern` is not configured.
+const DEFAULT_TAG_NAME_PATTERN: &str = r"^[a-zA-Z_][a-zA-Z0-9_\-/]*$";
+
+/// How a violation of a rule is reported.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    #[serde(alias = "ERROR")]
+    Error,
+    #[serde(alias = "WARNING")]
+    Warning,
+    #[serde(alias = "IGNORE")]
+    Ignore,
+}
+
+impl LintLevel {
+    /// The level as it appears in a [`rover_client::shared::Diagnostic`]
+    pub fn as_diagnostic_level(&self) -> &'static str {
+        match self {
+            LintLevel::Error => "ERROR",
+            LintLevel::Warning => "WARNING",
+            LintLevel::Ignore => "IGNORED",
+        }
+    }
+}
+
+/// Configuration for the local lint engine, read from a YAML file such as:
+///
+/// ```yaml
+/// rules:
+///   ALL_ELEMENTS_REQUIRE_DESCRIPTION: warning
+///   TYPE_SUFFIX: ignore
+/// tag_name_pattern: "^[a-z][a-z0-9-]*$"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// Overrides for the default level of each rule
+    #[serde(default)]
+    rules: HashMap<LintRule, LintLevel>,
+
+    /// The pattern every `@tag(name:)` must match
+    tag_name_pattern: Option<String>,
+}
+
+impl LintConfig {
+    /// Loads the configuration from `path` if one is passed, otherwise from
+    /// [`DEFAULT_LINT_CONFIG_FILE`] if it exists, falling back to the default rule set.
+    pub fn load(path: Option<&Utf8Path>) -> RoverResult<LintConfig> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let default_path = Utf8PathBuf::from(DEFAULT_LINT_CONFIG_FILE);
+                if !default_path.exists() {
+                    return Ok(LintConfig::default());
+                }
+                default_path
+            }
+        };
+        let contents = Fs::read_file(&path)
+            .with_context(|| format!("Could not read lint configuration from {}", path))?;
+        LintConfig::parse(&contents).map_err(|e| {
+            let mut err = RoverError::new(anyhow!(
+                "Could not parse lint configuration from {}: {}",
+                path,
+                e
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(format!(
+                "Make sure each entry under `rules` is one of [{}] set to `error`, `warning` or `ignore`.",
+                LintRule::all()
+                    .iter()
+                    .map(|rule| rule.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+            err
+        })
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<LintConfig> {
+        let config: LintConfig = serde_yaml::from_str(contents)?;
+        // validate the pattern up front rather than when the first @tag is linted
+        config.tag_name_pattern()?;
+        Ok(config)
+    }
+
+    /// The level a rule is reported at, taking overrides into account
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    pub fn tag_name_pattern(&self) -> anyhow::Result<Regex> {
+        let pattern = self
+            .tag_name_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_TAG_NAME_PATTERN);
+        Regex::new(pattern).with_context(|| format!("Invalid tag_name_pattern '{}'", pattern))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_uses_default_levels_when_unconfigured() {
+        let config = LintConfig::parse("{}").unwrap();
+        assert_eq!(
+            config.level(LintRule::FieldNamesShouldBeCamelCase),
+            LintLevel::Warning
+        );
+        assert_eq!(
+            config.level(LintRule::DeprecatedDirectiveMissingReason),
+            LintLevel::Error
+        );
+    }
+
+    #[test]
+    fn it_overrides_rule_levels() {
+        let config = LintConfig::parse(
+            "rules:\n  FIELD_NAMES_SHOULD_BE_CAMEL_CASE: error\n  TYPE_SUFFIX: IGNORE\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.level(LintRule::FieldNamesShouldBeCamelCase),
+            LintLevel::Error
+        );
+        assert_eq!(config.level(LintRule::TypeSuffix), LintLevel::Ignore);
+    }
+
+    #[test]
+    fn it_rejects_unknown_rules_and_bad_patterns() {
+        assert!(LintConfig::parse("rules:\n  NOT_A_RULE: error\n").is_err());
+        assert!(LintConfig::parse("tag_name_pattern: \"[\"\n").is_err());
+    }
+}
@@ -0,0 +1,718 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use anyhow::anyhow;
+use apollo_parser::cst::{self, CstNode};
+use apollo_parser::Parser;
+use camino::Utf8Path;
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
+use rover_client::shared::{Diagnostic, LintResponse};
+use rover_std::{Fs, Style};
+use serde_json::{json, Value};
+use similar::TextDiff;
+
+use super::{range, LintLevel, LintRule};
+use crate::{RoverError, RoverErrorSuggestion, RoverResult};
+
+/// The reason added to `@deprecated` directives missing one, matching the default in the GraphQL spec
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// The result of applying every mechanical fix available for a lint response
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LintFix {
+    pub file_name: String,
+    pub original_schema: String,
+    pub fixed_schema: String,
+    /// A description of each fix that was applied
+    pub fixes: Vec<String>,
+}
+
+impl LintFix {
+    pub fn is_empty(&self) -> bool {
+        self.fixes.is_empty()
+    }
+
+    /// A unified diff between the original and the fixed schema
+    pub fn diff(&self) -> String {
+        TextDiff::from_lines(&self.original_schema, &self.fixed_schema)
+            .unified_diff()
+            .header(&self.file_name, &self.file_name)
+            .to_string()
+    }
+
+    pub fn get_stdout(&self) -> String {
+        if self.is_empty() {
+            "No lint violations in this schema can be fixed automatically".to_string()
+        } else {
+            self.diff()
+        }
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({
+            "file_name": self.file_name,
+            "fixes": self.fixes,
+            "diff": self.diff(),
+        })
+    }
+
+    /// Writes the fixed schema back to the schema file it was read from
+    pub fn write(&self, path: Option<&Utf8Path>) -> RoverResult<()> {
+        let Some(path) = path else {
+            let mut err = RoverError::new(anyhow!(
+                "Fixes can only be written back to a schema read from a file"
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(
+                "Pass `--dry-run` to print the fixes as a diff instead.".to_string(),
+            ));
+            return Err(err);
+        };
+        Fs::write_file(path, &self.fixed_schema)?;
+        eprintln!(
+            "Applied {} fix(es) to {}",
+            self.fixes.len(),
+            Style::Path.paint(path.as_str())
+        );
+        Ok(())
+    }
+}
+
+/// Computes the fixes for every violation in `lint_response` with an unambiguous
+/// remedy, along with sorting `@tag` directives. Violations reported as ignored are left alone.
+///
+/// Diagnostics are matched to the schema by their coordinate, so this works for
+/// diagnostics from GraphOS as well as from the local lint engine.
+pub fn fix(lint_response: &LintResponse) -> RoverResult<LintFix> {
+    let schema = &lint_response.proposed_schema;
+    let tree = Parser::new(schema).parse();
+    if tree.errors().next().is_some() {
+        return Err(RoverError::new(anyhow!(
+            "Could not parse {} to apply lint fixes",
+            lint_response.file_name
+        )));
+    }
+    let document = tree.document();
+
+    let mut plan = FixPlan::new(&document);
+    for diagnostic in &lint_response.diagnostics {
+        if diagnostic.level != LintLevel::Ignore.as_diagnostic_level() {
+            plan.add(diagnostic);
+        }
+    }
+
+    let mut fixer = Fixer {
+        schema,
+        plan: &plan,
+        edits: Vec::new(),
+        sorted_tags: Vec::new(),
+    };
+    fixer.visit(&document);
+
+    let mut fixes = plan.descriptions.clone();
+    fixes.extend(
+        fixer
+            .sorted_tags
+            .iter()
+            .map(|coordinate| format!("Sorted the @tag directives on {}", coordinate)),
+    );
+
+    Ok(LintFix {
+        file_name: lint_response.file_name.clone(),
+        original_schema: schema.clone(),
+        fixed_schema: apply_edits(schema, fixer.edits),
+        fixes,
+    })
+}
+
+/// The fixes to make, keyed by the coordinate of the element they apply to
+#[derive(Default)]
+struct FixPlan {
+    /// Every coordinate defined in the schema, used to avoid renames that collide
+    coordinates: HashSet<String>,
+    /// Names referenced by federation field sets, which can't be safely renamed
+    field_set_names: HashSet<String>,
+    renames: HashMap<String, String>,
+    missing_reasons: HashSet<String>,
+    descriptions: Vec<String>,
+}
+
+impl FixPlan {
+    fn new(document: &cst::Document) -> Self {
+        let mut plan = FixPlan::default();
+        for node in document.syntax().descendants() {
+            if let Some(argument) = cst::Argument::cast(node) {
+                let is_field_set = argument
+                    .name()
+                    .is_some_and(|name| &*name.text() == "fields");
+                if let (true, Some(cst::Value::StringValue(fields))) =
+                    (is_field_set, argument.value())
+                {
+                    plan.field_set_names.extend(
+                        String::from(fields)
+                            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                            .filter(|name| !name.is_empty())
+                            .map(str::to_string),
+                    );
+                }
+            }
+        }
+        for definition in document.definitions() {
+            let Some((type_name, members)) = members(&definition) else {
+                continue;
+            };
+            plan.coordinates.insert(type_name.clone());
+            for (member, arguments) in members {
+                for argument in arguments {
+                    plan.coordinates
+                        .insert(format!("{}.{}({}:)", type_name, member, argument));
+                }
+                plan.coordinates.insert(format!("{}.{}", type_name, member));
+            }
+        }
+        plan
+    }
+
+    fn add(&mut self, diagnostic: &Diagnostic) {
+        let Ok(rule) = diagnostic.rule.parse::<LintRule>() else {
+            return;
+        };
+        let coordinate = diagnostic.coordinate.as_str();
+        match rule {
+            // each coordinate is only described once, however many times it was reported
+            LintRule::DeprecatedDirectiveMissingReason
+                if self.missing_reasons.insert(coordinate.to_string()) =>
+            {
+                self.descriptions
+                    .push(format!("Added a deprecation reason to {}", coordinate));
+            }
+            LintRule::TypeNamesShouldBePascalCase if !coordinate.contains('.') => {
+                let new_name = recase(coordinate, |name| name.to_upper_camel_case());
+                self.rename(coordinate, new_name.clone(), new_name);
+            }
+            LintRule::FieldNamesShouldBeCamelCase => {
+                if let Some((type_name, field)) = coordinate.split_once('.') {
+                    if self.field_set_names.contains(field) {
+                        return;
+                    }
+                    let new_name = recase(field, |name| name.to_lower_camel_case());
+                    self.rename(coordinate, format!("{}.{}", type_name, new_name), new_name);
+                }
+            }
+            LintRule::EnumValuesShouldBeScreamingSnakeCase => {
+                if let Some((type_name, value)) = coordinate.split_once('.') {
+                    let new_name = recase(value, |name| name.to_shouty_snake_case());
+                    self.rename(coordinate, format!("{}.{}", type_name, new_name), new_name);
+                }
+            }
+            LintRule::InputArgumentNamesShouldBeCamelCase => {
+                let argument = coordinate
+                    .strip_suffix(":)")
+                    .and_then(|coordinate| coordinate.split_once('('));
+                if let Some((field_coordinate, argument)) = argument {
+                    let new_name = recase(argument, |name| name.to_lower_camel_case());
+                    self.rename(
+                        coordinate,
+                        format!("{}({}:)", field_coordinate, new_name),
+                        new_name,
+                    );
+                }
+            }
+            // the remaining rules either have no single correct fix, or, like
+            // renaming types to drop a suffix, are too invasive to apply mechanically
+            _ => (),
+        }
+    }
+
+    fn rename(&mut self, coordinate: &str, new_coordinate: String, new_name: String) {
+        if new_coordinate == coordinate
+            || new_name.is_empty()
+            || self.coordinates.contains(&new_coordinate)
+            || self.renames.contains_key(coordinate)
+        {
+            return;
+        }
+        self.descriptions
+            .push(format!("Renamed {} to {}", coordinate, new_coordinate));
+        self.coordinates.insert(new_coordinate);
+        self.renames.insert(coordinate.to_string(), new_name);
+    }
+}
+
+/// The name of a type along with its members and their argument names
+type Members = (String, Vec<(String, Vec<String>)>);
+
+fn members(definition: &cst::Definition) -> Option<Members> {
+    fn fields(fields: Option<cst::FieldsDefinition>) -> Vec<(String, Vec<String>)> {
+        fields
+            .into_iter()
+            .flat_map(|fields| fields.field_definitions())
+            .filter_map(|field| {
+                let arguments = field
+                    .arguments_definition()
+                    .into_iter()
+                    .flat_map(|arguments| arguments.input_value_definitions())
+                    .filter_map(|argument| argument.name())
+                    .map(|name| name.text().to_string())
+                    .collect();
+                Some((field.name()?.text().to_string(), arguments))
+            })
+            .collect()
+    }
+    fn input_fields(fields: Option<cst::InputFieldsDefinition>) -> Vec<(String, Vec<String>)> {
+        fields
+            .into_iter()
+            .flat_map(|fields| fields.input_value_definitions())
+            .filter_map(|field| Some((field.name()?.text().to_string(), Vec::new())))
+            .collect()
+    }
+    fn enum_values(values: Option<cst::EnumValuesDefinition>) -> Vec<(String, Vec<String>)> {
+        values
+            .into_iter()
+            .flat_map(|values| values.enum_value_definitions())
+            .filter_map(|value| Some((value.enum_value()?.name()?.text().to_string(), Vec::new())))
+            .collect()
+    }
+
+    let (name, members) = match definition {
+        cst::Definition::ObjectTypeDefinition(def) => (def.name(), fields(def.fields_definition())),
+        cst::Definition::ObjectTypeExtension(ext) => (ext.name(), fields(ext.fields_definition())),
+        cst::Definition::InterfaceTypeDefinition(def) => {
+            (def.name(), fields(def.fields_definition()))
+        }
+        cst::Definition::InterfaceTypeExtension(ext) => {
+            (ext.name(), fields(ext.fields_definition()))
+        }
+        cst::Definition::InputObjectTypeDefinition(def) => {
+            (def.name(), input_fields(def.input_fields_definition()))
+        }
+        cst::Definition::InputObjectTypeExtension(ext) => {
+            (ext.name(), input_fields(ext.input_fields_definition()))
+        }
+        cst::Definition::EnumTypeDefinition(def) => {
+            (def.name(), enum_values(def.enum_values_definition()))
+        }
+        cst::Definition::EnumTypeExtension(ext) => {
+            (ext.name(), enum_values(ext.enum_values_definition()))
+        }
+        cst::Definition::UnionTypeDefinition(def) => (def.name(), Vec::new()),
+        cst::Definition::ScalarTypeDefinition(def) => (def.name(), Vec::new()),
+        _ => return None,
+    };
+    Some((name?.text().to_string(), members))
+}
+
+struct Edit {
+    range: Range<usize>,
+    replacement: String,
+}
+
+/// Walks the schema and turns a [`FixPlan`] into text edits
+struct Fixer<'a> {
+    schema: &'a str,
+    plan: &'a FixPlan,
+    edits: Vec<Edit>,
+    sorted_tags: Vec<String>,
+}
+
+impl<'a> Fixer<'a> {
+    fn visit(&mut self, document: &cst::Document) {
+        for node in document.syntax().descendants() {
+            if let Some(name) = cst::NamedType::cast(node).and_then(|named| named.name()) {
+                self.rename(&name.text(), &name);
+            }
+        }
+
+        for definition in document.definitions() {
+            match definition {
+                cst::Definition::ObjectTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                    self.fields(def.name(), def.fields_definition());
+                }
+                cst::Definition::ObjectTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                    self.fields(ext.name(), ext.fields_definition());
+                }
+                cst::Definition::InterfaceTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                    self.fields(def.name(), def.fields_definition());
+                }
+                cst::Definition::InterfaceTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                    self.fields(ext.name(), ext.fields_definition());
+                }
+                cst::Definition::InputObjectTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                    self.input_fields(def.name(), def.input_fields_definition());
+                }
+                cst::Definition::InputObjectTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                    self.input_fields(ext.name(), ext.input_fields_definition());
+                }
+                cst::Definition::EnumTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                    self.enum_values(def.name(), def.enum_values_definition());
+                }
+                cst::Definition::EnumTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                    self.enum_values(ext.name(), ext.enum_values_definition());
+                }
+                cst::Definition::UnionTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                }
+                cst::Definition::UnionTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                }
+                cst::Definition::ScalarTypeDefinition(def) => {
+                    self.type_name(def.name(), def.directives());
+                }
+                cst::Definition::ScalarTypeExtension(ext) => {
+                    self.type_name(ext.name(), ext.directives());
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn type_name(&mut self, name: Option<cst::Name>, directives: Option<cst::Directives>) {
+        if let Some(name) = name {
+            let type_name = name.text().to_string();
+            self.rename(&type_name, &name);
+            self.directives(&type_name, directives);
+        }
+    }
+
+    fn fields(&mut self, type_name: Option<cst::Name>, fields: Option<cst::FieldsDefinition>) {
+        let (Some(type_name), Some(fields)) = (type_name, fields) else {
+            return;
+        };
+        for field in fields.field_definitions() {
+            let Some(name) = field.name() else {
+                continue;
+            };
+            let coordinate = format!("{}.{}", type_name.text(), name.text());
+            self.rename(&coordinate, &name);
+            self.directives(&coordinate, field.directives());
+            for argument in field
+                .arguments_definition()
+                .into_iter()
+                .flat_map(|arguments| arguments.input_value_definitions())
+            {
+                if let Some(argument_name) = argument.name() {
+                    let argument_coordinate = format!("{}({}:)", coordinate, argument_name.text());
+                    self.input_value(&argument_coordinate, &argument_name, &argument);
+                }
+            }
+        }
+    }
+
+    fn input_fields(
+        &mut self,
+        type_name: Option<cst::Name>,
+        fields: Option<cst::InputFieldsDefinition>,
+    ) {
+        let (Some(type_name), Some(fields)) = (type_name, fields) else {
+            return;
+        };
+        for field in fields.input_value_definitions() {
+            if let Some(name) = field.name() {
+                let coordinate = format!("{}.{}", type_name.text(), name.text());
+                self.input_value(&coordinate, &name, &field);
+            }
+        }
+    }
+
+    fn input_value(
+        &mut self,
+        coordinate: &str,
+        name: &cst::Name,
+        value: &cst::InputValueDefinition,
+    ) {
+        self.rename(coordinate, name);
+        self.directives(coordinate, value.directives());
+
+        // keep default values pointing at enum values that were renamed
+        let enum_type = value.ty().and_then(named_type);
+        let default_value = value.default_value().and_then(|default| default.value());
+        if let (Some(enum_type), Some(default_value)) = (enum_type, default_value) {
+            let values: Vec<cst::Value> = match default_value {
+                cst::Value::ListValue(list) => list.values().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                if let Some(name) = enum_value_name(value) {
+                    self.rename(&format!("{}.{}", enum_type, name.text()), &name);
+                }
+            }
+        }
+    }
+
+    fn enum_values(
+        &mut self,
+        type_name: Option<cst::Name>,
+        values: Option<cst::EnumValuesDefinition>,
+    ) {
+        let (Some(type_name), Some(values)) = (type_name, values) else {
+            return;
+        };
+        for value in values.enum_value_definitions() {
+            if let Some(name) = value.enum_value().and_then(|value| value.name()) {
+                let coordinate = format!("{}.{}", type_name.text(), name.text());
+                self.rename(&coordinate, &name);
+                self.directives(&coordinate, value.directives());
+            }
+        }
+    }
+
+    fn directives(&mut self, coordinate: &str, directives: Option<cst::Directives>) {
+        let Some(directives) = directives else {
+            return;
+        };
+        let directives: Vec<cst::Directive> = directives.directives().collect();
+
+        if self.plan.missing_reasons.contains(coordinate) {
+            if let Some(deprecated) = directives
+                .iter()
+                .find(|directive| directive_name(directive).as_deref() == Some("deprecated"))
+            {
+                self.deprecation_reason(deprecated);
+            }
+        }
+
+        let mut sorted = false;
+        for run in directives.split(|directive| directive_name(directive).as_deref() != Some("tag"))
+        {
+            let (Some(first), Some(last)) = (run.first(), run.last()) else {
+                continue;
+            };
+            let mut tags: Vec<(String, String)> = run
+                .iter()
+                .map(|tag| {
+                    (
+                        tag_name(tag).unwrap_or_default(),
+                        self.schema[range(tag)].to_string(),
+                    )
+                })
+                .collect();
+            if tags.windows(2).all(|pair| pair[0].0 <= pair[1].0) {
+                continue;
+            }
+            tags.sort();
+            self.edits.push(Edit {
+                range: range(first).start..range(last).end,
+                replacement: tags
+                    .into_iter()
+                    .map(|(_, text)| text)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+            sorted = true;
+        }
+        if sorted {
+            self.sorted_tags.push(coordinate.to_string());
+        }
+    }
+
+    fn deprecation_reason(&mut self, deprecated: &cst::Directive) {
+        let reason = format!("\"{}\"", DEFAULT_DEPRECATION_REASON);
+        let existing = deprecated.arguments().and_then(|arguments| {
+            arguments.arguments().find(|argument| {
+                argument
+                    .name()
+                    .is_some_and(|name| &*name.text() == "reason")
+            })
+        });
+        match (
+            existing.and_then(|argument| argument.value()),
+            deprecated.name(),
+        ) {
+            // an empty or null reason is replaced in place
+            (Some(value), _) => self.edits.push(Edit {
+                range: range(&value),
+                replacement: reason,
+            }),
+            (None, Some(name)) if deprecated.arguments().is_none() => self.edits.push(Edit {
+                range: range(&name).end..range(&name).end,
+                replacement: format!("(reason: {})", reason),
+            }),
+            _ => (),
+        }
+    }
+
+    fn rename(&mut self, coordinate: &str, name: &cst::Name) {
+        if let Some(new_name) = self.plan.renames.get(coordinate) {
+            self.edits.push(Edit {
+                range: range(name),
+                replacement: new_name.clone(),
+            });
+        }
+    }
+}
+
+fn directive_name(directive: &cst::Directive) -> Option<String> {
+    directive.name().map(|name| name.text().to_string())
+}
+
+fn tag_name(tag: &cst::Directive) -> Option<String> {
+    tag.arguments()?
+        .arguments()
+        .find(|argument| argument.name().is_some_and(|name| &*name.text() == "name"))
+        .and_then(|argument| match argument.value()? {
+            cst::Value::StringValue(name) => Some(String::from(name)),
+            _ => None,
+        })
+}
+
+fn named_type(ty: cst::Type) -> Option<String> {
+    match ty {
+        cst::Type::NamedType(named) => Some(named.name()?.text().to_string()),
+        cst::Type::ListType(list) => named_type(list.ty()?),
+        cst::Type::NonNullType(non_null) => match (non_null.named_type(), non_null.list_type()) {
+            (Some(named), _) => Some(named.name()?.text().to_string()),
+            (None, Some(list)) => named_type(list.ty()?),
+            (None, None) => None,
+        },
+    }
+}
+
+fn enum_value_name(value: cst::Value) -> Option<cst::Name> {
+    match value {
+        cst::Value::EnumValue(value) => value.name(),
+        _ => None,
+    }
+}
+
+/// Converts the casing of a name, keeping any leading underscores
+fn recase(name: &str, convert: impl Fn(&str) -> String) -> String {
+    let trimmed = name.trim_start_matches('_');
+    format!(
+        "{}{}",
+        &name[..name.len() - trimmed.len()],
+        convert(trimmed)
+    )
+}
+
+/// Applies non-overlapping edits, dropping any edit that overlaps an earlier one
+fn apply_edits(schema: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    let mut fixed = String::with_capacity(schema.len());
+    let mut position = 0;
+    for edit in edits {
+        if edit.range.start < position {
+            continue;
+        }
+        fixed.push_str(&schema[position..edit.range.start]);
+        fixed.push_str(&edit.replacement);
+        position = edit.range.end;
+    }
+    fixed.push_str(&schema[position..]);
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::utils::lint::{lint, LintConfig};
+
+    fn fix_schema(schema: &str) -> LintFix {
+        let lint_response = lint(
+            &LintConfig::default(),
+            "schema.graphql".to_string(),
+            schema.to_string(),
+        )
+        .unwrap();
+        fix(&lint_response).unwrap()
+    }
+
+    #[test]
+    fn it_fixes_casing_and_references() {
+        let lint_fix = fix_schema(indoc! {r#"
+            type Query {
+              product_by_id(Product_Id: ID!, status: stock_status = inStock): product
+            }
+
+            type product {
+              id: ID!
+            }
+
+            enum stock_status {
+              inStock
+              OUT_OF_STOCK
+            }
+        "#});
+
+        assert_eq!(
+            lint_fix.fixed_schema,
+            indoc! {r#"
+                type Query {
+                  productById(productId: ID!, status: StockStatus = IN_STOCK): Product
+                }
+
+                type Product {
+                  id: ID!
+                }
+
+                enum StockStatus {
+                  IN_STOCK
+                  OUT_OF_STOCK
+                }
+            "#}
+        );
+        assert_eq!(lint_fix.fixes.len(), 5);
+    }
+
+    #[test]
+    fn it_adds_missing_deprecation_reasons() {
+        let lint_fix = fix_schema(indoc! {r#"
+            type Query {
+              legacy: String @deprecated
+              older: String @deprecated(reason: "")
+            }
+        "#});
+
+        assert_eq!(
+            lint_fix.fixed_schema,
+            indoc! {r#"
+                type Query {
+                  legacy: String @deprecated(reason: "No longer supported")
+                  older: String @deprecated(reason: "No longer supported")
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn it_sorts_tags() {
+        let lint_fix = fix_schema(indoc! {r#"
+            type Query @tag(name: "public") @tag(name: "internal") @key(fields: "id") {
+              id: ID!
+            }
+        "#});
+
+        assert_eq!(
+            lint_fix.fixed_schema,
+            indoc! {r#"
+                type Query @tag(name: "internal") @tag(name: "public") @key(fields: "id") {
+                  id: ID!
+                }
+            "#}
+        );
+        assert_eq!(lint_fix.fixes, vec!["Sorted the @tag directives on Query"]);
+    }
+
+    #[test]
+    fn it_skips_ambiguous_renames() {
+        let schema = indoc! {r#"
+            type Product @key(fields: "product_id") {
+              product_id: ID!
+              productName: String
+              product_name: String
+            }
+        "#};
+        let lint_fix = fix_schema(schema);
+
+        assert!(lint_fix.is_empty());
+        assert_eq!(lint_fix.fixed_schema, schema);
+    }
+}
@@ -0,0 +1,232 @@
+//! An offline lint engine implementing a core set of GraphOS's schema linting rules.
+//!
+//! It produces the same [`LintResponse`] GraphOS linting does, so diagnostics are
+//! rendered and serialized identically regardless of where the schema was linted.
+
+mod baseline;
+mod config;
+mod fix;
+mod rules;
+
+pub use baseline::LintBaseline;
+pub use config::{LintConfig, LintLevel, DEFAULT_LINT_CONFIG_FILE};
+pub use fix::{fix, LintFix};
+pub use rules::LintRule;
+
+use std::ops::Range;
+
+use anyhow::anyhow;
+use apollo_parser::cst::CstNode;
+use apollo_parser::{Parser, SyntaxKind};
+use rover_client::shared::{Diagnostic, LintResponse};
+use rover_client::RoverClientError;
+
+use crate::{RoverError, RoverResult};
+
+use rules::RuleVisitor;
+
+/// Lints `proposed_schema` against the configured rules
+pub fn lint(
+    config: &LintConfig,
+    file_name: String,
+    proposed_schema: String,
+) -> RoverResult<LintResponse> {
+    Ok(LintResponse {
+        diagnostics: diagnostics(config, &proposed_schema)?,
+        file_name,
+        proposed_schema,
+    })
+}
+
+/// Like GraphOS linting, returns a [`RoverClientError::LintFailures`] if any diagnostic is an error.
+pub fn fail_on_errors(lint_response: LintResponse) -> RoverResult<LintResponse> {
+    if lint_response
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.level == LintLevel::Error.as_diagnostic_level())
+    {
+        Err(RoverClientError::LintFailures { lint_response }.into())
+    } else {
+        Ok(lint_response)
+    }
+}
+
+fn diagnostics(config: &LintConfig, proposed_schema: &str) -> RoverResult<Vec<Diagnostic>> {
+    let tree = Parser::new(proposed_schema).parse();
+    let errors = tree
+        .errors()
+        .map(|error| format!("{} at byte {}", error.message(), error.index()))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(RoverError::new(anyhow!(
+            "Could not parse the schema for linting:\n{}",
+            errors.join("\n")
+        )));
+    }
+
+    let tag_name_pattern = config.tag_name_pattern()?;
+    let diagnostics = RuleVisitor::new(&tag_name_pattern)
+        .visit(&tree.document())
+        .into_iter()
+        .filter_map(|violation| {
+            let level = config.level(violation.rule);
+            if level == LintLevel::Ignore {
+                return None;
+            }
+            Some(Diagnostic {
+                level: level.as_diagnostic_level().to_string(),
+                message: violation.message,
+                coordinate: violation.coordinate,
+                start_line: line_number(proposed_schema, violation.start_byte_offset),
+                start_byte_offset: violation.start_byte_offset,
+                end_byte_offset: violation.end_byte_offset,
+                rule: violation.rule.to_string(),
+            })
+        })
+        .collect();
+    Ok(diagnostics)
+}
+
+/// The byte range of a node, excluding the whitespace, commas and comments the
+/// parser attaches to it
+fn range(node: &impl CstNode) -> Range<usize> {
+    let mut tokens = node
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                SyntaxKind::WHITESPACE | SyntaxKind::COMMA | SyntaxKind::COMMENT
+            )
+        });
+    let full_range = node.syntax().text_range();
+    let first = tokens.next().map(|token| token.text_range());
+    let last = tokens.last().map(|token| token.text_range()).or(first);
+    match (first, last) {
+        (Some(first), Some(last)) => first.start().into()..last.end().into(),
+        _ => full_range.start().into()..full_range.end().into(),
+    }
+}
+
+/// The 1-indexed line containing `byte_offset`, as GraphOS reports it
+fn line_number(schema: &str, byte_offset: usize) -> i64 {
+    schema[..byte_offset].matches('\n').count() as i64 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::RoverErrorCode;
+
+    #[test]
+    fn it_reports_core_rule_violations() {
+        let schema = indoc! {r#"
+            type Query {
+              product_by_id(ProductId: ID!): ProductType @deprecated
+            }
+
+            type ProductType @tag(name: "has spaces") {
+              id: ID!
+            }
+
+            enum StockEnum {
+              inStock
+            }
+        "#};
+
+        let diagnostics = diagnostics(&LintConfig::default(), schema).unwrap();
+
+        let rules: Vec<(&str, &str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.rule.as_str(), d.coordinate.as_str(), d.level.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (
+                    "FIELD_NAMES_SHOULD_BE_CAMEL_CASE",
+                    "Query.product_by_id",
+                    "WARNING"
+                ),
+                (
+                    "INPUT_ARGUMENT_NAMES_SHOULD_BE_CAMEL_CASE",
+                    "Query.product_by_id(ProductId:)",
+                    "WARNING"
+                ),
+                (
+                    "DEPRECATED_DIRECTIVE_MISSING_REASON",
+                    "Query.product_by_id",
+                    "ERROR"
+                ),
+                ("TYPE_SUFFIX", "ProductType", "WARNING"),
+                ("TAG_DIRECTIVE_INVALID_NAME", "ProductType", "ERROR"),
+                ("ENUM_SUFFIX", "StockEnum", "WARNING"),
+                (
+                    "ENUM_VALUES_SHOULD_BE_SCREAMING_SNAKE_CASE",
+                    "StockEnum.inStock",
+                    "WARNING"
+                ),
+            ]
+        );
+
+        let field = &diagnostics[0];
+        assert_eq!(field.start_line, 2);
+        assert_eq!(
+            &schema[field.start_byte_offset..field.end_byte_offset],
+            "product_by_id"
+        );
+    }
+
+    #[test]
+    fn it_fails_when_a_rule_is_violated_at_error_level() {
+        let schema = "type Query { legacy: String @deprecated }";
+
+        let response = lint(
+            &LintConfig::default(),
+            "schema.graphql".to_string(),
+            schema.to_string(),
+        )
+        .unwrap();
+        let error = fail_on_errors(response).unwrap_err();
+
+        assert_eq!(error.code(), Some(RoverErrorCode::E042));
+    }
+
+    #[test]
+    fn it_respects_configured_levels() {
+        let config = LintConfig::parse(indoc! {r#"
+            rules:
+              ALL_ELEMENTS_REQUIRE_DESCRIPTION: warning
+              FIELD_NAMES_SHOULD_BE_CAMEL_CASE: ignore
+        "#})
+        .unwrap();
+        let schema = indoc! {r#"
+            "The root query"
+            type Query {
+              product_by_id: String
+            }
+        "#};
+
+        let response = lint(&config, "schema.graphql".to_string(), schema.to_string()).unwrap();
+
+        assert_eq!(response.diagnostics.len(), 1);
+        assert_eq!(
+            response.diagnostics[0].rule,
+            "ALL_ELEMENTS_REQUIRE_DESCRIPTION"
+        );
+        assert_eq!(response.diagnostics[0].coordinate, "Query.product_by_id");
+    }
+
+    #[test]
+    fn it_errors_on_unparseable_schemas() {
+        let response = lint(
+            &LintConfig::default(),
+            "schema.graphql".to_string(),
+            "type Query {".to_string(),
+        );
+        assert!(response.is_err());
+    }
+}
@@ -0,0 +1,481 @@
+use apollo_parser::cst::{self, CstNode};
+use regex::Regex;
+use serde::Deserialize;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::config::LintLevel;
+use super::range;
+
+/// The rules implemented by the local lint engine. Names match the rules of
+/// the same purpose in GraphOS linting, so configuration carries over.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Display, EnumIter, EnumString)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum LintRule {
+    FieldNamesShouldBeCamelCase,
+    InputArgumentNamesShouldBeCamelCase,
+    TypeNamesShouldBePascalCase,
+    EnumValuesShouldBeScreamingSnakeCase,
+    AllElementsRequireDescription,
+    DeprecatedDirectiveMissingReason,
+    TagDirectiveInvalidName,
+    TypeSuffix,
+    InputTypeSuffix,
+    EnumSuffix,
+}
+
+impl LintRule {
+    pub fn all() -> Vec<LintRule> {
+        use strum::IntoEnumIterator;
+        LintRule::iter().collect()
+    }
+
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            LintRule::DeprecatedDirectiveMissingReason | LintRule::TagDirectiveInvalidName => {
+                LintLevel::Error
+            }
+            LintRule::AllElementsRequireDescription => LintLevel::Ignore,
+            _ => LintLevel::Warning,
+        }
+    }
+}
+
+/// A single rule violation found in a schema, before a level is applied to it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Violation {
+    pub(crate) rule: LintRule,
+    pub(crate) coordinate: String,
+    pub(crate) message: String,
+    pub(crate) start_byte_offset: usize,
+    pub(crate) end_byte_offset: usize,
+}
+
+#[derive(Clone, Copy)]
+enum TypeKind {
+    Object,
+    Interface,
+    Input,
+    Enum,
+    Union,
+    Scalar,
+}
+
+/// Walks every type system definition in a document and records the rule violations it finds
+pub(crate) struct RuleVisitor<'a> {
+    tag_name_pattern: &'a Regex,
+    violations: Vec<Violation>,
+}
+
+impl<'a> RuleVisitor<'a> {
+    pub(crate) fn new(tag_name_pattern: &'a Regex) -> Self {
+        Self {
+            tag_name_pattern,
+            violations: Vec::new(),
+        }
+    }
+
+    pub(crate) fn visit(mut self, document: &cst::Document) -> Vec<Violation> {
+        for definition in document.definitions() {
+            match definition {
+                cst::Definition::ObjectTypeDefinition(def) => {
+                    let type_name = self.type_definition(
+                        TypeKind::Object,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                    if let (Some(type_name), Some(fields)) = (type_name, def.fields_definition()) {
+                        self.fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::ObjectTypeExtension(ext) => {
+                    let type_name = self.type_extension(ext.name(), ext.directives());
+                    if let (Some(type_name), Some(fields)) = (type_name, ext.fields_definition()) {
+                        self.fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::InterfaceTypeDefinition(def) => {
+                    let type_name = self.type_definition(
+                        TypeKind::Interface,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                    if let (Some(type_name), Some(fields)) = (type_name, def.fields_definition()) {
+                        self.fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::InterfaceTypeExtension(ext) => {
+                    let type_name = self.type_extension(ext.name(), ext.directives());
+                    if let (Some(type_name), Some(fields)) = (type_name, ext.fields_definition()) {
+                        self.fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::InputObjectTypeDefinition(def) => {
+                    let type_name = self.type_definition(
+                        TypeKind::Input,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                    if let (Some(type_name), Some(fields)) =
+                        (type_name, def.input_fields_definition())
+                    {
+                        self.input_fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::InputObjectTypeExtension(ext) => {
+                    let type_name = self.type_extension(ext.name(), ext.directives());
+                    if let (Some(type_name), Some(fields)) =
+                        (type_name, ext.input_fields_definition())
+                    {
+                        self.input_fields(&type_name, fields);
+                    }
+                }
+                cst::Definition::EnumTypeDefinition(def) => {
+                    let type_name = self.type_definition(
+                        TypeKind::Enum,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                    if let (Some(type_name), Some(values)) =
+                        (type_name, def.enum_values_definition())
+                    {
+                        self.enum_values(&type_name, values);
+                    }
+                }
+                cst::Definition::EnumTypeExtension(ext) => {
+                    let type_name = self.type_extension(ext.name(), ext.directives());
+                    if let (Some(type_name), Some(values)) =
+                        (type_name, ext.enum_values_definition())
+                    {
+                        self.enum_values(&type_name, values);
+                    }
+                }
+                cst::Definition::UnionTypeDefinition(def) => {
+                    self.type_definition(
+                        TypeKind::Union,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                }
+                cst::Definition::ScalarTypeDefinition(def) => {
+                    self.type_definition(
+                        TypeKind::Scalar,
+                        def.name(),
+                        def.description(),
+                        def.directives(),
+                    );
+                }
+                _ => (),
+            }
+        }
+        self.violations
+            .sort_by_key(|violation| violation.start_byte_offset);
+        self.violations
+    }
+
+    fn type_definition(
+        &mut self,
+        kind: TypeKind,
+        name: Option<cst::Name>,
+        description: Option<cst::Description>,
+        directives: Option<cst::Directives>,
+    ) -> Option<String> {
+        let name = name?;
+        let type_name = name.text().to_string();
+        if is_built_in(&type_name) {
+            return None;
+        }
+
+        if !is_pascal_case(&type_name) {
+            self.report(
+                LintRule::TypeNamesShouldBePascalCase,
+                &type_name,
+                format!("Type name \"{}\" should be PascalCase.", type_name),
+                &name,
+            );
+        }
+
+        match kind {
+            TypeKind::Object if type_name.ends_with("Type") => self.report(
+                LintRule::TypeSuffix,
+                &type_name,
+                format!("Type name \"{}\" should not end with \"Type\".", type_name),
+                &name,
+            ),
+            TypeKind::Input if !type_name.ends_with("Input") => self.report(
+                LintRule::InputTypeSuffix,
+                &type_name,
+                format!(
+                    "Input type name \"{}\" should end with \"Input\".",
+                    type_name
+                ),
+                &name,
+            ),
+            TypeKind::Enum if type_name.ends_with("Enum") => self.report(
+                LintRule::EnumSuffix,
+                &type_name,
+                format!("Enum name \"{}\" should not end with \"Enum\".", type_name),
+                &name,
+            ),
+            TypeKind::Object
+            | TypeKind::Interface
+            | TypeKind::Input
+            | TypeKind::Enum
+            | TypeKind::Union
+            | TypeKind::Scalar => (),
+        }
+
+        self.description(&type_name, description, &name);
+        self.directives(&type_name, directives);
+        Some(type_name)
+    }
+
+    fn type_extension(
+        &mut self,
+        name: Option<cst::Name>,
+        directives: Option<cst::Directives>,
+    ) -> Option<String> {
+        let type_name = name?.text().to_string();
+        self.directives(&type_name, directives);
+        Some(type_name)
+    }
+
+    fn fields(&mut self, type_name: &str, fields: cst::FieldsDefinition) {
+        for field in fields.field_definitions() {
+            let Some(name) = field.name() else {
+                continue;
+            };
+            let field_name = name.text().to_string();
+            if is_built_in(&field_name) {
+                continue;
+            }
+            let coordinate = format!("{}.{}", type_name, field_name);
+
+            if !is_camel_case(&field_name) {
+                self.report(
+                    LintRule::FieldNamesShouldBeCamelCase,
+                    &coordinate,
+                    format!("Field \"{}\" should be camelCase.", coordinate),
+                    &name,
+                );
+            }
+            self.description(&coordinate, field.description(), &name);
+            self.directives(&coordinate, field.directives());
+
+            if let Some(arguments) = field.arguments_definition() {
+                for argument in arguments.input_value_definitions() {
+                    let Some(argument_name) = argument.name() else {
+                        continue;
+                    };
+                    let argument_coordinate = format!("{}({}:)", coordinate, argument_name.text());
+                    if !is_camel_case(&argument_name.text()) {
+                        self.report(
+                            LintRule::InputArgumentNamesShouldBeCamelCase,
+                            &argument_coordinate,
+                            format!("Argument \"{}\" should be camelCase.", argument_coordinate),
+                            &argument_name,
+                        );
+                    }
+                    self.description(&argument_coordinate, argument.description(), &argument_name);
+                    self.directives(&argument_coordinate, argument.directives());
+                }
+            }
+        }
+    }
+
+    fn input_fields(&mut self, type_name: &str, fields: cst::InputFieldsDefinition) {
+        for field in fields.input_value_definitions() {
+            let Some(name) = field.name() else {
+                continue;
+            };
+            let coordinate = format!("{}.{}", type_name, name.text());
+            if !is_camel_case(&name.text()) {
+                self.report(
+                    LintRule::FieldNamesShouldBeCamelCase,
+                    &coordinate,
+                    format!("Input field \"{}\" should be camelCase.", coordinate),
+                    &name,
+                );
+            }
+            self.description(&coordinate, field.description(), &name);
+            self.directives(&coordinate, field.directives());
+        }
+    }
+
+    fn enum_values(&mut self, type_name: &str, values: cst::EnumValuesDefinition) {
+        for value in values.enum_value_definitions() {
+            let Some(name) = value.enum_value().and_then(|value| value.name()) else {
+                continue;
+            };
+            let coordinate = format!("{}.{}", type_name, name.text());
+            if !is_screaming_snake_case(&name.text()) {
+                self.report(
+                    LintRule::EnumValuesShouldBeScreamingSnakeCase,
+                    &coordinate,
+                    format!(
+                        "Enum value \"{}\" should be SCREAMING_SNAKE_CASE.",
+                        coordinate
+                    ),
+                    &name,
+                );
+            }
+            self.description(&coordinate, value.description(), &name);
+            self.directives(&coordinate, value.directives());
+        }
+    }
+
+    fn description(
+        &mut self,
+        coordinate: &str,
+        description: Option<cst::Description>,
+        name: &cst::Name,
+    ) {
+        let has_description = description
+            .and_then(|description| description.string_value())
+            .is_some_and(|value| !String::from(value).trim().is_empty());
+        if !has_description {
+            self.report(
+                LintRule::AllElementsRequireDescription,
+                coordinate,
+                format!("Schema element {} is missing a description.", coordinate),
+                name,
+            );
+        }
+    }
+
+    fn directives(&mut self, coordinate: &str, directives: Option<cst::Directives>) {
+        let Some(directives) = directives else {
+            return;
+        };
+        for directive in directives.directives() {
+            let Some(directive_name) = directive.name() else {
+                continue;
+            };
+            let argument = |argument_name: &str| {
+                directive.arguments().and_then(|arguments| {
+                    arguments.arguments().find(|argument| {
+                        argument
+                            .name()
+                            .is_some_and(|name| &*name.text() == argument_name)
+                    })
+                })
+            };
+
+            match &*directive_name.text() {
+                "deprecated" => {
+                    let has_reason = match argument("reason").and_then(|reason| reason.value()) {
+                        Some(cst::Value::StringValue(reason)) => {
+                            !String::from(reason).trim().is_empty()
+                        }
+                        _ => false,
+                    };
+                    if !has_reason {
+                        self.report(
+                            LintRule::DeprecatedDirectiveMissingReason,
+                            coordinate,
+                            format!(
+                                "Deprecated element {} is missing a deprecation reason.",
+                                coordinate
+                            ),
+                            &directive,
+                        );
+                    }
+                }
+                "tag" => {
+                    if let Some(cst::Value::StringValue(tag)) =
+                        argument("name").and_then(|name| name.value())
+                    {
+                        let tag_name = String::from(&tag);
+                        if !self.tag_name_pattern.is_match(&tag_name) {
+                            self.report(
+                                LintRule::TagDirectiveInvalidName,
+                                coordinate,
+                                format!(
+                                    "Tag \"{}\" on {} does not match the pattern `{}`.",
+                                    tag_name,
+                                    coordinate,
+                                    self.tag_name_pattern.as_str()
+                                ),
+                                &tag,
+                            );
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn report(&mut self, rule: LintRule, coordinate: &str, message: String, node: &impl CstNode) {
+        let range = range(node);
+        self.violations.push(Violation {
+            rule,
+            coordinate: coordinate.to_string(),
+            message,
+            start_byte_offset: range.start,
+            end_byte_offset: range.end,
+        });
+    }
+}
+
+/// Names starting with `__` are reserved for introspection
+fn is_built_in(name: &str) -> bool {
+    name.starts_with("__")
+}
+
+// leading underscores are allowed so fields like federation's `_service` aren't reported
+fn is_camel_case(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_screaming_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::camel("userId", true)]
+    #[case::federation("_service", true)]
+    #[case::pascal("UserId", false)]
+    #[case::snake("user_id", false)]
+    fn it_detects_camel_case(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_camel_case(name), expected)
+    }
+
+    #[rstest]
+    #[case::pascal("UserProfile", true)]
+    #[case::camel("userProfile", false)]
+    #[case::snake("User_Profile", false)]
+    fn it_detects_pascal_case(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_pascal_case(name), expected)
+    }
+
+    #[rstest]
+    #[case::screaming("IN_STOCK", true)]
+    #[case::digits("SIZE_2XL", true)]
+    #[case::pascal("InStock", false)]
+    fn it_detects_screaming_snake_case(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_screaming_snake_case(name), expected)
+    }
+}
@@ -1,7 +1,14 @@
+pub mod auth_provider;
 pub mod client;
+pub mod crash_bundle;
+pub mod device_login;
 pub mod env;
+pub mod lint;
 pub mod parsers;
 pub mod pkg;
+pub mod project_config;
+pub mod schema_diff;
+pub mod shell;
 pub mod stringify;
 #[cfg(feature = "composition-js")]
 pub mod supergraph_config;
@@ -111,6 +111,22 @@ pub fn parse_header(header: &str) -> std::result::Result<(String, String), io::E
     }
 }
 
+/// Parses a name=path pair from a string and returns a tuple of the subgraph name
+/// and the [`FileDescriptorType`] of its schema.
+pub fn parse_subgraph_schema(
+    input: &str,
+) -> std::result::Result<(String, FileDescriptorType), io::Error> {
+    match input.split_once('=') {
+        Some((name, schema)) if !name.is_empty() => {
+            Ok((name.to_string(), FileDescriptorType::from_str(schema)?))
+        }
+        _ => {
+            let msg = format!("Could not parse \"name=path\" pair for provided subgraph schema: \"{}\". Subgraph schemas must be provided in name=path pairs, e.g. products=./products.graphql", input);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
@@ -119,7 +135,7 @@ mod tests {
     use assert_fs::prelude::*;
     use camino::Utf8PathBuf;
 
-    use super::FileDescriptorType;
+    use super::{parse_subgraph_schema, FileDescriptorType};
 
     #[test]
     fn it_correctly_parses_stdin_flag() {
@@ -194,4 +210,21 @@ mod tests {
         let schema_result = fd.read_file_descriptor("SDL", &mut input.as_bytes());
         assert!(schema_result.is_err())
     }
+
+    #[test]
+    fn it_parses_subgraph_schema_pairs() {
+        let (name, schema) = parse_subgraph_schema("products=./products.graphql").unwrap();
+        assert_eq!(name, "products");
+        assert_eq!(
+            schema,
+            FileDescriptorType::File(Utf8PathBuf::from("./products.graphql"))
+        );
+    }
+
+    #[test]
+    fn it_errs_on_malformed_subgraph_schema_pairs() {
+        assert!(parse_subgraph_schema("./products.graphql").is_err());
+        assert!(parse_subgraph_schema("=./products.graphql").is_err());
+        assert!(parse_subgraph_schema("products=").is_err());
+    }
 }
@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use apollo_parser::{
+    cst::{self, CstNode},
+    Parser,
+};
+use rover_std::Style;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// The types, fields, arguments, enum values and directives that were added, removed
+/// or changed between two schemas, by schema coordinate. Descriptions and the order of
+/// definitions are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaDiff {
+    changes: Vec<SchemaChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SchemaChange {
+    Added {
+        coordinate: String,
+        definition: String,
+    },
+    Removed {
+        coordinate: String,
+        definition: String,
+    },
+    Changed {
+        coordinate: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl SchemaChange {
+    fn coordinate(&self) -> &str {
+        match self {
+            SchemaChange::Added { coordinate, .. }
+            | SchemaChange::Removed { coordinate, .. }
+            | SchemaChange::Changed { coordinate, .. } => coordinate,
+        }
+    }
+}
+
+impl SchemaDiff {
+    pub fn between(old_sdl: &str, new_sdl: &str) -> SchemaDiff {
+        let old = elements(old_sdl);
+        let new = elements(new_sdl);
+
+        let removed: BTreeSet<&String> = old.keys().filter(|c| !new.contains_key(*c)).collect();
+        let added: BTreeSet<&String> = new.keys().filter(|c| !old.contains_key(*c)).collect();
+        // the members of an added or removed element are implied, so they aren't listed
+        let is_implied = |coordinate: &str, parents: &BTreeSet<&String>| {
+            parent(coordinate).is_some_and(|parent| parents.contains(&parent.to_string()))
+        };
+
+        let mut changes = Vec::new();
+        for (coordinate, definition) in &old {
+            match new.get(coordinate) {
+                None if !is_implied(coordinate, &removed) => changes.push(SchemaChange::Removed {
+                    coordinate: coordinate.clone(),
+                    definition: definition.clone(),
+                }),
+                Some(new_definition) if new_definition != definition => {
+                    changes.push(SchemaChange::Changed {
+                        coordinate: coordinate.clone(),
+                        from: definition.clone(),
+                        to: new_definition.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+        for coordinate in &added {
+            if !is_implied(coordinate, &added) {
+                changes.push(SchemaChange::Added {
+                    coordinate: coordinate.to_string(),
+                    definition: new[*coordinate].clone(),
+                })
+            }
+        }
+        changes.sort_by(|a, b| a.coordinate().cmp(b.coordinate()));
+        SchemaDiff { changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn get_stdout(&self) -> String {
+        if self.changes.is_empty() {
+            return "No structural changes".to_string();
+        }
+        self.changes
+            .iter()
+            .map(|change| match change {
+                SchemaChange::Added {
+                    coordinate,
+                    definition,
+                } => Style::Success.paint(format!("+ {}  {}", coordinate, definition)),
+                SchemaChange::Removed {
+                    coordinate,
+                    definition,
+                } => Style::Failure.paint(format!("- {}  {}", coordinate, definition)),
+                SchemaChange::Changed {
+                    coordinate,
+                    from,
+                    to,
+                } => Style::Pending.paint(format!("~ {}  {} -> {}", coordinate, from, to)),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn get_json(&self) -> Value {
+        json!({ "changes": self.changes })
+    }
+}
+
+/// The coordinate an element belongs to, `Type` for `Type.field` and `Type.field` for `Type.field(arg:)`
+fn parent(coordinate: &str) -> Option<&str> {
+    match coordinate.strip_suffix(":)") {
+        Some(argument) => argument.rsplit_once('(').map(|(parent, _)| parent),
+        None => coordinate.rsplit_once('.').map(|(parent, _)| parent),
+    }
+}
+
+/// Every element of a schema by coordinate, mapped to its definition without its description
+fn elements(sdl: &str) -> BTreeMap<String, String> {
+    let mut elements = BTreeMap::new();
+    let document = Parser::new(sdl).parse().document();
+    for definition in document.definitions() {
+        match definition {
+            cst::Definition::SchemaDefinition(schema) => {
+                elements.insert(
+                    "schema".to_string(),
+                    format!(
+                        "schema{} {{ {} }}",
+                        prefixed(schema.directives()),
+                        schema
+                            .root_operation_type_definitions()
+                            .map(|root| text(&root))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    ),
+                );
+            }
+            cst::Definition::ObjectTypeDefinition(object) => {
+                if let Some(name) = object.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!(
+                            "type {}{}{}",
+                            name,
+                            prefixed(object.implements_interfaces()),
+                            prefixed(object.directives())
+                        ),
+                    );
+                    insert_fields(&mut elements, &name, object.fields_definition());
+                }
+            }
+            cst::Definition::ObjectTypeExtension(object) => {
+                if let Some(name) = object.name() {
+                    insert_fields(&mut elements, &name.text(), object.fields_definition());
+                }
+            }
+            cst::Definition::InterfaceTypeDefinition(interface) => {
+                if let Some(name) = interface.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!(
+                            "interface {}{}{}",
+                            name,
+                            prefixed(interface.implements_interfaces()),
+                            prefixed(interface.directives())
+                        ),
+                    );
+                    insert_fields(&mut elements, &name, interface.fields_definition());
+                }
+            }
+            cst::Definition::InterfaceTypeExtension(interface) => {
+                if let Some(name) = interface.name() {
+                    insert_fields(&mut elements, &name.text(), interface.fields_definition());
+                }
+            }
+            cst::Definition::InputObjectTypeDefinition(input) => {
+                if let Some(name) = input.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!("input {}{}", name, prefixed(input.directives())),
+                    );
+                    for field in input
+                        .input_fields_definition()
+                        .into_iter()
+                        .flat_map(|fields| fields.input_value_definitions())
+                    {
+                        insert_input_value(&mut elements, &name, ".", "", &field);
+                    }
+                }
+            }
+            cst::Definition::EnumTypeDefinition(enum_type) => {
+                if let Some(name) = enum_type.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!("enum {}{}", name, prefixed(enum_type.directives())),
+                    );
+                    for value in enum_type
+                        .enum_values_definition()
+                        .into_iter()
+                        .flat_map(|values| values.enum_value_definitions())
+                    {
+                        if let Some(enum_value) = value.enum_value() {
+                            let value_name = text(&enum_value);
+                            elements.insert(
+                                format!("{}.{}", name, value_name),
+                                format!("{}{}", value_name, prefixed(value.directives())),
+                            );
+                        }
+                    }
+                }
+            }
+            cst::Definition::UnionTypeDefinition(union) => {
+                if let Some(name) = union.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!(
+                            "union {}{}{}",
+                            name,
+                            prefixed(union.directives()),
+                            union
+                                .union_member_types()
+                                .map(|members| format!(" {}", text(&members)))
+                                .unwrap_or_default()
+                        ),
+                    );
+                }
+            }
+            cst::Definition::ScalarTypeDefinition(scalar) => {
+                if let Some(name) = scalar.name() {
+                    let name = name.text().to_string();
+                    elements.insert(
+                        name.clone(),
+                        format!("scalar {}{}", name, prefixed(scalar.directives())),
+                    );
+                }
+            }
+            cst::Definition::DirectiveDefinition(directive) => {
+                if let Some(name) = directive.name() {
+                    let coordinate = format!("@{}", name.text());
+                    elements.insert(
+                        coordinate.clone(),
+                        format!(
+                            "directive {}{}{}",
+                            coordinate,
+                            if directive.repeatable_token().is_some() {
+                                " repeatable"
+                            } else {
+                                ""
+                            },
+                            prefixed(directive.directive_locations())
+                        ),
+                    );
+                    for argument in directive
+                        .arguments_definition()
+                        .into_iter()
+                        .flat_map(|arguments| arguments.input_value_definitions())
+                    {
+                        insert_input_value(&mut elements, &coordinate, "(", ":)", &argument);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    elements
+}
+
+fn insert_fields(
+    elements: &mut BTreeMap<String, String>,
+    type_name: &str,
+    fields: Option<cst::FieldsDefinition>,
+) {
+    for field in fields
+        .into_iter()
+        .flat_map(|fields| fields.field_definitions())
+    {
+        let Some(name) = field.name() else {
+            continue;
+        };
+        let coordinate = format!("{}.{}", type_name, name.text());
+        elements.insert(
+            coordinate.clone(),
+            format!(
+                "{}: {}{}",
+                name.text(),
+                field.ty().map(|ty| text(&ty)).unwrap_or_default(),
+                prefixed(field.directives())
+            ),
+        );
+        for argument in field
+            .arguments_definition()
+            .into_iter()
+            .flat_map(|arguments| arguments.input_value_definitions())
+        {
+            insert_input_value(elements, &coordinate, "(", ":)", &argument);
+        }
+    }
+}
+
+fn insert_input_value(
+    elements: &mut BTreeMap<String, String>,
+    parent: &str,
+    separator: &str,
+    suffix: &str,
+    input_value: &cst::InputValueDefinition,
+) {
+    if let Some(name) = input_value.name() {
+        elements.insert(
+            format!("{}{}{}{}", parent, separator, name.text(), suffix),
+            format!(
+                "{}: {}{}{}",
+                name.text(),
+                input_value.ty().map(|ty| text(&ty)).unwrap_or_default(),
+                input_value
+                    .default_value()
+                    .map(|default| format!(" {}", text(&default)))
+                    .unwrap_or_default(),
+                prefixed(input_value.directives())
+            ),
+        );
+    }
+}
+
+/// The source text of a node with its whitespace collapsed
+fn text(node: &impl CstNode) -> String {
+    node.syntax()
+        .text()
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The text of an optional node, preceded by a space if it's present
+fn prefixed(node: Option<impl CstNode>) -> String {
+    node.map(|node| format!(" {}", text(&node)))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const OLD: &str = indoc! {r#"
+        type Query {
+          "All products"
+          products(first: Int = 10): [Product!]!
+          legacy: String
+        }
+
+        type Product {
+          id: ID!
+          name: String
+        }
+
+        enum Status {
+          ACTIVE
+          RETIRED
+        }
+    "#};
+
+    #[test]
+    fn it_reports_structural_changes() {
+        let new = indoc! {r#"
+            enum Status {
+              ACTIVE
+              RETIRED @deprecated
+            }
+
+            type Query {
+              "Every product"
+              products(first: Int = 20, after: String): [Product!]!
+              review(id: ID!): Review
+            }
+
+            type Product {
+              id: ID!
+              name: String!
+            }
+
+            type Review {
+              id: ID!
+              body: String
+            }
+        "#};
+
+        let diff = SchemaDiff::between(OLD, new);
+        let summary: Vec<String> = diff
+            .changes
+            .iter()
+            .map(|change| match change {
+                SchemaChange::Added { coordinate, .. } => format!("+ {coordinate}"),
+                SchemaChange::Removed { coordinate, .. } => format!("- {coordinate}"),
+                SchemaChange::Changed { coordinate, .. } => format!("~ {coordinate}"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "~ Product.name",
+                "- Query.legacy",
+                "+ Query.products(after:)",
+                "~ Query.products(first:)",
+                "+ Query.review",
+                "+ Review",
+                "~ Status.RETIRED",
+            ]
+        );
+        assert_eq!(
+            diff.changes[0],
+            SchemaChange::Changed {
+                coordinate: "Product.name".to_string(),
+                from: "name: String".to_string(),
+                to: "name: String!".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn it_ignores_descriptions_and_ordering() {
+        let reordered = indoc! {r#"
+            enum Status { ACTIVE RETIRED }
+            type Product { id: ID! name: String }
+            type Query {
+              products(first: Int = 10): [Product!]!
+              legacy: String
+            }
+        "#};
+        assert!(SchemaDiff::between(OLD, reordered).is_empty());
+    }
+
+    #[test]
+    fn it_finds_parents() {
+        assert_eq!(parent("Query.products(first:)"), Some("Query.products"));
+        assert_eq!(parent("@key(fields:)"), Some("@key"));
+        assert_eq!(parent("Query.products"), Some("Query"));
+        assert_eq!(parent("Query"), None);
+    }
+}
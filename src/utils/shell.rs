@@ -0,0 +1,14 @@
+use std::process::Command;
+
+/// A command that runs `script` with the platform's shell, `sh -c` or `cmd /C`
+pub fn command(script: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", script]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", script]);
+        shell
+    }
+}
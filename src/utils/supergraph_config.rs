@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use anyhow::anyhow;
@@ -8,7 +9,7 @@ use apollo_federation_types::config::{
 use apollo_parser::{cst, Parser};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use rover_client::blocking::{GraphQLClient, StudioClient};
+use rover_client::blocking::{GraphQLClient, GraphQLTransport, StudioClient};
 use rover_client::operations::subgraph;
 use rover_client::operations::subgraph::fetch::SubgraphFetchInput;
 use rover_client::operations::subgraph::fetch_all::SubgraphFetchAllInput;
@@ -19,6 +20,7 @@ use rover_client::RoverClientError;
 use rover_std::{Emoji, Fs, Style};
 
 use crate::options::ProfileOpt;
+use crate::utils::auth_provider::{send_with_auth, AuthProvider, AuthProviderConfig};
 use crate::utils::client::StudioClientConfig;
 use crate::utils::expansion::expand;
 use crate::utils::parsers::FileDescriptorType;
@@ -133,6 +135,7 @@ mod test_get_supergraph_config {
     use speculoos::prelude::OptionAssertions;
 
     use houston::Config;
+    use rover_client::blocking::RetryPolicy;
     use rover_client::shared::GraphRef;
 
     use crate::options::ProfileOpt;
@@ -307,7 +310,7 @@ mod test_get_supergraph_config {
             config,
             false,
             ClientBuilder::default(),
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let actual_result = if let Some(name) = local_subgraph {
@@ -389,7 +392,7 @@ pub(crate) fn resolve_supergraph_yaml(
         err.set_suggestion(RoverErrorSuggestion::ValidComposeRoutingUrl);
         err
     };
-    let supergraph_config = unresolved_supergraph_yaml
+    let (supergraph_config, introspection_settings) = unresolved_supergraph_yaml
         .read_file_descriptor("supergraph config", &mut std::io::stdin())
         .and_then(|contents| expand_supergraph_yaml(&contents))?;
     let maybe_specified_federation_version = supergraph_config.get_federation_version();
@@ -434,24 +437,39 @@ pub(crate) fn resolve_supergraph_yaml(
                         subgraph_url,
                         introspection_headers,
                     } => {
+                        let settings = introspection_settings
+                            .get(&subgraph_name)
+                            .cloned()
+                            .unwrap_or_default();
                         client_config
-                            .get_reqwest_client()
-                            .map_err(RoverError::from)
+                            .check_transport(settings.transport)
+                            .and_then(|_| {
+                                client_config.get_reqwest_client().map_err(RoverError::from)
+                            })
                             .and_then(|reqwest_client| {
+                                let auth = settings.auth.map(|config| {
+                                    AuthProvider::new(config, reqwest_client.clone())
+                                });
                                 let client = GraphQLClient::new(
                                     subgraph_url.as_ref(),
                                     reqwest_client,
-                                    client_config.retry_period,
-                                );
+                                    client_config.retry_policy.clone(),
+                                )
+                                .with_transport(settings.transport)
+                                .with_timeout(client_config.client_timeout());
 
                                 // given a federated introspection URL, use subgraph introspect to
                                 // obtain SDL and add it to subgraph_definition.
-                                introspect::run(
-                                    SubgraphIntrospectInput {
-                                        headers: introspection_headers.clone().unwrap_or_default(),
+                                send_with_auth(
+                                    auth.as_ref(),
+                                    introspection_headers.clone().unwrap_or_default(),
+                                    |headers| {
+                                        introspect::run(
+                                            SubgraphIntrospectInput { headers },
+                                            &client,
+                                            false,
+                                        )
                                     },
-                                    &client,
-                                    false,
                                 )
                                 .map(|introspection_response| {
                                     let schema = introspection_response.result;
@@ -464,7 +482,6 @@ pub(crate) fn resolve_supergraph_yaml(
                                         .unwrap_or_else(|| subgraph_url.to_string());
                                     SubgraphDefinition::new(subgraph_name, url, schema)
                                 })
-                                .map_err(RoverError::from)
                             })
                     }
                     SchemaSource::Subgraph {
@@ -623,11 +640,78 @@ pub(crate) fn resolve_supergraph_yaml(
     Ok(resolved_supergraph_config)
 }
 
-fn expand_supergraph_yaml(content: &str) -> RoverResult<SupergraphConfig> {
-    serde_yaml::from_str(content)
+/// Rover-only settings alongside a subgraph's `subgraph_url`, which are removed before
+/// the rest of the file is read as a [`SupergraphConfig`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct IntrospectionSettings {
+    transport: GraphQLTransport,
+    auth: Option<AuthProviderConfig>,
+}
+
+fn expand_supergraph_yaml(
+    content: &str,
+) -> RoverResult<(SupergraphConfig, HashMap<String, IntrospectionSettings>)> {
+    let mut expanded = serde_yaml::from_str(content)
         .map_err(RoverError::from)
-        .and_then(expand)
-        .and_then(|v| serde_yaml::from_value(v).map_err(RoverError::from))
+        .and_then(expand)?;
+    let introspection_settings = take_introspection_settings(&mut expanded)?;
+    let supergraph_config = serde_yaml::from_value(expanded)?;
+    Ok((supergraph_config, introspection_settings))
+}
+
+fn take_introspection_settings(
+    supergraph_yaml: &mut serde_yaml::Value,
+) -> RoverResult<HashMap<String, IntrospectionSettings>> {
+    let mut introspection_settings = HashMap::new();
+    let Some(subgraphs) = supergraph_yaml
+        .get_mut("subgraphs")
+        .and_then(serde_yaml::Value::as_mapping_mut)
+    else {
+        return Ok(introspection_settings);
+    };
+    for (subgraph_name, subgraph) in subgraphs.iter_mut() {
+        let (Some(subgraph_name), Some(schema)) = (
+            subgraph_name.as_str(),
+            subgraph
+                .get_mut("schema")
+                .and_then(serde_yaml::Value::as_mapping_mut),
+        ) else {
+            continue;
+        };
+        let invalid_setting = |setting: &str, error: serde_yaml::Error, suggestion: &str| {
+            let mut err = RoverError::new(anyhow!(
+                "Invalid `{}` for the '{}' subgraph: {}",
+                setting,
+                subgraph_name,
+                error
+            ));
+            err.set_suggestion(RoverErrorSuggestion::Adhoc(suggestion.to_string()));
+            err
+        };
+        let mut settings = IntrospectionSettings::default();
+        if let Some(transport) = schema.remove("introspection_transport") {
+            settings.transport = serde_yaml::from_value(transport).map_err(|e| {
+                invalid_setting(
+                    "introspection_transport",
+                    e,
+                    "Set `introspection_transport` to one of `post`, `get` or `websocket`.",
+                )
+            })?;
+        }
+        if let Some(auth) = schema.remove("introspection_auth") {
+            settings.auth = Some(serde_yaml::from_value(auth).map_err(|e| {
+                invalid_setting(
+                    "introspection_auth",
+                    e,
+                    "Set `introspection_auth.type` to `client_credentials` with a `token_url`, `client_id` and `client_secret`, or to `command` with a `command`.",
+                )
+            })?);
+        }
+        if settings != IntrospectionSettings::default() {
+            introspection_settings.insert(subgraph_name.to_string(), settings);
+        }
+    }
+    Ok(introspection_settings)
 }
 
 #[cfg(test)]
@@ -652,6 +736,7 @@ mod test_resolve_supergraph_yaml {
     use speculoos::prelude::{ResultAssertions, VecAssertions};
 
     use houston::Config;
+    use rover_client::blocking::RetryPolicy;
 
     use crate::options::ProfileOpt;
     use crate::utils::client::{ClientBuilder, StudioClientConfig};
@@ -695,7 +780,7 @@ mod test_resolve_supergraph_yaml {
             config,
             false,
             ClientBuilder::default(),
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         )
     }
 
@@ -725,13 +810,53 @@ mod test_resolve_supergraph_yaml {
             subgraphs:
 "#
         };
-        let config = super::expand_supergraph_yaml(yaml).unwrap();
+        let (config, _) = super::expand_supergraph_yaml(yaml).unwrap();
         assert_eq!(
             config.get_federation_version(),
             Some(FederationVersion::LatestFedOne)
         );
     }
 
+    #[test]
+    fn test_supergraph_yaml_introspection_settings() {
+        let yaml = indoc! {r#"
+            subgraphs:
+              cdn:
+                schema:
+                  subgraph_url: https://cdn.example.com/graphql
+                  introspection_transport: get
+              legacy:
+                schema:
+                  subgraph_url: https://legacy.example.com/graphql
+                  introspection_transport: websocket
+                  introspection_auth:
+                    type: command
+                    command: ./mint-token.sh
+                    expires_in: 3600
+              films:
+                schema:
+                  subgraph_url: https://films.example.com/graphql
+"#
+        };
+        let (config, settings) = super::expand_supergraph_yaml(yaml).unwrap();
+        assert_eq!(config.into_iter().count(), 3);
+        assert_eq!(settings.len(), 2);
+        assert_eq!(settings["cdn"].transport, GraphQLTransport::Get);
+        assert_eq!(settings["legacy"].transport, GraphQLTransport::WebSocket);
+        assert_eq!(
+            settings["legacy"].auth,
+            Some(AuthProviderConfig::Command {
+                command: "./mint-token.sh".to_string(),
+                expires_in: Some(3600),
+            })
+        );
+
+        let invalid = yaml.replace("transport: get", "transport: put");
+        assert!(super::expand_supergraph_yaml(&invalid).is_err());
+        let invalid = yaml.replace("type: command", "type: password");
+        assert!(super::expand_supergraph_yaml(&invalid).is_err());
+    }
+
     #[rstest]
     fn it_errs_on_invalid_subgraph_path(
         client_config: StudioClientConfig,
@@ -1088,7 +1213,7 @@ type _Service {\n  sdl: String\n}"#;
             config,
             false,
             ClientBuilder::default(),
-            Some(Duration::from_secs(3)),
+            RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(3)),
         );
 
         let mut supergraph_config_path = tempfile::NamedTempFile::new()?;
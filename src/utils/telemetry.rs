@@ -1,4 +1,6 @@
+use anyhow::anyhow;
 use camino::Utf8PathBuf;
+use houston::Config;
 use reqwest::blocking::Client;
 use url::Url;
 
@@ -10,6 +12,11 @@ use std::collections::HashMap;
 
 const TELEMETRY_URL: &str = "https://rover.apollo.dev/telemetry";
 
+/// Where copies of the most recent usage reports are kept, for `rover telemetry show`
+pub(crate) fn telemetry_spool_path(config: &Config) -> Utf8PathBuf {
+    config.home.join("telemetry.jsonl")
+}
+
 fn get_command_from_args(raw_arguments: &mut serde_json::Value) -> Command {
     let mut commands = Vec::new();
     let mut arguments = HashMap::new();
@@ -117,7 +124,15 @@ impl Report for Rover {
     }
 
     fn client(&self) -> Result<Client, SputnikError> {
-        self.get_reqwest_client().map_err(SputnikError::from)
+        self.get_reqwest_client()
+            .map_err(|e| SputnikError::AdhocError(anyhow!(e.message())))
+    }
+
+    fn spool_path(&self) -> Result<Option<Utf8PathBuf>, SputnikError> {
+        let config = self
+            .get_rover_config()
+            .map_err(|_| SputnikError::ConfigError)?;
+        Ok(Some(telemetry_spool_path(&config)))
     }
 }
 
@@ -6,7 +6,7 @@ use camino::Utf8PathBuf;
 use reqwest::blocking::Client;
 
 use rover_client::{
-    blocking::GraphQLClient,
+    blocking::{GraphQLClient, RetryPolicy},
     operations::graph::introspect::{self, GraphIntrospectInput},
 };
 use rover_std::Fs;
@@ -37,11 +37,12 @@ fn introspect() -> Result<String> {
     let graphql_client = GraphQLClient::new(
         graphql_endpoint,
         Client::new(),
-        Some(Duration::from_secs(10)),
+        RetryPolicy::default().with_max_elapsed_time(Duration::from_secs(10)),
     );
     introspect::run(
         GraphIntrospectInput {
             headers: HashMap::new(),
+            options: Default::default(),
         },
         &graphql_client,
         false,